@@ -0,0 +1,607 @@
+//! This module provides the audio processing unit of the 2A03, which is mapped to $4000-$4013, $4015 and $4017.
+//!
+//! Every channel is implemented in its own submodule. The units the channels share, the envelope and the length counter, live here together with the frame sequencer that clocks them and the mixer.
+
+mod dmc;
+mod noise;
+mod pulse;
+mod resampler;
+mod triangle;
+
+use dmc::Dmc;
+use noise::Noise;
+use pulse::Pulse;
+use resampler::Resampler;
+use std::sync::mpsc::Sender;
+use triangle::Triangle;
+
+/// Clock rate of the NTSC CPU in Hz, the APU runs one cycle per CPU cycle.
+pub const CPU_CLOCK: u32 = 1_789_773;
+
+/// Clock rate of the PAL CPU in Hz.
+pub const PAL_CPU_CLOCK: u32 = 1_662_607;
+
+/// Number of samples that are sent to the output at once.
+const SAMPLE_CHUNK: usize = 1024;
+
+/// Lengths that are loaded into a length counter, indexed by the upper 5 bits of the last channel register.
+const LENGTH_TABLE: [u8; 32] = [
+    10, 254, 20, 2, 40, 4, 80, 6, 160, 8, 60, 10, 14, 12, 26, 14, 12, 16, 24, 18, 48, 20, 96, 22,
+    192, 24, 72, 26, 16, 28, 32, 30,
+];
+
+/// What a step of the frame sequencer does.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+enum FrameStep {
+    /// Clocks the envelopes and the linear counter.
+    Quarter,
+    /// Clocks the length counters and the sweeps as well.
+    Half,
+    /// Raises the frame interrupt.
+    Irq,
+    /// Clocks everything and raises the frame interrupt.
+    HalfIrq,
+    /// Does nothing.
+    None,
+}
+
+/// CPU cycles of the frame sequencer steps for the 4-step and the 5-step mode. The sequencer starts over at the last step.
+const FRAME_STEPS: [[(u32, FrameStep); 6]; 2] = [
+    [
+        (7457, FrameStep::Quarter),
+        (14913, FrameStep::Half),
+        (22371, FrameStep::Quarter),
+        (29828, FrameStep::Irq),
+        (29829, FrameStep::HalfIrq),
+        (29830, FrameStep::Irq),
+    ],
+    [
+        (7457, FrameStep::Quarter),
+        (14913, FrameStep::Half),
+        (22371, FrameStep::Quarter),
+        (29829, FrameStep::None),
+        (37281, FrameStep::Half),
+        (37282, FrameStep::None),
+    ],
+];
+
+/// Volume envelope of the pulse and noise channels, which either decays from 15 or holds a constant volume.
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct Envelope {
+    /// Indicates whether the volume is constant instead of decaying.
+    pub constant: bool,
+    /// Indicates whether the decay starts over at 15 after reaching 0.
+    pub looping: bool,
+    /// Constant volume, or the period of the decay divider.
+    pub volume: u8,
+    /// Indicates whether the decay restarts on the next quarter frame.
+    pub start: bool,
+    /// Counter of the decay divider.
+    pub divider: u8,
+    /// Current decay level.
+    pub decay: u8,
+}
+
+impl Envelope {
+    /// Sets the flags and the volume from the first register of a channel (`--LC VVVV`).
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The byte written to the register.
+    ///
+    /// Nothing is returned.
+    pub fn write(&mut self, data: u8) {
+        self.looping = data & 0b10_0000 != 0;
+        self.constant = data & 0b1_0000 != 0;
+        self.volume = data & 0b1111;
+    }
+
+    /// Clocks the envelope on a quarter frame.
+    ///
+    /// Nothing is returned.
+    pub fn clock(&mut self) {
+        if self.start {
+            self.start = false;
+            self.decay = 15;
+            self.divider = self.volume;
+        } else if self.divider == 0 {
+            self.divider = self.volume;
+            if self.decay > 0 {
+                self.decay -= 1;
+            } else if self.looping {
+                self.decay = 15;
+            }
+        } else {
+            self.divider -= 1;
+        }
+    }
+
+    /// Returns the volume of the envelope.
+    ///
+    /// # Return
+    /// * `u8` - The volume (0-15).
+    pub fn output(&self) -> u8 {
+        if self.constant {
+            self.volume
+        } else {
+            self.decay
+        }
+    }
+}
+
+/// Length counter, which silences a channel once the duration that was written to it has passed.
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct LengthCounter {
+    /// Indicates whether the channel is enabled through $4015, a disabled counter stays 0.
+    pub enabled: bool,
+    /// Indicates whether the counter is halted.
+    pub halt: bool,
+    /// Remaining number of half frames.
+    pub counter: u8,
+}
+
+impl LengthCounter {
+    /// Loads the counter from the upper 5 bits of the last register of a channel, if the channel is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The byte written to the register.
+    ///
+    /// Nothing is returned.
+    pub fn load(&mut self, data: u8) {
+        if self.enabled {
+            self.counter = LENGTH_TABLE[(data >> 3) as usize];
+        }
+    }
+
+    /// Enables or disables the channel, disabling clears the counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the channel is enabled.
+    ///
+    /// Nothing is returned.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.counter = 0;
+        }
+    }
+
+    /// Clocks the counter on a half frame.
+    ///
+    /// Nothing is returned.
+    pub fn clock(&mut self) {
+        if self.counter > 0 && !self.halt {
+            self.counter -= 1;
+        }
+    }
+
+    /// Returns whether the counter has not run out, which is when the channel can be heard.
+    ///
+    /// # Return
+    /// * `bool` - Whether the counter is not 0.
+    pub fn active(&self) -> bool {
+        self.counter > 0
+    }
+}
+
+/// The channels of the APU with the frame sequencer and the mixer.
+#[derive(Debug)]
+pub struct Apu {
+    /// First pulse channel ($4000-$4003).
+    pub pulse1: Pulse,
+    /// Second pulse channel ($4004-$4007).
+    pub pulse2: Pulse,
+    /// Triangle channel ($4008-$400B).
+    pub triangle: Triangle,
+    /// Noise channel ($400C-$400F).
+    pub noise: Noise,
+    /// Delta modulation channel ($4010-$4013).
+    pub dmc: Dmc,
+    /// Number of CPU cycles since the frame sequencer started over.
+    pub frame_cycle: u32,
+    /// Indicates whether the frame sequencer runs the 5-step sequence, which has no interrupt.
+    pub five_step: bool,
+    /// Indicates whether the frame interrupt is disabled.
+    pub irq_inhibit: bool,
+    /// Indicates whether the frame interrupt is pending.
+    pub frame_irq: bool,
+    /// Mode written to $4017 that takes effect when `frame_reset_delay` runs out.
+    pub pending_five_step: bool,
+    /// Number of CPU cycles until the frame sequencer restarts after a write to $4017, 0 if no restart is pending.
+    pub frame_reset_delay: u8,
+    /// Indicates whether the current CPU cycle is the second half of an APU cycle, on which the pulse and noise timers are clocked.
+    pub odd_cycle: bool,
+    /// Converts the mixer output to the sample rate of `output`.
+    pub resampler: Resampler,
+    /// Receives chunks of samples between -1.0 and 1.0, `None` if no one listens and no samples are produced.
+    pub output: Option<Sender<Vec<f32>>>,
+}
+
+impl Default for Apu {
+    fn default() -> Self {
+        Apu {
+            pulse1: Pulse::new(true),
+            pulse2: Pulse::new(false),
+            triangle: Triangle::default(),
+            noise: Noise::default(),
+            dmc: Dmc::default(),
+            frame_cycle: 0,
+            five_step: false,
+            irq_inhibit: false,
+            frame_irq: false,
+            pending_five_step: false,
+            frame_reset_delay: 0,
+            odd_cycle: false,
+            resampler: Resampler::default(),
+            output: None,
+        }
+    }
+}
+
+impl Apu {
+    /// Writes one of the APU registers.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address of the register ($4000-$4013, $4015 or $4017).
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    pub fn write_register(&mut self, addr: u16, data: u8) {
+        let register = addr & 0b11;
+        match addr {
+            0x4000..=0x4003 => self.pulse1.write_register(register, data),
+            0x4004..=0x4007 => self.pulse2.write_register(register, data),
+            0x4008..=0x400b => self.triangle.write_register(register, data),
+            0x400c..=0x400f => self.noise.write_register(register, data),
+            0x4010..=0x4013 => self.dmc.write_register(register, data),
+            0x4015 => {
+                self.pulse1.length.set_enabled(data & 0b1 != 0);
+                self.pulse2.length.set_enabled(data & 0b10 != 0);
+                self.triangle.length.set_enabled(data & 0b100 != 0);
+                self.noise.length.set_enabled(data & 0b1000 != 0);
+                self.dmc.set_enabled(data & 0b1_0000 != 0);
+            }
+            0x4017 => {
+                self.irq_inhibit = data & 0b100_0000 != 0;
+                if self.irq_inhibit {
+                    self.frame_irq = false;
+                }
+                self.pending_five_step = data & 0b1000_0000 != 0;
+                // The sequencer restarts on an APU cycle boundary
+                self.frame_reset_delay = if self.odd_cycle { 4 } else { 3 };
+            }
+            _ => {}
+        }
+    }
+
+    /// Puts the channels and the frame sequencer in their power-on state. The resampler and the output stay.
+    ///
+    /// Nothing is returned.
+    pub fn power_on(&mut self) {
+        *self = Apu {
+            resampler: std::mem::take(&mut self.resampler),
+            output: self.output.take(),
+            ..Default::default()
+        };
+    }
+
+    /// Resets the APU with the reset button. The channels are silenced and the frame sequencer restarts in the mode last written to $4017.
+    ///
+    /// Nothing is returned.
+    pub fn reset(&mut self) {
+        self.write_register(0x4015, 0);
+        self.frame_irq = false;
+        let mode = (self.five_step as u8) << 7 | (self.irq_inhibit as u8) << 6;
+        self.write_register(0x4017, mode);
+    }
+
+    /// Reads the status register ($4015), which tells which length counters are running and which interrupts are pending. Reading it acknowledges the frame interrupt.
+    ///
+    /// # Return
+    /// * `u8` - The status, bit 5 is not driven by the APU.
+    pub fn read_status(&mut self) -> u8 {
+        let status = self.pulse1.length.active() as u8
+            | (self.pulse2.length.active() as u8) << 1
+            | (self.triangle.length.active() as u8) << 2
+            | (self.noise.length.active() as u8) << 3
+            | (self.dmc.active() as u8) << 4
+            | (self.frame_irq as u8) << 6
+            | (self.dmc.irq as u8) << 7;
+        self.frame_irq = false;
+        status
+    }
+
+    /// Returns whether the APU pulls the IRQ line low.
+    ///
+    /// # Return
+    /// * `bool` - Whether an interrupt of the APU is pending.
+    pub fn irq(&self) -> bool {
+        self.frame_irq || self.dmc.irq
+    }
+
+    /// Runs the APU for one CPU cycle. Sample bytes the DMC needs are fetched by the bus through `Dmc::sample_request`.
+    ///
+    /// Nothing is returned.
+    pub fn tick(&mut self) {
+        self.clock_frame_sequencer();
+        self.triangle.clock_timer();
+        self.noise.clock_timer();
+        self.dmc.clock_timer();
+        if self.odd_cycle {
+            self.pulse1.clock_timer();
+            self.pulse2.clock_timer();
+        }
+        self.odd_cycle = !self.odd_cycle;
+        if self.output.is_some() {
+            let mix = self.mix();
+            self.push_sample(mix);
+        }
+    }
+
+    /// Advances the frame sequencer and clocks the envelopes, counters and sweeps on its steps. A write to $4017 restarts it after a short delay, the 5-step mode clocks everything right away.
+    ///
+    /// Nothing is returned.
+    fn clock_frame_sequencer(&mut self) {
+        if self.frame_reset_delay > 0 {
+            self.frame_reset_delay -= 1;
+            if self.frame_reset_delay == 0 {
+                self.five_step = self.pending_five_step;
+                self.frame_cycle = 0;
+                if self.five_step {
+                    self.clock_quarter_frame();
+                    self.clock_half_frame();
+                }
+                return;
+            }
+        }
+
+        self.frame_cycle += 1;
+        let steps = &FRAME_STEPS[self.five_step as usize];
+        let index = match steps.iter().position(|&(c, _)| c == self.frame_cycle) {
+            Some(index) => index,
+            None => return,
+        };
+        let step = steps[index].1;
+        if step != FrameStep::Irq && step != FrameStep::None {
+            self.clock_quarter_frame();
+        }
+        if step == FrameStep::Half || step == FrameStep::HalfIrq {
+            self.clock_half_frame();
+        }
+        if (step == FrameStep::Irq || step == FrameStep::HalfIrq) && !self.irq_inhibit {
+            self.frame_irq = true;
+        }
+        if index == steps.len() - 1 {
+            self.frame_cycle = 0;
+        }
+    }
+
+    /// Clocks the envelopes and the linear counter of the triangle.
+    ///
+    /// Nothing is returned.
+    fn clock_quarter_frame(&mut self) {
+        self.pulse1.envelope.clock();
+        self.pulse2.envelope.clock();
+        self.triangle.clock_linear_counter();
+        self.noise.envelope.clock();
+    }
+
+    /// Clocks the length counters and the sweeps of the pulse channels.
+    ///
+    /// Nothing is returned.
+    fn clock_half_frame(&mut self) {
+        self.pulse1.clock_half_frame();
+        self.pulse2.clock_half_frame();
+        self.triangle.length.clock();
+        self.noise.length.clock();
+    }
+
+    /// Combines the outputs of the channels like the resistor network of the 2A03, which is not linear.
+    ///
+    /// # Return
+    /// * `f32` - The output level between 0.0 and 1.0.
+    pub fn mix(&self) -> f32 {
+        let pulse = (self.pulse1.output() + self.pulse2.output()) as f32;
+        let pulse_out = if pulse == 0.0 {
+            0.0
+        } else {
+            95.88 / (8128.0 / pulse + 100.0)
+        };
+        let tnd = self.triangle.output() as f32 / 8227.0
+            + self.noise.output() as f32 / 12241.0
+            + self.dmc.output as f32 / 22638.0;
+        let tnd_out = if tnd == 0.0 {
+            0.0
+        } else {
+            159.79 / (1.0 / tnd + 100.0)
+        };
+        pulse_out + tnd_out
+    }
+
+    /// Changes the rate of the samples that are sent to `output`. The rate control handle stays the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock_rate` - The CPU clock of the console in Hz, `CPU_CLOCK` or `PAL_CPU_CLOCK`.
+    /// * `sample_rate` - The rate in Hz.
+    ///
+    /// Nothing is returned.
+    pub fn set_sample_rate(&mut self, clock_rate: u32, sample_rate: u32) {
+        let rate_control = self.resampler.rate_control.clone();
+        self.resampler = Resampler::new(clock_rate as f64, sample_rate as f64);
+        self.resampler.rate_control = rate_control;
+    }
+
+    /// Passes the mixer output to the resampler and sends the samples in chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `mix` - Output of the mixer in this CPU cycle.
+    ///
+    /// Nothing is returned.
+    fn push_sample(&mut self, mix: f32) {
+        self.resampler.clock(mix);
+        if self.resampler.available() >= SAMPLE_CHUNK {
+            self.flush();
+        }
+    }
+
+    /// Sends the samples that have not been sent yet, even if they do not fill a chunk.
+    ///
+    /// Nothing is returned.
+    pub fn flush(&mut self) {
+        let mut samples = Vec::new();
+        self.resampler.read_samples(&mut samples);
+        if let Some(output) = &self.output {
+            if !samples.is_empty() && output.send(samples).is_err() {
+                self.output = None; // The receiver is gone
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod apu_tests {
+    use crate::apu::{Apu, Envelope, LengthCounter, SAMPLE_CHUNK};
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_envelope() {
+        let mut envelope = Envelope::default();
+        envelope.write(0b10_0001); // Looping with a period of 2 quarter frames
+        envelope.start = true;
+        envelope.clock();
+        assert_eq!(envelope.output(), 15);
+        for _ in 0..2 * 15 {
+            envelope.clock();
+        }
+        assert_eq!(envelope.output(), 0);
+        envelope.clock();
+        envelope.clock();
+        assert_eq!(envelope.output(), 15);
+
+        envelope.write(0b1_0111);
+        assert_eq!(envelope.output(), 7);
+    }
+
+    #[test]
+    fn test_length_counter() {
+        let mut length = LengthCounter::default();
+        length.load(0b0000_1000);
+        assert!(!length.active()); // Disabled channels are not loaded
+
+        length.set_enabled(true);
+        length.load(0b0000_1000); // 254
+        length.clock();
+        assert_eq!(length.counter, 253);
+        length.halt = true;
+        length.clock();
+        assert_eq!(length.counter, 253);
+        length.set_enabled(false);
+        assert!(!length.active());
+    }
+
+    #[test]
+    fn test_status_and_samples() {
+        let mut apu = Apu::default();
+        let (sender, receiver) = channel();
+        apu.output = Some(sender);
+        apu.write_register(0x4015, 0b0_1111);
+        apu.write_register(0x4000, 0b1011_1111); // 50% duty, constant volume 15
+        apu.write_register(0x4002, 0xfd);
+        apu.write_register(0x4003, 0b0000_1000); // Length 254
+        apu.write_register(0x400f, 0b0001_1000); // Length 2
+        assert_eq!(apu.read_status(), 0b1001);
+
+        // The noise length runs out after two half frames, two frames of 60 Hz
+        let frame = (super::CPU_CLOCK / 60) as usize;
+        for _ in 0..2 * frame {
+            apu.tick();
+        }
+        assert_eq!(apu.read_status(), 0b0100_0001); // The frame interrupt is raised as well
+        let samples: Vec<f32> = receiver.try_iter().flatten().collect();
+        assert_eq!(samples.len(), SAMPLE_CHUNK); // 1469 were produced
+        assert!(samples.iter().all(|&s| (-1.0..=1.0).contains(&s)));
+        assert!(samples.iter().any(|&s| s > 0.05) && samples.iter().any(|&s| s < -0.05));
+
+        apu.write_register(0x4015, 0);
+        assert_eq!(apu.read_status(), 0);
+    }
+
+    #[test]
+    fn test_sample_rate() {
+        let mut apu = Apu::default();
+        let rate_control = apu.resampler.rate_control.clone();
+        apu.set_sample_rate(super::PAL_CPU_CLOCK, 48000);
+        assert_eq!(apu.resampler.clock_rate, 1_662_607.0);
+        assert_eq!(apu.resampler.sample_rate, 48000.0);
+
+        // The frontend keeps its handle
+        rate_control.set_buffer_fill(0.0);
+        assert_eq!(apu.resampler.rate_control.ratio(), 1.005);
+    }
+
+    #[test]
+    fn test_frame_irq() {
+        let mut apu = Apu::default();
+        for _ in 0..29827 {
+            apu.tick();
+        }
+        assert!(!apu.irq());
+        apu.tick();
+        assert!(apu.irq());
+        assert_eq!(apu.read_status(), 0b0100_0000);
+        assert!(!apu.irq()); // Acknowledged by the read
+        apu.tick();
+        apu.tick();
+        assert!(apu.irq()); // Raised again on the next two cycles
+        apu.read_status();
+
+        // The next interrupt follows a full sequence later
+        for _ in 0..29827 {
+            apu.tick();
+        }
+        assert!(!apu.irq());
+        apu.tick();
+        assert!(apu.irq());
+
+        // Setting the inhibit flag acknowledges and disables the interrupt
+        apu.write_register(0x4017, 0b0100_0000);
+        assert!(!apu.irq());
+        for _ in 0..2 * 29830 {
+            apu.tick();
+        }
+        assert!(!apu.irq());
+    }
+
+    #[test]
+    fn test_five_step_mode() {
+        let mut apu = Apu::default();
+        apu.write_register(0x4015, 0b0001);
+        apu.write_register(0x4003, 0b0001_1000); // Length 2
+        apu.write_register(0x4017, 0b1000_0000);
+        let delay = apu.frame_reset_delay;
+        for _ in 0..delay {
+            apu.tick();
+        }
+        assert_eq!(apu.pulse1.length.counter, 1); // Clocked when the mode is set
+
+        // Half frames at 14913 and 37281, no interrupt
+        for _ in 0..14913 {
+            apu.tick();
+        }
+        assert_eq!(apu.pulse1.length.counter, 0);
+        for _ in 0..3 * 37282 {
+            apu.tick();
+        }
+        assert!(!apu.irq());
+        assert_eq!(apu.frame_cycle, 14913);
+
+        apu.reset();
+        assert!(apu.five_step);
+        assert_eq!(apu.read_status(), 0);
+    }
+}
@@ -0,0 +1,226 @@
+//! This module provides the delta modulation channel (DMC), which plays 1-bit delta-encoded samples from CPU memory.
+
+/// Timer periods of the DMC in CPU cycles on NTSC consoles.
+const RATE_TABLE: [u16; 16] = [
+    428, 380, 340, 320, 286, 254, 226, 214, 190, 160, 142, 128, 106, 84, 72, 54,
+];
+
+/// Delta modulation channel with its memory reader and output unit.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Dmc {
+    /// Indicates whether the end of a sample raises an interrupt.
+    pub irq_enabled: bool,
+    /// Indicates whether the DMC pulls the IRQ line low.
+    pub irq: bool,
+    /// Indicates whether the sample starts over when it ends.
+    pub looping: bool,
+    /// Period of the timer in CPU cycles.
+    pub timer_period: u16,
+    /// Counter of the timer.
+    pub timer: u16,
+    /// Address the sample starts at ($C000-$FFC0).
+    pub sample_address: u16,
+    /// Length of the sample in bytes.
+    pub sample_length: u16,
+    /// Address of the next sample byte.
+    pub current_address: u16,
+    /// Number of sample bytes that still have to be read.
+    pub bytes_remaining: u16,
+    /// Sample byte that was read but not yet moved into the shift register.
+    pub sample_buffer: Option<u8>,
+    /// Bits of the sample byte that is being played.
+    pub shift_register: u8,
+    /// Number of bits left in the shift register.
+    pub bits_remaining: u8,
+    /// Indicates whether the output level is held because the buffer was empty when the last byte ended.
+    pub silence: bool,
+    /// Output level (0-127).
+    pub output: u8,
+}
+
+impl Default for Dmc {
+    fn default() -> Self {
+        Dmc {
+            irq_enabled: false,
+            irq: false,
+            looping: false,
+            timer_period: RATE_TABLE[0],
+            timer: 0,
+            sample_address: 0xc000,
+            sample_length: 1,
+            current_address: 0xc000,
+            bytes_remaining: 0,
+            sample_buffer: None,
+            shift_register: 0,
+            bits_remaining: 8,
+            silence: true,
+            output: 0,
+        }
+    }
+}
+
+impl Dmc {
+    /// Writes one of the four registers of the channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - Number of the register (0-3).
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    pub fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {
+                self.irq_enabled = data & 0b1000_0000 != 0;
+                if !self.irq_enabled {
+                    self.irq = false;
+                }
+                self.looping = data & 0b100_0000 != 0;
+                self.timer_period = RATE_TABLE[(data & 0b1111) as usize];
+            }
+            1 => self.output = data & 0x7f,
+            2 => self.sample_address = 0xc000 | (data as u16) << 6,
+            _ => self.sample_length = ((data as u16) << 4) | 1,
+        }
+    }
+
+    /// Starts or stops the sample through $4015. Any write to $4015 acknowledges the interrupt.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the sample plays.
+    ///
+    /// Nothing is returned.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.irq = false;
+        if !enabled {
+            self.bytes_remaining = 0;
+        } else if self.bytes_remaining == 0 {
+            self.restart();
+        }
+    }
+
+    /// Starts reading the sample from its beginning.
+    ///
+    /// Nothing is returned.
+    fn restart(&mut self) {
+        self.current_address = self.sample_address;
+        self.bytes_remaining = self.sample_length;
+    }
+
+    /// Returns whether bytes of the sample are left to be read.
+    ///
+    /// # Return
+    /// * `bool` - Whether the sample is playing.
+    pub fn active(&self) -> bool {
+        self.bytes_remaining > 0
+    }
+
+    /// Returns the address of the next sample byte if the buffer is empty and the sample has not ended.
+    ///
+    /// # Return
+    /// * `Option<u16>` - The address that has to be read, `None` if no byte is needed.
+    pub fn sample_request(&self) -> Option<u16> {
+        (self.sample_buffer.is_none() && self.bytes_remaining > 0).then_some(self.current_address)
+    }
+
+    /// Stores the sample byte that was read for `sample_request` and moves on to the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The byte that was read.
+    ///
+    /// Nothing is returned.
+    pub fn fill_sample_buffer(&mut self, data: u8) {
+        self.sample_buffer = Some(data);
+        self.current_address = self.current_address.checked_add(1).unwrap_or(0x8000);
+        self.bytes_remaining -= 1;
+        if self.bytes_remaining == 0 {
+            if self.looping {
+                self.restart();
+            } else if self.irq_enabled {
+                self.irq = true;
+            }
+        }
+    }
+
+    /// Clocks the timer, which moves the output level by one bit of the sample when it runs out. Called every CPU cycle.
+    ///
+    /// Nothing is returned.
+    pub fn clock_timer(&mut self) {
+        if self.timer > 0 {
+            self.timer -= 1;
+            return;
+        }
+        self.timer = self.timer_period - 1;
+        if !self.silence {
+            if self.shift_register & 1 == 1 {
+                if self.output <= 125 {
+                    self.output += 2;
+                }
+            } else if self.output >= 2 {
+                self.output -= 2;
+            }
+        }
+        self.shift_register >>= 1;
+        self.bits_remaining -= 1;
+        if self.bits_remaining == 0 {
+            self.bits_remaining = 8;
+            match self.sample_buffer.take() {
+                Some(data) => {
+                    self.silence = false;
+                    self.shift_register = data;
+                }
+                None => self.silence = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod dmc_tests {
+    use crate::apu::dmc::Dmc;
+
+    #[test]
+    fn test_sample() {
+        let mut dmc = Dmc::default();
+        dmc.write_register(0, 0b1000_1111); // IRQ, fastest rate
+        dmc.write_register(1, 0x40);
+        dmc.write_register(2, 0xff); // $FFC0
+        dmc.write_register(3, 0x04); // 65 bytes
+        dmc.set_enabled(true);
+        assert_eq!(dmc.sample_request(), Some(0xffc0));
+
+        let mut bytes = 0;
+        while let Some(addr) = dmc.sample_request() {
+            dmc.fill_sample_buffer(0xff);
+            bytes += 1;
+            if addr == 0xffff {
+                assert_eq!(dmc.sample_request(), None); // Buffer full
+                dmc.sample_buffer = None;
+                assert_eq!(dmc.sample_request(), Some(0x8000)); // Wraps to $8000
+            }
+            dmc.sample_buffer = None;
+        }
+        assert_eq!(bytes, 65);
+        assert!(dmc.irq);
+        dmc.set_enabled(false);
+        assert!(!dmc.irq);
+    }
+
+    #[test]
+    fn test_output() {
+        let mut dmc = Dmc::default();
+        dmc.write_register(1, 0x7e);
+        dmc.sample_buffer = Some(0b0000_0001);
+        for _ in 0..8 * 428 {
+            dmc.clock_timer(); // Empties the shift register, loads the buffer
+        }
+        assert!(!dmc.silence);
+        for _ in 0..8 * 428 {
+            dmc.clock_timer();
+        }
+        assert_eq!(dmc.output, 0x7e - 7 * 2); // Clamped on the way up, then 7 steps down
+        assert!(dmc.silence);
+    }
+}
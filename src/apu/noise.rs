@@ -0,0 +1,117 @@
+//! This module provides the noise channel, which outputs the lowest bit of a linear-feedback shift register.
+
+use crate::apu::{Envelope, LengthCounter};
+
+/// Timer periods of the noise channel in CPU cycles on NTSC consoles.
+const PERIOD_TABLE: [u16; 16] = [
+    4, 8, 16, 32, 64, 96, 128, 160, 202, 254, 380, 508, 762, 1016, 2034, 4068,
+];
+
+/// Noise channel with its envelope and length counter.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Noise {
+    /// Indicates whether the feedback is taken from bit 6 instead of bit 1, which gives a short, metallic sequence.
+    pub short_mode: bool,
+    /// 15-bit shift register.
+    pub shift_register: u16,
+    /// Period of the timer in CPU cycles.
+    pub timer_period: u16,
+    /// Counter of the timer.
+    pub timer: u16,
+    /// Volume envelope.
+    pub envelope: Envelope,
+    /// Length counter.
+    pub length: LengthCounter,
+}
+
+impl Default for Noise {
+    fn default() -> Self {
+        Noise {
+            short_mode: false,
+            shift_register: 1,
+            timer_period: PERIOD_TABLE[0],
+            timer: 0,
+            envelope: Envelope::default(),
+            length: LengthCounter::default(),
+        }
+    }
+}
+
+impl Noise {
+    /// Writes one of the four registers of the channel, the second one is unused.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - Number of the register (0-3).
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    pub fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {
+                self.length.halt = data & 0b10_0000 != 0;
+                self.envelope.write(data);
+            }
+            1 => {}
+            2 => {
+                self.short_mode = data & 0b1000_0000 != 0;
+                self.timer_period = PERIOD_TABLE[(data & 0b1111) as usize];
+            }
+            _ => {
+                self.length.load(data);
+                self.envelope.start = true;
+            }
+        }
+    }
+
+    /// Clocks the timer, which shifts the register when it runs out. Called every CPU cycle.
+    ///
+    /// Nothing is returned.
+    pub fn clock_timer(&mut self) {
+        if self.timer == 0 {
+            self.timer = self.timer_period - 1;
+            let tap = if self.short_mode { 6 } else { 1 };
+            let feedback = (self.shift_register ^ (self.shift_register >> tap)) & 1;
+            self.shift_register = (self.shift_register >> 1) | (feedback << 14);
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    /// Returns the output level of the channel.
+    ///
+    /// # Return
+    /// * `u8` - The level (0-15).
+    pub fn output(&self) -> u8 {
+        if self.shift_register & 1 == 1 || !self.length.active() {
+            0
+        } else {
+            self.envelope.output()
+        }
+    }
+}
+
+#[cfg(test)]
+mod noise_tests {
+    use crate::apu::noise::Noise;
+
+    /// Returns the number of shifts after which the register repeats.
+    fn sequence_length(noise: &mut Noise) -> usize {
+        let start = noise.shift_register;
+        (1..=32767)
+            .find(|_| {
+                noise.timer = 0;
+                noise.clock_timer();
+                noise.shift_register == start
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sequences() {
+        let mut noise = Noise::default();
+        assert_eq!(sequence_length(&mut noise), 32767);
+        noise.write_register(2, 0b1000_0000);
+        assert_eq!(sequence_length(&mut noise), 93);
+    }
+}
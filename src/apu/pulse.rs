@@ -0,0 +1,206 @@
+//! This module provides the pulse channels, which play a square wave with a selectable duty cycle and can sweep their pitch.
+
+use crate::apu::{Envelope, LengthCounter};
+
+/// Waveforms of the four duty cycles, one step per 8 timer clocks.
+const DUTY_TABLE: [[u8; 8]; 4] = [
+    [0, 1, 0, 0, 0, 0, 0, 0],
+    [0, 1, 1, 0, 0, 0, 0, 0],
+    [0, 1, 1, 1, 1, 0, 0, 0],
+    [1, 0, 0, 1, 1, 1, 1, 1],
+];
+
+/// Pulse channel with its envelope, sweep unit and length counter.
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct Pulse {
+    /// Indicates whether this is the first channel, whose sweep subtracts one more when it lowers the period.
+    pub ones_complement: bool,
+    /// Selected duty cycle (0-3).
+    pub duty: u8,
+    /// Step in the waveform (0-7).
+    pub step: u8,
+    /// Period of the timer in APU cycles, minus one.
+    pub timer_period: u16,
+    /// Counter of the timer.
+    pub timer: u16,
+    /// Volume envelope.
+    pub envelope: Envelope,
+    /// Length counter.
+    pub length: LengthCounter,
+    /// Indicates whether the sweep changes the period.
+    pub sweep_enabled: bool,
+    /// Number of half frames between sweep updates, minus one.
+    pub sweep_period: u8,
+    /// Indicates whether the sweep lowers the period, raising the pitch.
+    pub sweep_negate: bool,
+    /// Amount the period is shifted right by to get the change of a sweep update.
+    pub sweep_shift: u8,
+    /// Indicates whether the sweep divider is reloaded on the next half frame.
+    pub sweep_reload: bool,
+    /// Counter of the sweep divider.
+    pub sweep_divider: u8,
+}
+
+impl Pulse {
+    /// Creates a pulse channel in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `ones_complement` - Whether this is the first pulse channel.
+    ///
+    /// # Return
+    /// * `Pulse` - The channel.
+    pub fn new(ones_complement: bool) -> Pulse {
+        Pulse {
+            ones_complement,
+            ..Default::default()
+        }
+    }
+
+    /// Writes one of the four registers of the channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - Number of the register (0-3).
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    pub fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {
+                self.duty = data >> 6;
+                self.length.halt = data & 0b10_0000 != 0;
+                self.envelope.write(data);
+            }
+            1 => {
+                self.sweep_enabled = data & 0b1000_0000 != 0;
+                self.sweep_period = (data >> 4) & 0b111;
+                self.sweep_negate = data & 0b1000 != 0;
+                self.sweep_shift = data & 0b111;
+                self.sweep_reload = true;
+            }
+            2 => self.timer_period = (self.timer_period & 0x700) | data as u16,
+            _ => {
+                self.timer_period = (self.timer_period & 0xff) | ((data as u16 & 0b111) << 8);
+                self.length.load(data);
+                self.step = 0;
+                self.envelope.start = true;
+            }
+        }
+    }
+
+    /// Clocks the timer, which advances the waveform when it runs out. Called every APU cycle (two CPU cycles).
+    ///
+    /// Nothing is returned.
+    pub fn clock_timer(&mut self) {
+        if self.timer == 0 {
+            self.timer = self.timer_period;
+            self.step = (self.step + 1) % 8;
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    /// Returns the period the sweep unit would change to.
+    ///
+    /// # Return
+    /// * `u16` - The target period, above $7FF if it overflows.
+    fn sweep_target(&self) -> u16 {
+        let change = self.timer_period >> self.sweep_shift;
+        if self.sweep_negate {
+            let extra = self.ones_complement as u16;
+            self.timer_period.saturating_sub(change + extra)
+        } else {
+            self.timer_period + change
+        }
+    }
+
+    /// Returns whether the sweep unit silences the channel, which it does for periods that are too low or would overflow, even if the sweep is disabled.
+    ///
+    /// # Return
+    /// * `bool` - Whether the channel is muted.
+    fn muted(&self) -> bool {
+        self.timer_period < 8 || self.sweep_target() > 0x7ff
+    }
+
+    /// Clocks the length counter and the sweep unit on a half frame.
+    ///
+    /// Nothing is returned.
+    pub fn clock_half_frame(&mut self) {
+        self.length.clock();
+        if self.sweep_divider == 0 && self.sweep_enabled && self.sweep_shift > 0 && !self.muted() {
+            self.timer_period = self.sweep_target();
+        }
+        if self.sweep_divider == 0 || self.sweep_reload {
+            self.sweep_divider = self.sweep_period;
+            self.sweep_reload = false;
+        } else {
+            self.sweep_divider -= 1;
+        }
+    }
+
+    /// Returns the output level of the channel.
+    ///
+    /// # Return
+    /// * `u8` - The level (0-15).
+    pub fn output(&self) -> u8 {
+        if self.muted()
+            || !self.length.active()
+            || DUTY_TABLE[self.duty as usize][self.step as usize] == 0
+        {
+            0
+        } else {
+            self.envelope.output()
+        }
+    }
+}
+
+#[cfg(test)]
+mod pulse_tests {
+    use crate::apu::pulse::Pulse;
+
+    #[test]
+    fn test_waveform() {
+        let mut pulse = Pulse::new(true);
+        pulse.length.enabled = true;
+        pulse.write_register(0, 0b0101_0111); // 25% duty, constant volume 7
+        pulse.write_register(2, 0x08);
+        pulse.write_register(3, 0b0000_1000);
+        let mut levels = Vec::new();
+        for _ in 0..8 * 9 {
+            pulse.clock_timer();
+            levels.push(pulse.output());
+        }
+        assert_eq!(levels.iter().filter(|&&level| level == 7).count(), 2 * 9);
+        assert!(levels.iter().all(|&level| level == 0 || level == 7));
+
+        pulse.write_register(2, 0x07); // Periods below 8 are muted
+        assert_eq!(pulse.output(), 0);
+    }
+
+    #[test]
+    fn test_sweep() {
+        let mut pulse1 = Pulse::new(true);
+        let mut pulse2 = Pulse::new(false);
+        for pulse in [&mut pulse1, &mut pulse2] {
+            pulse.write_register(1, 0b1000_1001); // Enabled, period 0, negate, shift 1
+            pulse.write_register(2, 0x00);
+            pulse.write_register(3, 0x01);
+            pulse.clock_half_frame();
+        }
+        // The first channel subtracts one more
+        assert_eq!(pulse1.timer_period, 0x100 - 0x80 - 1);
+        assert_eq!(pulse2.timer_period, 0x100 - 0x80);
+
+        // Raising the period above $7FF mutes the channel without changing the period
+        pulse2.length.enabled = true;
+        pulse2.write_register(0, 0b1001_1111);
+        pulse2.write_register(1, 0b1000_0001);
+        pulse2.write_register(2, 0xff);
+        pulse2.write_register(3, 0b0000_1101);
+        assert!(pulse2.muted());
+        pulse2.clock_half_frame();
+        assert_eq!(pulse2.timer_period, 0x5ff);
+        assert_eq!(pulse2.output(), 0);
+    }
+}
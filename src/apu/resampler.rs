@@ -0,0 +1,314 @@
+//! This module turns the mixer output, which changes at the CPU clock rate, into samples at the rate of the host.
+//!
+//! Every change of the level is added as a band-limited step, a windowed sinc impulse that is integrated when the samples are read. Only changes cost work, and no frequencies above the host's Nyquist rate fold back into the audible range. The samples then pass the filters of the NES audio output.
+
+use crate::apu::CPU_CLOCK;
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+/// Width of the sinc impulse in output samples.
+const TAPS: usize = 32;
+
+/// Number of fractional positions the impulse is precomputed for.
+const PHASES: usize = 64;
+
+/// Cutoff of the sinc impulse as a fraction of the sample rate, just below the Nyquist rate.
+const CUTOFF: f64 = 0.45;
+
+/// Largest relative change of the sample rate the rate control makes, small enough not to be heard as a change of pitch.
+const MAX_RATE_DELTA: f64 = 0.005;
+
+/// Shared handle through which the audio frontend reports how full its output buffer is. The resampler produces slightly more samples while the buffer runs low and fewer while it fills up, so the audio keeps pace with the video without gaps or overflows.
+#[derive(Clone, Debug)]
+pub struct RateControl(Arc<AtomicU32>);
+
+impl Default for RateControl {
+    fn default() -> Self {
+        RateControl(Arc::new(AtomicU32::new(0.5f32.to_bits())))
+    }
+}
+
+impl RateControl {
+    /// Reports the fill level of the output buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `fill` - How full the buffer is, from 0.0 (empty) to 1.0 (full). 0.5 keeps the nominal rate.
+    ///
+    /// Nothing is returned.
+    pub fn set_buffer_fill(&self, fill: f32) {
+        self.0
+            .store(fill.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Returns the factor the sample rate is adjusted by.
+    ///
+    /// # Return
+    /// * `f64` - The factor, between 1 - `MAX_RATE_DELTA` and 1 + `MAX_RATE_DELTA`.
+    pub fn ratio(&self) -> f64 {
+        let fill = f32::from_bits(self.0.load(Ordering::Relaxed)) as f64;
+        1.0 + MAX_RATE_DELTA * (1.0 - 2.0 * fill)
+    }
+}
+
+/// First-order filter like the RC filters of the NES audio output.
+#[derive(PartialEq, Debug, Default)]
+struct RcFilter {
+    /// Indicates whether the filter passes high frequencies instead of low ones.
+    high_pass: bool,
+    /// Coefficient of the filter.
+    alpha: f64,
+    /// Last input.
+    input: f64,
+    /// Last output.
+    output: f64,
+}
+
+impl RcFilter {
+    /// Creates a filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `high_pass` - Whether the filter is a high-pass filter.
+    /// * `cutoff` - Cutoff frequency in Hz.
+    /// * `sample_rate` - Rate in Hz of the samples that are filtered.
+    ///
+    /// # Return
+    /// * `RcFilter` - The filter.
+    fn new(high_pass: bool, cutoff: f64, sample_rate: f64) -> RcFilter {
+        let rc = 1.0 / (2.0 * PI * cutoff);
+        let dt = 1.0 / sample_rate;
+        RcFilter {
+            high_pass,
+            alpha: if high_pass {
+                rc / (rc + dt)
+            } else {
+                dt / (rc + dt)
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Filters one sample.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The sample.
+    ///
+    /// # Return
+    /// * `f64` - The filtered sample.
+    fn apply(&mut self, input: f64) -> f64 {
+        self.output = if self.high_pass {
+            self.alpha * (self.output + input - self.input)
+        } else {
+            self.output + self.alpha * (input - self.output)
+        };
+        self.input = input;
+        self.output
+    }
+}
+
+/// Band-limited resampler from the CPU clock rate to the rate of the host.
+#[derive(Debug)]
+pub struct Resampler {
+    /// Rate in Hz of the input, one level per CPU cycle.
+    pub clock_rate: f64,
+    /// Rate in Hz of the output.
+    pub sample_rate: f64,
+    /// Input of the rate control, shared with the frontend.
+    pub rate_control: RateControl,
+    /// Sinc impulse for every fractional position.
+    kernel: Vec<[f32; TAPS]>,
+    /// Output samples per input clock, including the rate control.
+    step: f64,
+    /// Position of the next input clock in output samples, counted from the start of `buffer`.
+    position: f64,
+    /// Impulses of the level changes, which are integrated into samples when they are read.
+    buffer: Vec<f32>,
+    /// Level of the last input clock.
+    level: f32,
+    /// Sum of the impulses that were read.
+    integrator: f64,
+    /// Filters of the NES audio output: high-pass at 90 Hz and 440 Hz, low-pass at 14 kHz.
+    filters: [RcFilter; 3],
+}
+
+impl Default for Resampler {
+    fn default() -> Self {
+        Resampler::new(CPU_CLOCK as f64, 44100.0)
+    }
+}
+
+impl Resampler {
+    /// Creates a resampler.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock_rate` - Rate in Hz of the input, the CPU clock of the console (about 1.79 MHz for NTSC, 1.66 MHz for PAL).
+    /// * `sample_rate` - Rate in Hz of the output.
+    ///
+    /// # Return
+    /// * `Resampler` - The resampler.
+    pub fn new(clock_rate: f64, sample_rate: f64) -> Resampler {
+        let kernel = (0..PHASES)
+            .map(|phase| {
+                let frac = phase as f64 / PHASES as f64;
+                let mut taps = [0.0; TAPS];
+                for (k, tap) in taps.iter_mut().enumerate() {
+                    // Distance from the impulse in output samples, the impulse is delayed by TAPS / 2 - 1 samples
+                    let t = k as f64 - (TAPS / 2 - 1) as f64 - frac;
+                    let x = 2.0 * CUTOFF * t;
+                    let sinc = if x == 0.0 {
+                        1.0
+                    } else {
+                        (PI * x).sin() / (PI * x)
+                    };
+                    let w = (t + (TAPS / 2) as f64) / TAPS as f64;
+                    let blackman = 0.42 - 0.5 * (2.0 * PI * w).cos() + 0.08 * (4.0 * PI * w).cos();
+                    *tap = (sinc * blackman) as f32;
+                }
+                // Every step has to add up to its full height
+                let sum: f32 = taps.iter().sum();
+                taps.map(|tap| tap / sum)
+            })
+            .collect();
+        Resampler {
+            clock_rate,
+            sample_rate,
+            rate_control: RateControl::default(),
+            kernel,
+            step: sample_rate / clock_rate,
+            position: 0.0,
+            buffer: vec![0.0; TAPS],
+            level: 0.0,
+            integrator: 0.0,
+            filters: [
+                RcFilter::new(true, 90.0, sample_rate),
+                RcFilter::new(true, 440.0, sample_rate),
+                RcFilter::new(false, 14000.0, sample_rate),
+            ],
+        }
+    }
+
+    /// Takes the level of one input clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Output of the mixer in this clock.
+    ///
+    /// Nothing is returned.
+    pub fn clock(&mut self, level: f32) {
+        if level != self.level {
+            let delta = level - self.level;
+            self.level = level;
+            let index = self.position as usize;
+            let phase = ((self.position - index as f64) * PHASES as f64) as usize;
+            if self.buffer.len() < index + TAPS {
+                self.buffer.resize(index + TAPS, 0.0);
+            }
+            for (sample, tap) in self.buffer[index..].iter_mut().zip(self.kernel[phase]) {
+                *sample += delta * tap;
+            }
+        }
+        self.position += self.step;
+    }
+
+    /// Returns the number of samples that can be read, which no future level change affects anymore.
+    ///
+    /// # Return
+    /// * `usize` - The number of samples.
+    pub fn available(&self) -> usize {
+        self.position as usize
+    }
+
+    /// Moves the finished samples to a buffer, and picks up the rate control for the next ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - The buffer, which gets samples between about -1.0 and 1.0.
+    ///
+    /// Nothing is returned.
+    pub fn read_samples(&mut self, samples: &mut Vec<f32>) {
+        let count = self.available();
+        if self.buffer.len() < count + TAPS {
+            self.buffer.resize(count + TAPS, 0.0);
+        }
+        for impulse in self.buffer.drain(..count) {
+            self.integrator += impulse as f64;
+            let sample = self
+                .filters
+                .iter_mut()
+                .fold(self.integrator, |sample, filter| filter.apply(sample));
+            samples.push(sample as f32);
+        }
+        self.position -= count as f64;
+        self.step = self.sample_rate / self.clock_rate * self.rate_control.ratio();
+    }
+}
+
+#[cfg(test)]
+mod resampler_tests {
+    use crate::apu::resampler::Resampler;
+    use crate::apu::CPU_CLOCK;
+
+    /// Resamples one second of a square wave.
+    fn square_wave(resampler: &mut Resampler, frequency: u32) -> Vec<f32> {
+        let mut samples = Vec::new();
+        let half_period = CPU_CLOCK / frequency / 2;
+        for clock in 0..CPU_CLOCK {
+            resampler.clock(if clock / half_period % 2 == 0 {
+                0.5
+            } else {
+                0.0
+            });
+        }
+        resampler.read_samples(&mut samples);
+        samples
+    }
+
+    /// Returns the RMS of the second half of the samples, after the filters have settled.
+    fn rms(samples: &[f32]) -> f32 {
+        let settled = &samples[samples.len() / 2..];
+        (settled.iter().map(|s| s * s).sum::<f32>() / settled.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_square_wave() {
+        let mut resampler = Resampler::default();
+        let samples = square_wave(&mut resampler, 1000);
+        assert!((44090..=44100).contains(&samples.len()));
+
+        let settled = &samples[samples.len() / 2..];
+        let crossings = settled
+            .windows(2)
+            .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+            .count();
+        assert!((499..=501).contains(&crossings)); // 1 kHz over half a second
+        let mean = settled.iter().sum::<f32>() / settled.len() as f32;
+        assert!(mean.abs() < 0.01); // The high-pass filters remove the offset
+        assert!(settled.iter().all(|s| s.abs() < 0.4));
+    }
+
+    #[test]
+    fn test_band_limit() {
+        // A square wave above the Nyquist rate is filtered out instead of aliasing
+        let audible = rms(&square_wave(&mut Resampler::default(), 1000));
+        let ultrasonic = rms(&square_wave(&mut Resampler::default(), 30000));
+        assert!(ultrasonic < audible * 0.05);
+    }
+
+    #[test]
+    fn test_rate_control() {
+        let mut resampler = Resampler::default();
+        let mut samples = Vec::new();
+        resampler.rate_control.set_buffer_fill(0.0);
+        resampler.read_samples(&mut samples); // Picks up the rate
+        for _ in 0..CPU_CLOCK {
+            resampler.clock(0.0);
+        }
+        resampler.read_samples(&mut samples);
+        assert_eq!(samples.len(), (44100.0 * 1.005) as usize);
+        assert!(samples.iter().all(|&s| s == 0.0));
+    }
+}
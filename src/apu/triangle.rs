@@ -0,0 +1,123 @@
+//! This module provides the triangle channel, which plays a fixed 32-step triangle wave and has a linear counter next to its length counter.
+
+use crate::apu::LengthCounter;
+
+/// Triangle channel with its linear and length counters.
+#[derive(PartialEq, Eq, Default, Debug)]
+pub struct Triangle {
+    /// Step in the waveform (0-31).
+    pub step: u8,
+    /// Period of the timer in CPU cycles, minus one.
+    pub timer_period: u16,
+    /// Counter of the timer.
+    pub timer: u16,
+    /// Length counter, halted by the control flag.
+    pub length: LengthCounter,
+    /// Indicates whether the linear counter keeps reloading, the control flag.
+    pub control: bool,
+    /// Value the linear counter is reloaded with.
+    pub linear_reload_value: u8,
+    /// Indicates whether the linear counter is reloaded on the next quarter frame.
+    pub linear_reload: bool,
+    /// Remaining number of quarter frames.
+    pub linear_counter: u8,
+}
+
+impl Triangle {
+    /// Writes one of the four registers of the channel, the second one is unused.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - Number of the register (0-3).
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    pub fn write_register(&mut self, register: u16, data: u8) {
+        match register {
+            0 => {
+                self.control = data & 0b1000_0000 != 0;
+                self.length.halt = self.control;
+                self.linear_reload_value = data & 0x7f;
+            }
+            1 => {}
+            2 => self.timer_period = (self.timer_period & 0x700) | data as u16,
+            _ => {
+                self.timer_period = (self.timer_period & 0xff) | ((data as u16 & 0b111) << 8);
+                self.length.load(data);
+                self.linear_reload = true;
+            }
+        }
+    }
+
+    /// Clocks the timer, which advances the waveform while both counters are running. Called every CPU cycle.
+    ///
+    /// Nothing is returned.
+    pub fn clock_timer(&mut self) {
+        if self.timer == 0 {
+            self.timer = self.timer_period;
+            if self.length.active() && self.linear_counter > 0 {
+                self.step = (self.step + 1) % 32;
+            }
+        } else {
+            self.timer -= 1;
+        }
+    }
+
+    /// Clocks the linear counter on a quarter frame.
+    ///
+    /// Nothing is returned.
+    pub fn clock_linear_counter(&mut self) {
+        if self.linear_reload {
+            self.linear_counter = self.linear_reload_value;
+        } else if self.linear_counter > 0 {
+            self.linear_counter -= 1;
+        }
+        if !self.control {
+            self.linear_reload = false;
+        }
+    }
+
+    /// Returns the output level of the channel. A stopped triangle keeps its level instead of dropping to 0.
+    ///
+    /// # Return
+    /// * `u8` - The level (0-15).
+    pub fn output(&self) -> u8 {
+        if self.step < 16 {
+            15 - self.step
+        } else {
+            self.step - 16
+        }
+    }
+}
+
+#[cfg(test)]
+mod triangle_tests {
+    use crate::apu::triangle::Triangle;
+
+    #[test]
+    fn test_counters() {
+        let mut triangle = Triangle::default();
+        triangle.length.enabled = true;
+        triangle.write_register(0, 0x02); // Linear counter of 2 quarter frames
+        triangle.write_register(2, 0x00);
+        triangle.write_register(3, 0b0000_1000);
+        triangle.clock_linear_counter();
+        assert_eq!(triangle.linear_counter, 2);
+
+        let levels: Vec<u8> = (0..32)
+            .map(|_| {
+                triangle.clock_timer();
+                triangle.output()
+            })
+            .collect();
+        assert_eq!(levels[..4], [14, 13, 12, 11]);
+        assert_eq!(levels[14..18], [0, 0, 1, 2]);
+
+        triangle.clock_linear_counter();
+        triangle.clock_linear_counter();
+        triangle.clock_timer();
+        assert_eq!(triangle.output(), 15); // Stopped, but the level is kept
+        triangle.clock_timer();
+        assert_eq!(triangle.output(), 15);
+    }
+}
@@ -1,11 +1,65 @@
 //! This module provides the bus, which connects the CPU, the Cartridge and the mapper.
 
-use crate::controller::Controller;
-use crate::{Cartridge, Cpu6502, Instruction, MapperType};
+use crate::apu::Apu;
+use crate::cartridge::RomError;
+use crate::controller::{ConsoleButton, Controller, ExpansionDevice};
+use crate::instructions::{InstructionState, Interrupt};
+use crate::mapper::{get_mapper, Mapper};
+use crate::save::SaveFile;
+use crate::{Cartridge, Cpu6502, Instruction};
 use std::error::Error;
-use tudelft_nes_ppu::{Cpu, Mirroring, Ppu, PpuRegister};
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tudelft_nes_ppu::{Buttons, Cpu, Mirroring, Ppu, PpuRegister};
 use tudelft_nes_test::TestableCpu;
 
+/// Copy of the nametable memory. The PPU is run with four-screen mirroring and the bus fills its four nametables according to the mirroring the mapper selects, so that the mirroring can change at runtime.
+pub struct Nametables {
+    /// The 2 kB of console VRAM followed by the 2 kB of cartridge VRAM used by four-screen boards.
+    pub ram: Box<[u8; 4096]>,
+    /// Mirroring the nametables of the PPU are currently filled with.
+    pub mirroring: Mirroring,
+}
+
+impl Default for Nametables {
+    fn default() -> Self {
+        Nametables {
+            ram: Box::new([0; 4096]),
+            mirroring: Mirroring::Horizontal,
+        }
+    }
+}
+
+impl Nametables {
+    /// Returns which 1 kB page of `ram` is shown in a nametable.
+    ///
+    /// # Arguments
+    ///
+    /// * `nametable` - Number of the nametable (0-3).
+    ///
+    /// # Return
+    /// * `usize` - Number of the page (0-3).
+    fn page(&self, nametable: usize) -> usize {
+        match self.mirroring {
+            Mirroring::Horizontal => nametable / 2,
+            Mirroring::Vertical => nametable % 2,
+            Mirroring::FourScreen => nametable,
+            Mirroring::SingleScreenLower => 0,
+            Mirroring::SingleScreenUpper => 1,
+        }
+    }
+}
+
+/// Returns a seed for random RAM that differs between runs.
+///
+/// # Return
+/// * `u64` - The nanoseconds of the system clock.
+pub fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
+}
+
 #[derive(Default)]
 /// This struct combines the different peripherals of the NES.
 pub struct Bus {
@@ -13,17 +67,495 @@ pub struct Bus {
     pub cpu: Cpu6502,
     /// Cartridge which is connected to the bus.
     pub cartridge: Cartridge,
-    /// The current number of remaining cycles for an instruction.
-    pub cycle: u16,
-    /// Mapper used for accessing memory.
-    pub mapper: MapperType,
+    /// Progress of the instruction that is executed, one cycle per tick.
+    pub instruction: InstructionState,
+    /// Number of cycles the CPU is halted while OAM DMA copies sprite data to the PPU.
+    pub stall_cycles: u16,
+    /// Page OAM DMA copies to the PPU, `None` if no OAM DMA is running.
+    pub oam_dma_page: Option<u8>,
+    /// Read and write cycles OAM DMA has run, it reads a byte on the even cycles and writes it on the odd ones.
+    pub oam_dma_cycle: u16,
+    /// Cycles before OAM DMA continues, for the halt and alignment cycles and for DMC DMA taking over the bus.
+    pub oam_dma_delay: u16,
+    /// Bytes OAM DMA has copied. The PPU only takes the whole OAM at once, so they are passed on after the last write.
+    pub oam_dma_data: Vec<u8>,
+    /// Cycles left until a DMC DMA that halted the CPU has read its sample byte.
+    pub dmc_dma_cycles: u8,
+    /// Indicates whether the PPU requested an NMI that has not been handled yet.
+    pub nmi_pending: bool,
+    /// Interrupt seen at the end of the last cycle.
+    pub pending_interrupt: Option<Interrupt>,
+    /// Interrupt seen at the end of the cycle before, which decides whether an interrupt follows the current instruction.
+    pub polled_interrupt: Option<Interrupt>,
+    /// Mapper used for accessing the cartridge.
+    pub mapper: Box<dyn Mapper>,
     /// Controller to handle user input.
     pub controller: Controller,
+    /// Controller in the second port ($4017).
+    pub controller2: Controller,
+    /// Buttons pressed on the second controller. The window only reads one controller, so these are set by the program.
+    pub controller2_buttons: Buttons,
+    /// Device plugged into the expansion port, if any.
+    pub expansion: Option<Box<dyn ExpansionDevice>>,
+    /// Audio processing unit, which also holds the frame counter.
+    pub apu: Apu,
+    /// Value last driven on the data bus. Reads of lines no device drives return it.
+    pub open_bus: u8,
     /// Flag to stop the program
     pub jam: bool,
+    /// File the battery-backed memory of the cartridge is stored in, `None` if it is not persisted.
+    pub save_file: Option<SaveFile>,
+    /// Nametable memory, kept here to support mappers that change the mirroring.
+    pub nametables: Nametables,
+    /// Mirroring forced by the user, overriding the one of the mapper.
+    pub mirroring_override: Option<Mirroring>,
+    /// Whether power cycles from the frontend fill the internal RAM with random values instead of zeros.
+    pub random_ram: bool,
+
+    // Copies of PPU registers, the PPU does not pass pattern table writes to the cartridge itself
+    /// Value last written to PPUCTRL ($2000).
+    pub ppu_ctrl: u8,
+    /// Nametable (0-3) last selected through PPUCTRL ($2000) or the first write to PPUADDR ($2006).
+    pub ppu_nametable: u8,
+    /// Value last written to PPUMASK ($2001).
+    pub ppu_mask: u8,
+    /// Number of PPU cycles since the start of the frame, used to clock scanline counters of mappers.
+    pub ppu_dot: u32,
+    /// VRAM address the next PPUDATA ($2007) access goes to.
+    pub ppu_addr: u16,
+    /// Indicates whether the next write to PPUADDR ($2006) or PPUSCROLL ($2005) is the second one.
+    pub ppu_addr_toggle: bool,
 }
 
 impl Bus {
+    /// Builds the CPU, mapper and memory map for a parsed cartridge and jumps to its reset vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - The cartridge that is inserted, its PRG ROM stays in the cartridge and is read through the mapper.
+    ///
+    /// # Return
+    /// * `Result<Bus, RomError>` - The bus in its power-on state, or an error if the mapper is not supported.
+    pub fn from_cartridge(cartridge: Cartridge) -> Result<Bus, RomError> {
+        // Bus implements Drop, so the remaining fields cannot be filled with `..Default::default()`
+        let mut bus = Bus::default();
+        bus.mapper = get_mapper(&cartridge)?;
+        bus.cartridge = cartridge;
+        bus.controller = Controller::new();
+        bus.controller2 = Controller::new();
+        bus.power_on(None);
+        bus.nametables.mirroring = bus.mirroring();
+        Ok(bus)
+    }
+
+    /// Puts the CPU in the state it has after power-on and the reset sequence, with the program counter at the reset vector. The mapper is expected to be in its power-on state already.
+    ///
+    /// # Arguments
+    ///
+    /// * `ram_seed` - Seed to fill the internal RAM with random values, `None` to clear it.
+    ///
+    /// Nothing is returned.
+    fn power_on(&mut self, ram_seed: Option<u64>) {
+        self.cpu = Cpu6502 {
+            sp: 0x01fd,
+            irq_dis: true,
+            ..Default::default()
+        };
+        if let Some(seed) = ram_seed {
+            self.randomize_ram(seed);
+        }
+        for (i, byte) in self.cartridge.trainer_data.clone().into_iter().enumerate() {
+            self.mapper
+                .cpu_write(&mut self.cartridge, 0x7000 + i as u16, byte);
+        }
+        self.cpu.pc = u16::from_le_bytes([self.peek(0xfffc), self.peek(0xfffd)]); // Reset vector
+        self.apu.power_on();
+        self.instruction = InstructionState::default();
+        self.stall_cycles = 0;
+        self.oam_dma_page = None;
+        self.dmc_dma_cycles = 0;
+        self.nmi_pending = false;
+        self.pending_interrupt = None;
+        self.polled_interrupt = None;
+        self.jam = false;
+    }
+
+    /// Fills the internal RAM with pseudo-random values, like the RAM of most consoles after power-on.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed of the values, the same seed always gives the same RAM.
+    ///
+    /// Nothing is returned.
+    pub fn randomize_ram(&mut self, seed: u64) {
+        let mut state = seed | 1; // Xorshift never leaves 0
+        for byte in &mut self.cpu.mem {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *byte = (state >> 32) as u8;
+        }
+    }
+
+    /// Presses the reset button. The CPU abandons its instruction and runs the interrupt sequence through the reset vector, which lowers the stack pointer by 3 and sets the interrupt disable flag. The APU channels are silenced, the RAM, the other registers and the mapper keep their state.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU, whose control registers are reset as well.
+    ///
+    /// Nothing is returned.
+    pub fn reset(&mut self, ppu: &mut Ppu) {
+        self.instruction = InstructionState::default();
+        self.stall_cycles = 0;
+        self.oam_dma_page = None;
+        self.dmc_dma_cycles = 0;
+        self.nmi_pending = false;
+        self.pending_interrupt = None;
+        self.polled_interrupt = Some(Interrupt::Reset);
+        self.jam = false;
+        self.apu.reset();
+        self.reset_ppu(ppu);
+    }
+
+    /// Switches the console off and on again. The CPU and its RAM return to their power-on state, and the mapper is rebuilt with its power-on registers. The memory of the cartridge, including battery-backed RAM, is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU. It is owned by the run loop and cannot be rebuilt, so it gets the register writes of a reset.
+    /// * `ram_seed` - Seed to fill the internal RAM with random values, `None` to clear it.
+    ///
+    /// Nothing is returned.
+    pub fn power_cycle(&mut self, ppu: &mut Ppu, ram_seed: Option<u64>) {
+        self.mapper = get_mapper(&self.cartridge)
+            .expect("the mapper was supported when the cartridge was inserted");
+        self.power_on(ram_seed);
+        self.reset_ppu(ppu);
+        self.update_mirroring(ppu);
+    }
+
+    /// Clears PPUCTRL, PPUMASK and the write toggle of the PPU, which the reset line of the console does.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// Nothing is returned.
+    fn reset_ppu(&mut self, ppu: &mut Ppu) {
+        self.data_write(ppu, 0x2000, 0);
+        self.data_write(ppu, 0x2001, 0);
+        self.data_read(ppu, 0x2002); // Clears the write toggle
+    }
+
+    /// Presses the console button the user selected with a chord on the controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU, which holds the state of the controller.
+    ///
+    /// Nothing is returned.
+    fn poll_console_buttons(&mut self, ppu: &mut Ppu) {
+        match self.controller.console_button(ppu.get_joypad_state()) {
+            Some(ConsoleButton::Reset) => {
+                log::info!("Reset");
+                self.reset(ppu);
+            }
+            Some(ConsoleButton::Power) => {
+                log::info!("Power cycle");
+                let seed = self.random_ram.then(clock_seed);
+                self.power_cycle(ppu, seed);
+            }
+            None => {}
+        }
+    }
+
+    /// Restores the battery-backed memory of the cartridge from a save file and keeps it in sync with that file from now on. Cartridges without a battery ignore the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `save_file` - The save file belonging to the ROM.
+    ///
+    /// # Return
+    /// * `io::Result<()>` - An error if the save file exists but could not be read.
+    pub fn attach_save_file(&mut self, mut save_file: SaveFile) -> io::Result<()> {
+        if self.mapper.save_ram(&self.cartridge).is_none() {
+            return Ok(());
+        }
+        if let Some(data) = save_file.load()? {
+            self.mapper.load_save_ram(&mut self.cartridge, &data);
+        }
+        self.save_file = Some(save_file);
+        Ok(())
+    }
+
+    /// Writes the battery-backed memory to the save file, if there is one.
+    ///
+    /// Nothing is returned.
+    pub fn flush_save_file(&mut self) {
+        if let (Some(save_file), Some(data)) = (
+            self.save_file.as_mut(),
+            self.mapper.save_ram(&self.cartridge),
+        ) {
+            if let Err(e) = save_file.flush(data) {
+                log::warn!("could not write '{}': {e}", save_file.path.display());
+            }
+        }
+    }
+
+    /// Returns the nametable mirroring currently selected by the cartridge and its mapper, or by the user.
+    ///
+    /// # Return
+    /// * `Mirroring` - Mirroring the nametables are arranged in.
+    pub fn mirroring(&self) -> Mirroring {
+        self.mirroring_override
+            .unwrap_or_else(|| self.mapper.mirroring(&self.cartridge))
+    }
+
+    /// Forces a mirroring instead of the one selected by the mapper. Has to be called before the PPU runs, as the nametables are not copied.
+    ///
+    /// # Arguments
+    ///
+    /// * `mirroring` - Mirroring the nametables are arranged in from now on.
+    ///
+    /// Nothing is returned.
+    pub fn override_mirroring(&mut self, mirroring: Mirroring) {
+        self.mirroring_override = Some(mirroring);
+        self.nametables.mirroring = mirroring;
+    }
+
+    /// Writes bytes to the VRAM of the PPU without changing the address, write toggle and nametable selection the game has set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `addr` - VRAM address of the first byte.
+    /// * `data` - The bytes that are written to consecutive addresses.
+    ///
+    /// Nothing is returned.
+    fn write_ppu_vram(&self, ppu: &mut Ppu, addr: u16, data: &[u8]) {
+        if self.ppu_addr_toggle {
+            // Complete the pending address so the next write is taken as the high byte
+            ppu.write_ppu_register(PpuRegister::Address, self.ppu_addr as u8);
+        }
+        ppu.write_ppu_register(PpuRegister::Controller, self.ppu_ctrl & !0b100);
+        ppu.write_ppu_register(PpuRegister::Address, (addr >> 8) as u8);
+        ppu.write_ppu_register(PpuRegister::Address, addr as u8);
+        for &byte in data {
+            ppu.write_ppu_register(PpuRegister::Data, byte);
+        }
+
+        ppu.write_ppu_register(PpuRegister::Address, (self.ppu_addr >> 8) as u8);
+        ppu.write_ppu_register(PpuRegister::Address, self.ppu_addr as u8);
+        if self.ppu_addr_toggle {
+            ppu.write_ppu_register(PpuRegister::Address, (self.ppu_addr >> 8) as u8);
+        }
+        ppu.write_ppu_register(
+            PpuRegister::Controller,
+            (self.ppu_ctrl & !0b11) | self.ppu_nametable,
+        );
+    }
+
+    /// Stores a byte written to a nametable and copies it to the other nametables of the PPU showing the same page.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU, which has already received the byte at `addr`.
+    /// * `addr` - VRAM address in $2000-$3EFF.
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    fn write_nametable(&mut self, ppu: &mut Ppu, addr: u16, data: u8) {
+        let nametable = (addr as usize >> 10) & 0b11;
+        let offset = addr as usize & 0x3ff;
+        let page = self.nametables.page(nametable);
+        self.nametables.ram[page * 1024 + offset] = data;
+        for other in (0..4).filter(|&other| other != nametable) {
+            if self.nametables.page(other) == page {
+                self.write_ppu_vram(ppu, (0x2000 + other * 1024 + offset) as u16, &[data]);
+            }
+        }
+    }
+
+    /// Refills the nametables of the PPU if the mapper has selected another mirroring.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// Nothing is returned.
+    fn update_mirroring(&mut self, ppu: &mut Ppu) {
+        let mirroring = self.mirroring();
+        if mirroring == self.nametables.mirroring {
+            return;
+        }
+        self.nametables.mirroring = mirroring;
+        for nametable in 0..4 {
+            let start = self.nametables.page(nametable) * 1024;
+            let page: [u8; 1024] = self.nametables.ram[start..start + 1024].try_into().unwrap();
+            self.write_ppu_vram(ppu, (0x2000 + nametable * 1024) as u16, &page);
+        }
+    }
+
+    /// Keeps the copies of the PPU registers up to date and passes pattern table writes to the mapper.
+    ///
+    /// # Arguments
+    ///
+    /// * `register` - Number of the PPU register (0-7) that is accessed.
+    /// * `data` - The data that is written, `None` for reads.
+    ///
+    /// Nothing is returned.
+    fn shadow_ppu_register(&mut self, register: u16, data: Option<u8>) {
+        match (register, data) {
+            (0, Some(data)) => {
+                self.ppu_ctrl = data;
+                self.ppu_nametable = data & 0b11;
+            }
+            (1, Some(data)) => self.ppu_mask = data,
+            (2, None) => self.ppu_addr_toggle = false,
+            (5, Some(_)) => self.ppu_addr_toggle = !self.ppu_addr_toggle,
+            (6, Some(data)) => {
+                self.ppu_addr = if self.ppu_addr_toggle {
+                    (self.ppu_addr & 0xff00) | data as u16
+                } else {
+                    self.ppu_nametable = (data >> 2) & 0b11;
+                    (self.ppu_addr & 0x00ff) | ((data as u16 & 0x3f) << 8)
+                };
+                self.ppu_addr_toggle = !self.ppu_addr_toggle;
+            }
+            (7, data) => {
+                if let (0x0000..=0x1fff, Some(data)) = (self.ppu_addr, data) {
+                    self.mapper
+                        .ppu_write(&mut self.cartridge, self.ppu_addr, data);
+                }
+                let increment = if self.ppu_ctrl & 0b100 != 0 { 32 } else { 1 };
+                self.ppu_addr = (self.ppu_addr + increment) & 0x3fff;
+            }
+            _ => {}
+        }
+    }
+
+    /// Follows the PPU through the three cycles it runs per CPU cycle and notifies the mapper once per rendered scanline, on the dot where PPU address line A12 rises. That dot depends on the pattern tables of the background and the sprites.
+    ///
+    /// Nothing is returned.
+    fn clock_ppu_scanlines(&mut self) {
+        // 8x16 sprites pick their table per tile, but the empty sprite slots fetch tile $FF from $1000
+        let sprites_high = self.ppu_ctrl & 0b10_1000 != 0;
+        let background_high = self.ppu_ctrl & 0b1_0000 != 0;
+        let a12_rise = match (background_high, sprites_high) {
+            // Sprite fetches follow the background fetches
+            (false, true) => Some(260),
+            // The background fetches for the next scanline follow the sprite fetches
+            (true, false) => Some(324),
+            // A12 stays low, or only drops for the short nametable fetches which the MMC3 filters out
+            _ => None,
+        };
+        let rendering = self.ppu_mask & 0b1_1000 != 0;
+        for _ in 0..3 {
+            self.ppu_dot = (self.ppu_dot + 1) % (341 * 262);
+            let (scanline, dot) = (self.ppu_dot / 341, self.ppu_dot % 341);
+            if rendering && a12_rise == Some(dot) && (scanline < 240 || scanline == 261) {
+                self.mapper.notify_scanline();
+            }
+        }
+    }
+
+    /// Runs the APU for one CPU cycle. The sample byte the DMC asks for is fetched on the next read of the CPU, unless the CPU is not reading because it is halted for OAM DMA or jammed. The DMA then takes 2 cycles of the OAM DMA, or none.
+    ///
+    /// Nothing is returned.
+    fn clock_apu(&mut self) {
+        self.apu.tick();
+        if self.jam || self.stall_cycles != 0 {
+            if let Some(addr) = self.apu.dmc.sample_request() {
+                self.fetch_dmc_sample(addr);
+                if !self.jam {
+                    self.stall_cycles += 2;
+                    self.oam_dma_delay += 2;
+                }
+            }
+        }
+    }
+
+    /// Runs a cycle of the DMC DMA that halted the CPU. The CPU keeps repeating its read while it waits for the APU cycle the DMA reads on, and the controllers see the repeated reads as one long read. The sample byte is read through the mapper in the last cycle.
+    ///
+    /// Nothing is returned.
+    pub fn dmc_dma_cycle(&mut self) {
+        self.dmc_dma_cycles -= 1;
+        if self.dmc_dma_cycles == 0 {
+            if let Some(addr) = self.apu.dmc.sample_request() {
+                self.fetch_dmc_sample(addr);
+            }
+        }
+    }
+
+    /// Runs one cycle of OAM DMA, if it is running. Every byte of the page is read through the bus on one cycle and written to OAM on the next.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU, which receives the page after the last write.
+    ///
+    /// Nothing is returned.
+    fn oam_dma_step(&mut self, ppu: &mut Ppu) {
+        let page = match self.oam_dma_page {
+            Some(page) => page,
+            None => return,
+        };
+        if self.oam_dma_delay != 0 {
+            self.oam_dma_delay -= 1;
+            return;
+        }
+        if self.oam_dma_cycle % 2 == 0 {
+            let addr = ((page as u16) << 8) | (self.oam_dma_cycle / 2);
+            let data = self.bus_read(ppu, addr);
+            self.oam_dma_data.push(data);
+        }
+        self.oam_dma_cycle += 1;
+        if self.oam_dma_cycle == 512 {
+            let mut oam = [0; 256];
+            oam.copy_from_slice(&self.oam_dma_data);
+            ppu.write_oam_dma(oam);
+            self.oam_dma_page = None;
+        }
+    }
+
+    /// Reads a sample byte for the DMC.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address of the byte, which is mapped to the cartridge.
+    ///
+    /// Nothing is returned.
+    fn fetch_dmc_sample(&mut self, addr: u16) {
+        let data = self.peek(addr);
+        self.open_bus = data;
+        self.apu.dmc.fill_sample_buffer(data);
+    }
+
+    /// Returns whether a device pulls the shared IRQ line low. Every source keeps the line low until the program acknowledges it at that source, so the sources are combined here.
+    ///
+    /// # Return
+    /// * `bool` - The state of the IRQ line.
+    pub fn irq_line(&self) -> bool {
+        self.mapper.irq() || self.apu.irq()
+    }
+
+    /// Reads memory that has no side effects on reading, i.e. the CPU RAM and the cartridge. Addresses the cartridge does not drive return the open bus value.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address that shall be read.
+    ///
+    /// # Return
+    /// * `u8` - read data byte of address.
+    fn peek(&self, addr: u16) -> u8 {
+        match addr {
+            0x0000..=0x1fff => self.cpu.memory_read(addr),
+            0x6000..=0xffff => self
+                .mapper
+                .cpu_read(&self.cartridge, addr)
+                .unwrap_or(self.open_bus),
+            _ => self.open_bus, // The registers are not peeked and no mapper uses the expansion area
+        }
+    }
+
     /// Writes data to the dedicated memory. Depending on the address, either the CPU's memory is written to or other peripherals are activated.
     ///
     /// # Arguments
@@ -34,9 +566,12 @@ impl Bus {
     ///
     /// Nothing is returned.
     pub fn data_write(&mut self, ppu: &mut Ppu, addr: u16, data: u8) {
+        self.open_bus = data;
         if (0x2000..=0x3fff).contains(&addr) {
             //ppu register mapping
             let remainder = (addr - 0x2000) % 8;
+            let vram_addr = self.ppu_addr;
+            self.shadow_ppu_register(remainder, Some(data));
             match remainder {
                 0 => ppu.write_ppu_register(PpuRegister::Controller, data),
                 1 => ppu.write_ppu_register(PpuRegister::Mask, data),
@@ -48,26 +583,20 @@ impl Bus {
                 7 => ppu.write_ppu_register(PpuRegister::Data, data),
                 _ => panic!("Out of ppu map bound"),
             }
-        } else if addr == 0x4014 {
-            let page_start: u16 = ((data as u16) << 8) % 0x800;
-            if page_start >= 0x2000 {
-                panic!("Page address is out of CPU RAM bound");
+            if remainder == 7 && (0x2000..0x3f00).contains(&vram_addr) {
+                self.write_nametable(ppu, vram_addr, data);
             }
-            let mut oam_data: [u8; 256] = [0; 256];
-            for i in page_start..=page_start + 255 {
-                oam_data[(i - page_start) as usize] = self.data_read(ppu, i);
-            }
-            ppu.write_oam_dma(oam_data);
-            self.cycle += 513; // TODO this can be 514 cycles too
-        } else if addr == 0x4016 {
-            self.controller.set_strobe(data);
-        } else if !(0x2000..0x4020).contains(&addr) {
-            self.cpu
-                .memory_write(&self.cartridge, &mut self.mapper, addr, data);
+        } else if (0x4000..0x4020).contains(&addr) {
+            self.io_write(addr, data);
+        } else if addr >= 0x6000 {
+            self.mapper.cpu_write(&mut self.cartridge, addr, data);
+            self.update_mirroring(ppu);
+        } else if addr < 0x2000 {
+            self.cpu.memory_write(addr, data);
         }
     }
 
-    /// Reads data from the dedicated memory. Depending on the address, either the CPU's memory is read or other peripherals are activated.
+    /// Reads data from the dedicated memory for the CPU. If the DMC needs a sample byte, the DMA halts the CPU on this read, which then has to be repeated after the DMA.
     ///
     /// # Arguments
     ///
@@ -77,9 +606,27 @@ impl Bus {
     /// # Return
     /// * `u8` - read data byte of address.
     pub fn data_read(&mut self, ppu: &mut Ppu, addr: u16) -> u8 {
-        if (0x2000..=0x3fff).contains(&addr) {
+        if self.apu.dmc.sample_request().is_some() {
+            // Dummy and read cycle after this halt cycle, plus one to line up with the APU cycle
+            self.dmc_dma_cycles = if self.apu.odd_cycle { 3 } else { 2 };
+        }
+        self.bus_read(ppu, addr)
+    }
+
+    /// Reads data from the dedicated memory. Depending on the address, either the CPU's memory is read or other peripherals are activated.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `addr` - The address that shall be read.
+    ///
+    /// # Return
+    /// * `u8` - read data byte of address.
+    fn bus_read(&mut self, ppu: &mut Ppu, addr: u16) -> u8 {
+        let data = if (0x2000..=0x3fff).contains(&addr) {
             //ppu register mapping
             let remainder = (addr - 0x2000) % 8;
+            self.shadow_ppu_register(remainder, None);
             match remainder {
                 0 => ppu.read_ppu_register(PpuRegister::Controller, self),
                 1 => ppu.read_ppu_register(PpuRegister::Mask, self),
@@ -91,12 +638,77 @@ impl Bus {
                 7 => ppu.read_ppu_register(PpuRegister::Data, self),
                 _ => panic!("Out of ppu map bound"),
             }
-        } else if addr == 0x4016 {
-            self.controller.get_controller_byte(ppu)
-        } else if !(0x2000..0x4020).contains(&addr) {
-            self.cpu.memory_read(&self.mapper, addr)
+        } else if (0x4000..0x4020).contains(&addr) {
+            self.io_read(ppu, addr)
         } else {
-            0 // Default to 0
+            self.peek(addr)
+        };
+        // $4015 is read inside the CPU, the external data bus keeps its value
+        if addr != 0x4015 {
+            self.open_bus = data;
+        }
+        data
+    }
+
+    /// Writes one of the I/O registers at $4000-$401F.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Address of the register.
+    /// * `data` - The byte that is written.
+    ///
+    /// Nothing is returned.
+    fn io_write(&mut self, addr: u16, data: u8) {
+        match addr {
+            0x4014 => {
+                // Halt cycle, plus an alignment cycle if the DMA would start with a write
+                let delay = if self.apu.odd_cycle { 2 } else { 1 };
+                self.oam_dma_page = Some(data);
+                self.oam_dma_cycle = 0;
+                self.oam_dma_delay = delay;
+                self.oam_dma_data.clear();
+                self.stall_cycles += delay + 512;
+            }
+            0x4016 => {
+                // OUT0 strobes both controllers, all outputs go to the expansion port
+                self.controller.set_strobe(data);
+                self.controller2.set_strobe(data);
+                if let Some(expansion) = self.expansion.as_mut() {
+                    expansion.write(data & 0b111);
+                }
+            }
+            0x4000..=0x4013 | 0x4015 | 0x4017 => self.apu.write_register(addr, data),
+            _ => {} // Test registers, disabled on retail consoles
+        }
+    }
+
+    /// Reads one of the I/O registers at $4000-$401F. Bits that no device drives return the open bus value.
+    ///
+    /// # Arguments
+    ///
+    /// * `ppu` - Borrowed instance of PPU, which holds the buttons of the first controller.
+    /// * `addr` - Address of the register.
+    ///
+    /// # Return
+    /// * `u8` - The value of the register.
+    fn io_read(&mut self, ppu: &mut Ppu, addr: u16) -> u8 {
+        match addr {
+            0x4015 => self.apu.read_status() | (self.open_bus & 0b0010_0000),
+            0x4016 | 0x4017 => {
+                let port = (addr - 0x4016) as usize;
+                let controller = if port == 0 {
+                    self.controller.get_controller_byte(ppu.get_joypad_state())
+                } else {
+                    self.controller2
+                        .get_controller_byte(self.controller2_buttons)
+                };
+                let expansion = self
+                    .expansion
+                    .as_mut()
+                    .map_or(0, |expansion| expansion.read(port) & 0b1_1111);
+                (self.open_bus & 0b1110_0000) | controller | expansion
+            }
+            _ => self.open_bus, // Write-only APU registers and disabled test registers
         }
     }
 }
@@ -104,40 +716,38 @@ impl Bus {
 /// See docs of `Cpu` for explanations of each function
 impl Cpu for Bus {
     fn tick(&mut self, ppu: &mut Ppu) -> Result<(), Box<dyn Error>> {
+        self.poll_console_buttons(ppu);
+        self.clock_ppu_scanlines();
+        self.clock_apu();
+        self.mapper.notify_cpu_cycle();
+        if self.save_file.as_mut().map_or(false, SaveFile::tick) {
+            self.flush_save_file();
+        }
         if !self.jam {
-            if self.cycle != 0 {
-                self.cycle -= 1;
-                return Ok(());
+            if self.stall_cycles != 0 {
+                self.stall_cycles -= 1;
+                self.oam_dma_step(ppu);
+                Instruction::poll_interrupts(self);
+            } else {
+                Instruction::execute_cycle(self, ppu);
             }
-
-            Instruction::do_instruction(self, ppu);
-            self.cycle -= 1;
         }
         Result::Ok(())
     }
 
     fn ppu_read_chr_rom(&self, offset: u16) -> u8 {
-        self.mapper.get_chr_data(&self.cartridge, offset)
+        self.mapper.ppu_read(&self.cartridge, offset)
     }
 
     fn non_maskable_interrupt(&mut self) {
-        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal); // Not used as nmi vector never in ppu range
-
-        let p = self.cpu.carry as u8 |
-            (self.cpu.zero as u8) << 1 |
-            (self.cpu.irq_dis as u8) << 2 |
-            (self.cpu.dec as u8) << 3 |
-            (self.cpu.b as u8) << 4 |
-            0b0010_0000 | //ignore_flag
-            (self.cpu.overflow as u8) << 6 |
-            (self.cpu.negative as u8) << 7;
-
-        self.cpu.stack_push(((self.cpu.pc >> 8) & 0xff) as u8);
-        self.cpu.stack_push((self.cpu.pc & 0xff) as u8);
-        self.cpu.stack_push(p);
-        self.cpu.irq_dis = true;
-        self.cpu.pc = (self.data_read(&mut dummy_ppu, 0xFFFA) as u16)
-            | ((self.data_read(&mut dummy_ppu, 0xFFFB) as u16) << 8);
+        self.nmi_pending = true;
+    }
+}
+
+impl Drop for Bus {
+    fn drop(&mut self) {
+        self.flush_save_file();
+        self.apu.flush();
     }
 }
 
@@ -146,15 +756,7 @@ impl Cpu for Bus {
 /// tests to find bugs in your cpu.
 impl TestableCpu for Bus {
     fn get_cpu(rom: &[u8]) -> Result<Self, Box<dyn Error>> {
-        let cartridge = Cartridge::generate_from_rom(rom);
-        Ok(Bus {
-            cpu: Cpu6502::generate_from_rom(rom),
-            cartridge: Cartridge::generate_from_rom(rom),
-            cycle: 0,
-            mapper: MapperType::get_mapper(cartridge.mapper_number, cartridge),
-            controller: Controller::new(),
-            jam: false,
-        })
+        Ok(Bus::from_cartridge(Cartridge::generate_from_rom(rom)?)?)
     }
 
     fn set_program_counter(&mut self, value: u16) {
@@ -162,14 +764,60 @@ impl TestableCpu for Bus {
     }
 
     fn memory_read(&self, address: u16) -> u8 {
-        self.cpu.memory_read(&self.mapper, address)
+        self.peek(address)
     }
 }
 
 #[cfg(test)]
 mod mycpu_tests {
-    use crate::Bus;
-    use tudelft_nes_ppu::{Mirroring, Ppu};
+    use crate::controller::ExpansionDevice;
+    use crate::save::SaveFile;
+    use crate::{Bus, Instruction};
+    use tudelft_nes_ppu::{Buttons, Cpu, Mirroring, Ppu};
+    use tudelft_nes_test::TestableCpu;
+
+    #[test]
+    fn test_get_cpu_error() {
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 1, 0, 0xf0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x4000, 0);
+        let err = Bus::get_cpu(&rom)
+            .err()
+            .expect("mapper 15 is not supported");
+        assert_eq!(err.to_string(), "mapper 15 is not supported");
+        assert!(Bus::get_cpu(&rom[..8]).is_err());
+
+        let mut rom = vec![b'N', b'E', b'S', 0x1a, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        rom.resize(16 + 0x4000, 0);
+        let err = Bus::get_cpu(&rom).err().expect("PRG ROM is truncated");
+        assert_eq!(
+            err.to_string(),
+            "PRG ROM is truncated: expected 32768 bytes, found 16384"
+        );
+    }
+
+    #[test]
+    fn test_get_cpu() {
+        // MMC1 with 128 kB PRG ROM, 8 kB CHR ROM and a trainer
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 8, 1, 0x14, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 512 + 8 * 0x4000 + 0x2000, 0);
+        rom[16] = 0x42;
+        let last_bank = 16 + 512 + 7 * 0x4000;
+        rom[last_bank + 0x3ffc] = 0x34; // Reset vector in the last bank
+        rom[last_bank + 0x3ffd] = 0xc2;
+        rom[last_bank + 0x0234] = 0xea;
+
+        let bus = Bus::get_cpu(&rom).unwrap();
+        assert_eq!(bus.cpu.pc, 0xc234);
+        assert_eq!(bus.cpu.sp, 0x01fd);
+        assert!(bus.cpu.irq_dis);
+        assert_eq!(bus.memory_read(0xc234), 0xea);
+        assert_eq!(bus.memory_read(0x7000), 0x42);
+        assert_eq!(bus.cartridge.prg_rom_data.len(), 8 * 0x4000);
+    }
 
     #[test]
     fn test_ram_mirror() {
@@ -198,4 +846,467 @@ mod mycpu_tests {
         test_cpu.data_read(&mut ppu, 0x2000); // Dummy read
         assert_eq!(test_cpu.data_read(&mut ppu, 0x2004), 0x63);
     }
+
+    #[test]
+    fn test_mmc3_irq() {
+        // MMC3 with 32 kB PRG ROM, the last 8 kB bank is fixed at $E000
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 2, 1, 0x40, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x8000 + 0x2000, 0);
+        let last_bank = 16 + 0x6000;
+        rom[last_bank..last_bank + 4].copy_from_slice(&[0x58, 0x4c, 0x01, 0xe0]); // CLI; JMP $E001
+        rom[last_bank + 0x10..last_bank + 0x13].copy_from_slice(&[0x4c, 0x10, 0xe0]); // JMP $E010
+        rom[last_bank + 0x1ffc..last_bank + 0x2000].copy_from_slice(&[0x00, 0xe0, 0x10, 0xe0]);
+
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        test_cpu.data_write(&mut ppu, 0x2000, 0b1000); // Sprites at $1000
+        test_cpu.data_write(&mut ppu, 0x2001, 0b1_1000); // Enable rendering
+        test_cpu.data_write(&mut ppu, 0xc000, 1); // Latch
+        test_cpu.data_write(&mut ppu, 0xc001, 0); // Reload
+        test_cpu.data_write(&mut ppu, 0xe001, 0); // Enable IRQ
+
+        // The counter is reloaded on scanline 0 and reaches 0 on scanline 1
+        for _ in 0..150 {
+            test_cpu.tick(&mut ppu).unwrap();
+        }
+        assert!(!test_cpu.irq_line());
+        for _ in 0..150 {
+            test_cpu.tick(&mut ppu).unwrap();
+        }
+        while test_cpu.instruction.cycle != 0 {
+            test_cpu.tick(&mut ppu).unwrap();
+        }
+        assert!(test_cpu.irq_line());
+        assert_eq!(test_cpu.cpu.pc, 0xe010);
+        assert!(test_cpu.cpu.irq_dis);
+        assert_eq!(test_cpu.cpu.mem[0x1fb] & 0b0011_0000, 0b0010_0000); // B flag clear
+    }
+
+    #[test]
+    fn test_mmc3_irq_pattern_tables() {
+        let rom = [
+            &[
+                b'N', b'E', b'S', 0x1a, 2, 1, 0x40, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ][..],
+            &[0; 0x8000 + 0x2000],
+        ]
+        .concat();
+        // Returns the dot on the first scanline at which the IRQ with latch 0 is seen, which moves in steps of three dots
+        let irq_dot = |ppu_ctrl: u8| {
+            let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+            let mut ppu = Ppu::new(Mirroring::Vertical);
+            test_cpu.data_write(&mut ppu, 0xc001, 0); // Reload with latch 0
+            test_cpu.data_write(&mut ppu, 0xe001, 0); // Enable IRQ
+            test_cpu.ppu_ctrl = ppu_ctrl;
+            test_cpu.ppu_mask = 0b1_1000;
+            test_cpu.ppu_dot = 0;
+            while test_cpu.ppu_dot < 341 {
+                test_cpu.clock_ppu_scanlines();
+                if test_cpu.irq_line() {
+                    return Some(test_cpu.ppu_dot);
+                }
+            }
+            None
+        };
+
+        assert_eq!(irq_dot(0b00_0000), None); // Background and sprites at $0000
+        assert_eq!(irq_dot(0b00_1000), Some(261)); // Sprites at $1000
+        assert_eq!(irq_dot(0b01_0000), Some(324)); // Background at $1000
+        assert_eq!(irq_dot(0b01_1000), None); // Background and sprites at $1000
+        assert_eq!(irq_dot(0b10_0000), Some(261)); // 8x16 sprites
+        assert_eq!(irq_dot(0b11_0000), None); // 8x16 sprites, background at $1000
+    }
+
+    #[test]
+    fn test_mmc1_mirroring() {
+        // MMC1 with vertical mirroring in the header
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 2, 1, 0x11, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x8000 + 0x2000, 0);
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        let mut ppu = Ppu::new(Mirroring::FourScreen);
+        assert_eq!(test_cpu.mirroring(), Mirroring::Vertical);
+        let read_vram = |test_cpu: &mut Bus, ppu: &mut Ppu, addr: u16| {
+            test_cpu.data_read(ppu, 0x2002);
+            test_cpu.data_write(ppu, 0x2006, (addr >> 8) as u8);
+            test_cpu.data_write(ppu, 0x2006, addr as u8);
+            test_cpu.data_read(ppu, 0x2007); // Fills the read buffer
+            test_cpu.data_read(ppu, 0x2007)
+        };
+        let write_control = |test_cpu: &mut Bus, ppu: &mut Ppu, value: u8| {
+            for bit in 0..5 {
+                test_cpu.data_write(ppu, 0x8000, (value >> bit) & 1);
+                // Writes on consecutive cycles are ignored
+                test_cpu.mapper.notify_cpu_cycle();
+                test_cpu.mapper.notify_cpu_cycle();
+            }
+        };
+
+        test_cpu.data_write(&mut ppu, 0x2006, 0x28);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x05);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2005), 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2405), 0x00);
+
+        // Switch to horizontal mirroring, the page written above is now shown in the upper nametables
+        write_control(&mut test_cpu, &mut ppu, 0b0_0011);
+        assert_eq!(test_cpu.mirroring(), Mirroring::Horizontal);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2005), 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2405), 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2805), 0x00);
+
+        // The address of the game survives the copies to the other nametables
+        test_cpu.data_write(&mut ppu, 0x2006, 0x2c);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x07);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x22);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x33);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2808), 0x33);
+    }
+
+    #[test]
+    fn test_runtime_mirroring() {
+        // AxROM starts with single-screen mirroring of the lower page
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 2, 0, 0x70, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x8000, 0);
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        let mut ppu = Ppu::new(Mirroring::FourScreen);
+        let read_vram = |test_cpu: &mut Bus, ppu: &mut Ppu, addr: u16| {
+            test_cpu.data_read(ppu, 0x2002);
+            test_cpu.data_write(ppu, 0x2006, (addr >> 8) as u8);
+            test_cpu.data_write(ppu, 0x2006, addr as u8);
+            test_cpu.data_read(ppu, 0x2007); // Fills the read buffer
+            test_cpu.data_read(ppu, 0x2007)
+        };
+
+        test_cpu.data_write(&mut ppu, 0x2006, 0x2c);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x05);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2005), 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2405), 0x11);
+
+        // Select the upper page, which is still empty
+        test_cpu.data_write(&mut ppu, 0x8010, 0x10);
+        assert_eq!(test_cpu.mirroring(), Mirroring::SingleScreenUpper);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2805), 0x00);
+
+        // The address of the game survives the copies to the other nametables
+        test_cpu.data_write(&mut ppu, 0x2006, 0x20);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x07);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x22);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x33);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2c08), 0x33);
+        test_cpu.data_write(&mut ppu, 0x8010, 0x00);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2805), 0x11);
+        assert_eq!(read_vram(&mut test_cpu, &mut ppu, 0x2807), 0x00);
+    }
+
+    #[test]
+    fn test_save_file() {
+        // MMC1 with battery-backed PRG RAM
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 2, 1, 0x12, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x8000 + 0x2000, 0);
+        let dir = std::env::temp_dir().join(format!("nes-bus-save-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rom_path = dir.join("game.nes");
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        test_cpu
+            .attach_save_file(SaveFile::for_rom(&rom_path))
+            .unwrap();
+        test_cpu.data_write(&mut ppu, 0x6000, 0x12);
+        test_cpu.data_write(&mut ppu, 0x7fff, 0x34);
+        drop(test_cpu);
+        let saved = std::fs::read(dir.join("game.sav")).unwrap();
+        assert_eq!(saved.len(), 0x2000);
+        assert_eq!((saved[0], saved[0x1fff]), (0x12, 0x34));
+
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        test_cpu
+            .attach_save_file(SaveFile::for_rom(&rom_path))
+            .unwrap();
+        assert_eq!(test_cpu.memory_read(0x6000), 0x12);
+        assert_eq!(test_cpu.memory_read(0x7fff), 0x34);
+        drop(test_cpu);
+
+        // Without a battery nothing is saved
+        rom[6] = 0x10;
+        std::fs::remove_file(dir.join("game.sav")).unwrap();
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        test_cpu
+            .attach_save_file(SaveFile::for_rom(&rom_path))
+            .unwrap();
+        test_cpu.data_write(&mut ppu, 0x6000, 0x12);
+        drop(test_cpu);
+        assert!(!dir.join("game.sav").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_chr_ram_write() {
+        // UxROM with CHR RAM
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 2, 0, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x8000, 0);
+        let mut test_cpu = Bus::get_cpu(&rom).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        test_cpu.data_write(&mut ppu, 0x2000, 0b100); // Increment by 32
+        test_cpu.data_write(&mut ppu, 0x2006, 0x12);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x34);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x56);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x78);
+        assert_eq!(test_cpu.ppu_read_chr_rom(0x1234), 0x56);
+        assert_eq!(test_cpu.ppu_read_chr_rom(0x1254), 0x78);
+
+        // Reading PPUSTATUS resets the write toggle
+        test_cpu.data_write(&mut ppu, 0x2006, 0x01);
+        test_cpu.data_read(&mut ppu, 0x2002);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x00);
+        test_cpu.data_write(&mut ppu, 0x2006, 0x10);
+        test_cpu.data_read(&mut ppu, 0x2007); // Fills the read buffer
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x2007), 0x00);
+        test_cpu.data_write(&mut ppu, 0x2000, 0);
+        test_cpu.data_write(&mut ppu, 0x2007, 0x9a);
+        assert_eq!(test_cpu.ppu_read_chr_rom(0x0050), 0x9a);
+    }
+
+    /// Builds a UxROM cartridge whose banks start with 0xaa and 0xbb. The program in the fixed bank stores 0x42 at $10 and loops.
+    fn uxrom_program() -> Vec<u8> {
+        let mut rom = vec![
+            b'N', b'E', b'S', 0x1a, 2, 0, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        rom.resize(16 + 0x8000, 0);
+        rom[16] = 0xaa;
+        rom[16 + 0x4000..16 + 0x4007].copy_from_slice(&[0xbb, 0xa9, 0x42, 0x85, 0x10, 0x4c, 0x05]); // LDA #$42; STA $10; JMP $C005
+        rom[16 + 0x4007] = 0xc0;
+        rom[16 + 0x7ffc] = 0x01; // Reset vector
+        rom[16 + 0x7ffd] = 0xc0;
+        rom
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut test_cpu = Bus::get_cpu(&uxrom_program()).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        Instruction::do_instruction(&mut test_cpu, &mut ppu);
+        Instruction::do_instruction(&mut test_cpu, &mut ppu);
+        test_cpu.data_write(&mut ppu, 0x8000, 1);
+        test_cpu.data_write(&mut ppu, 0x2000, 0x80);
+        test_cpu.cpu.irq_dis = false;
+        test_cpu.jam = true;
+
+        test_cpu.reset(&mut ppu);
+        assert_eq!(test_cpu.ppu_ctrl, 0);
+        assert!(!test_cpu.jam);
+        assert_eq!(Instruction::do_instruction(&mut test_cpu, &mut ppu), 7);
+        assert_eq!(test_cpu.cpu.pc, 0xc001);
+        assert_eq!(test_cpu.cpu.sp, 0x01fa);
+        assert!(test_cpu.cpu.irq_dis);
+        assert_eq!(test_cpu.cpu.a, 0x42);
+        assert_eq!(test_cpu.memory_read(0x10), 0x42); // RAM is kept
+        assert_eq!(test_cpu.memory_read(0x8000), 0xbb); // So is the bank
+        assert_eq!(test_cpu.memory_read(0x01fd), 0x00); // Nothing was pushed
+    }
+
+    #[test]
+    fn test_power_cycle() {
+        let mut test_cpu = Bus::get_cpu(&uxrom_program()).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        Instruction::do_instruction(&mut test_cpu, &mut ppu);
+        Instruction::do_instruction(&mut test_cpu, &mut ppu);
+        test_cpu.data_write(&mut ppu, 0x8000, 1);
+
+        test_cpu.power_cycle(&mut ppu, None);
+        assert_eq!(test_cpu.cpu.pc, 0xc001);
+        assert_eq!(test_cpu.cpu.sp, 0x01fd);
+        assert_eq!(test_cpu.cpu.a, 0);
+        assert_eq!(test_cpu.memory_read(0x10), 0);
+        assert_eq!(test_cpu.memory_read(0x8000), 0xaa); // The mapper is back in its power-on state
+
+        test_cpu.power_cycle(&mut ppu, Some(1));
+        let ram = test_cpu.cpu.mem;
+        assert!(ram.iter().any(|&byte| byte != 0));
+        test_cpu.power_cycle(&mut ppu, Some(1));
+        assert_eq!(test_cpu.cpu.mem, ram);
+        test_cpu.power_cycle(&mut ppu, Some(2));
+        assert_ne!(test_cpu.cpu.mem, ram);
+    }
+
+    /// Expansion device that echoes its outputs on bits 1-3 of both ports.
+    struct EchoDevice(u8);
+
+    impl ExpansionDevice for EchoDevice {
+        fn write(&mut self, out: u8) {
+            self.0 = out;
+        }
+
+        fn read(&mut self, _port: usize) -> u8 {
+            self.0 << 1
+        }
+    }
+
+    #[test]
+    fn test_undriven_reads() {
+        let mut test_cpu = Bus::default();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        test_cpu.cartridge.prg_ram = Vec::new();
+        test_cpu.data_write(&mut ppu, 0x0000, 0x5a);
+        test_cpu.data_read(&mut ppu, 0x0000);
+        // Nothing is mapped to the expansion area, and the cartridge has no PRG RAM
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4020), 0x5a);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x5fff), 0x5a);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x6000), 0x5a);
+
+        test_cpu.cartridge.prg_ram = vec![0x12; 0x2000];
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x6000), 0x12);
+    }
+
+    #[test]
+    fn test_io_registers() {
+        let mut test_cpu = Bus::default();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+
+        // Write-only and test registers return the last value on the bus
+        test_cpu.data_write(&mut ppu, 0x0000, 0xff);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x0000), 0xff);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4000), 0xff);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x401f), 0xff);
+        // Only bit 5 of $4015 is open bus, and reading it does not drive the bus
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4015), 0b0010_0000);
+        assert_eq!(test_cpu.open_bus, 0xff);
+
+        test_cpu.data_write(&mut ppu, 0x4003, 0x12);
+        test_cpu.data_write(&mut ppu, 0x4018, 0x34);
+        assert_eq!(test_cpu.apu.pulse1.timer_period, 0x200);
+
+        // The second controller shifts out its own buttons
+        test_cpu.controller2_buttons = Buttons {
+            b: true,
+            ..Default::default()
+        };
+        test_cpu.data_write(&mut ppu, 0x4016, 1);
+        test_cpu.data_write(&mut ppu, 0x4016, 0);
+        test_cpu.data_write(&mut ppu, 0x0000, 0x40);
+        test_cpu.data_read(&mut ppu, 0x0000);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017), 0x40); // A
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017), 0x41); // B
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4016), 0x40); // First controller, A
+
+        test_cpu.expansion = Some(Box::new(EchoDevice(0)));
+        test_cpu.data_write(&mut ppu, 0x4016, 0b110);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4016), 0b1100);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017), 0b1100);
+    }
+
+    #[test]
+    fn test_oam_dma() {
+        let mut test_cpu = Bus::get_cpu(&uxrom_program()).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        for i in 0..256 {
+            test_cpu.cpu.mem[0x0300 + i] = i as u8;
+        }
+        test_cpu.apu.odd_cycle = false;
+        test_cpu.data_write(&mut ppu, 0x4014, 0x03);
+        assert_eq!(test_cpu.stall_cycles, 513);
+
+        // The halt cycle reads nothing, then every other cycle reads the next byte
+        test_cpu.tick(&mut ppu).unwrap();
+        assert_eq!(test_cpu.open_bus, 0x03);
+        test_cpu.tick(&mut ppu).unwrap();
+        assert_eq!(test_cpu.open_bus, 0x00);
+        test_cpu.tick(&mut ppu).unwrap();
+        test_cpu.tick(&mut ppu).unwrap();
+        assert_eq!(test_cpu.open_bus, 0x01);
+        while test_cpu.stall_cycles != 0 {
+            test_cpu.tick(&mut ppu).unwrap();
+        }
+        assert_eq!(test_cpu.open_bus, 0xff);
+        assert_eq!(test_cpu.oam_dma_page, None);
+        assert_eq!(test_cpu.oam_dma_data, (0..=255).collect::<Vec<u8>>());
+
+        // Starting on an odd cycle takes an alignment cycle, and pages outside the RAM are read through the mapper
+        test_cpu.apu.odd_cycle = true;
+        test_cpu.data_write(&mut ppu, 0x4014, 0xc0);
+        assert_eq!(test_cpu.stall_cycles, 514);
+        while test_cpu.stall_cycles != 0 {
+            test_cpu.tick(&mut ppu).unwrap();
+        }
+        assert_eq!(test_cpu.open_bus, test_cpu.memory_read(0xc0ff));
+    }
+
+    #[test]
+    fn test_dmc_dma() {
+        let mut test_cpu = Bus::get_cpu(&uxrom_program()).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        test_cpu.data_write(&mut ppu, 0x4012, 0x00); // $C000
+        test_cpu.data_write(&mut ppu, 0x4013, 0x00); // 1 byte
+        test_cpu.controller2_buttons = Buttons {
+            a: true,
+            select: true,
+            ..Default::default()
+        };
+        test_cpu.data_write(&mut ppu, 0x4016, 1);
+        test_cpu.data_write(&mut ppu, 0x4016, 0);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017) & 1, 1); // A
+
+        // The DMA halts the CPU on its next read, which shifts out B and is repeated after the DMA
+        test_cpu.data_write(&mut ppu, 0x4015, 0b1_0000);
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017) & 1, 0); // B
+        assert!((2..=3).contains(&test_cpu.dmc_dma_cycles));
+        while test_cpu.dmc_dma_cycles != 0 {
+            assert_eq!(test_cpu.apu.dmc.sample_buffer, None);
+            test_cpu.dmc_dma_cycle();
+        }
+        assert_eq!(test_cpu.apu.dmc.sample_buffer, Some(0xbb));
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017) & 1, 1); // Select
+        assert_eq!(test_cpu.data_read(&mut ppu, 0x4017) & 1, 0); // Start
+        assert_eq!(test_cpu.dmc_dma_cycles, 0); // The sample has ended
+
+        // During OAM DMA the sample is fetched right away
+        test_cpu.apu.dmc.sample_buffer = None;
+        test_cpu.data_write(&mut ppu, 0x4015, 0b1_0000);
+        test_cpu.data_write(&mut ppu, 0x4014, 0x02);
+        let stall_cycles = test_cpu.stall_cycles;
+        test_cpu.tick(&mut ppu).unwrap();
+        assert_eq!(test_cpu.apu.dmc.sample_buffer, Some(0xbb));
+        assert_eq!(test_cpu.stall_cycles, stall_cycles + 2 - 1);
+    }
+
+    #[test]
+    fn test_dmc_dma_on_pull() {
+        let mut test_cpu = Bus::get_cpu(&uxrom_program()).unwrap();
+        let mut ppu = Ppu::new(Mirroring::Vertical);
+        test_cpu.data_write(&mut ppu, 0x4012, 0x00); // $C000
+        test_cpu.data_write(&mut ppu, 0x4013, 0x00); // 1 byte
+        test_cpu.cpu.pc = 0x0300;
+        test_cpu.cpu.mem[0x0300] = 0x68; // PLA
+        test_cpu.cpu.mem[0x01fe] = 0x42;
+        for _ in 0..3 {
+            test_cpu.tick(&mut ppu).unwrap(); // Opcode and dummy reads
+        }
+
+        // The DMA halts the CPU on the pull, which is repeated after the sample byte is read
+        test_cpu.data_write(&mut ppu, 0x4015, 0b1_0000);
+        test_cpu.tick(&mut ppu).unwrap();
+        assert_eq!(test_cpu.cpu.a, 0);
+        assert_eq!(test_cpu.cpu.sp, 0x01fd);
+        assert!((2..=3).contains(&test_cpu.dmc_dma_cycles));
+        while test_cpu.dmc_dma_cycles != 0 {
+            test_cpu.tick(&mut ppu).unwrap();
+        }
+        assert_eq!(test_cpu.apu.dmc.sample_buffer, Some(0xbb));
+        test_cpu.tick(&mut ppu).unwrap();
+        assert_eq!(test_cpu.cpu.a, 0x42);
+        assert_eq!(test_cpu.cpu.sp, 0x01fe);
+        assert_eq!(test_cpu.open_bus, 0x42); // The pull went over the bus
+        assert_eq!(test_cpu.cpu.pc, 0x0301);
+    }
 }
@@ -1,54 +1,241 @@
 //! Module that emulates a physical NES cartridge.
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use tudelft_nes_ppu::Mirroring;
+
+/// Size of the iNES header in bytes.
+pub const HEADER_SIZE: usize = 16;
+/// Size of the optional trainer in bytes.
+pub const TRAINER_SIZE: usize = 512;
+
+/// Reasons why an NES file cannot be turned into a running cartridge.
+#[derive(PartialEq, Eq, Debug)]
+pub enum RomError {
+    /// The file is shorter than the 16 byte header.
+    TruncatedHeader { size: usize },
+    /// The file does not start with the bytes `NES<EOF>`.
+    BadMagic,
+    /// The header announces a trainer, but the file ends before the trainer does.
+    TruncatedTrainer,
+    /// The header announces no PRG ROM at all, so there is no code to run.
+    MissingPrgRom,
+    /// The file ends before all PRG ROM announced by the header is read.
+    TruncatedPrgRom { expected: usize, found: usize },
+    /// The file ends before all CHR ROM announced by the header is read.
+    TruncatedChrRom { expected: usize, found: usize },
+    /// A ROM size given in NES 2.0 exponent-multiplier notation does not fit in memory.
+    RomSizeOverflow,
+    /// The mapper with this number is not implemented.
+    UnsupportedMapper(u16),
+    /// An NROM board can hold at most 32 kB of PRG ROM.
+    OversizedNrom { prg_rom_size_in_16kb: u16 },
+}
+
+impl Display for RomError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RomError::TruncatedHeader { size } => {
+                write!(f, "file is {size} bytes, too short for an iNES header")
+            }
+            RomError::BadMagic => write!(f, "not an iNES file (bad magic bytes)"),
+            RomError::TruncatedTrainer => write!(f, "file ends inside the trainer"),
+            RomError::MissingPrgRom => write!(f, "header specifies no PRG ROM"),
+            RomError::RomSizeOverflow => write!(f, "header specifies an impossibly large ROM"),
+            RomError::TruncatedPrgRom { expected, found } => write!(
+                f,
+                "PRG ROM is truncated: expected {expected} bytes, found {found}"
+            ),
+            RomError::TruncatedChrRom { expected, found } => write!(
+                f,
+                "CHR ROM is truncated: expected {expected} bytes, found {found}"
+            ),
+            RomError::UnsupportedMapper(number) => write!(f, "mapper {number} is not supported"),
+            RomError::OversizedNrom {
+                prg_rom_size_in_16kb,
+            } => write!(
+                f,
+                "NROM supports at most 32 kB of PRG ROM, header specifies {} kB",
+                *prg_rom_size_in_16kb as usize * 16
+            ),
+        }
+    }
+}
+
+impl Error for RomError {}
+
+/// Returns `len` bytes of `rom` starting at `offset`, or how many bytes are actually there.
+///
+/// # Arguments
+///
+/// * `rom` - A byte slice that contains the input .nes file.
+/// * `offset` - Start of the section.
+/// * `len` - Length of the section.
+///
+/// # Return
+/// * `Result<&[u8], usize>` - The section, or the number of bytes available if the file is too short.
+fn section(rom: &[u8], offset: usize, len: usize) -> Result<&[u8], usize> {
+    rom.get(offset..offset + len)
+        .ok_or_else(|| rom.len().saturating_sub(offset))
+}
+
+/// Checks the magic bytes and size of an iNES header.
+///
+/// # Arguments
+///
+/// * `rom` - A byte slice that contains the input .nes file.
+///
+/// # Return
+/// * `Result<(), RomError>` - Error if `rom` does not start with a valid header.
+pub fn check_header(rom: &[u8]) -> Result<(), RomError> {
+    if rom.len() < HEADER_SIZE {
+        return Err(RomError::TruncatedHeader { size: rom.len() });
+    }
+    if rom[0..4] != [b'N', b'E', b'S', 0x1a] {
+        return Err(RomError::BadMagic);
+    }
+    let nes_2_0 = (rom[7] & 0b1100) == 0b1000;
+    if rom[4] == 0 && (!nes_2_0 || rom[9] & 0x0f == 0) {
+        return Err(RomError::MissingPrgRom);
+    }
+    Ok(())
+}
+
+/// Computes the size of a PRG or CHR ROM from its header fields.
+///
+/// # Arguments
+///
+/// * `lsb` - Byte 4 (PRG) or byte 5 (CHR) of the header.
+/// * `msb` - The matching nibble of byte 9, always 0 for iNES 1.0 headers.
+/// * `unit` - Size of one bank in bytes (16 kB for PRG, 8 kB for CHR).
+///
+/// # Return
+/// * `Result<usize, RomError>` - Size in bytes. NES 2.0 headers with an MSB nibble of 0xF use the exponent-multiplier notation `2^E * (MM * 2 + 1)`.
+fn rom_size(lsb: u8, msb: u8, unit: usize) -> Result<usize, RomError> {
+    if msb == 0x0f {
+        let exponent = (lsb >> 2) as u32;
+        let multiplier = (lsb & 0b11) as usize * 2 + 1;
+        1usize
+            .checked_shl(exponent)
+            .filter(|size| size.leading_zeros() > 2)
+            .map(|size| size * multiplier)
+            .ok_or(RomError::RomSizeOverflow)
+    } else {
+        Ok((((msb as usize) << 8) | lsb as usize) * unit)
+    }
+}
+
+/// Converts an NES 2.0 RAM shift count into a size in bytes.
+///
+/// # Arguments
+///
+/// * `shift` - Shift count from byte 10 or 11 of the header.
+///
+/// # Return
+/// * `usize` - `64 << shift` bytes, or 0 if the shift count is 0.
+fn ram_size(shift: u8) -> usize {
+    if shift == 0 {
+        0
+    } else {
+        64 << shift
+    }
+}
+
+/// CPU/PPU timing the game is made for.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Timing {
+    /// RP2C02 ("NTSC NES").
+    Ntsc,
+    /// RP2C07 ("Licensed PAL NES").
+    Pal,
+    /// Works on both NTSC and PAL consoles.
+    MultiRegion,
+    /// UA6538 ("Dendy").
+    Dendy,
+}
+
+/// Kind of console the game is made for.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum ConsoleType {
+    /// Nintendo Entertainment System or Famicom.
+    Nes,
+    /// Nintendo Vs. System with its PPU and hardware type (NES 2.0 byte 13).
+    VsSystem { ppu_type: u8, hardware_type: u8 },
+    /// Nintendo Playchoice 10.
+    Playchoice10,
+    /// Extended console type (NES 2.0 byte 13), e.g. Famiclone with decimal mode.
+    Extended(u8),
+}
+
 #[derive(PartialEq, Eq, Debug)]
 
 /// A struct representing a physical NES cartridge which holds the contents contained in an NES file.
 pub struct Cartridge {
     // TODO make these private
     // HEADER
-    /// Size of program-ROM specified in multiples of 16 kB.
-    pub prg_rom_size_in_16kb: u8,
-    /// Size of character-ROM specified in multiples of 8 kB.
-    pub chr_rom_size_in_8kb: u8,
+    /// Size of program-ROM specified in multiples of 16 kB (rounded up for NES 2.0 exponent-multiplier sizes).
+    pub prg_rom_size_in_16kb: u16,
+    /// Size of character-ROM specified in multiples of 8 kB (rounded up for NES 2.0 exponent-multiplier sizes).
+    pub chr_rom_size_in_8kb: u16,
 
     // Flags 6
-    /// ID of used mapper (Currently supported: 0 -> NROM, 1 -> MMC1).
-    pub mapper_number: u8,
-    /// Indicates whether ROM from 0x8000 to 0xbfff is mirrored to 0xc0000 to 0xffff.
+    /// ID of used mapper, 12 bits for NES 2.0 (see `MAPPERS` in the mapper module for the supported boards).
+    pub mapper_number: u16,
+    /// Indicates whether the nametables are mirrored vertically (horizontally if false).
     pub mirroring_control: bool,
     /// Indicates whether trainer data is available.
     pub trainer_available: bool,
     /// Indicates whether cartridge contains battery-backed PRG RAM ($6000-7FFF) or other persistent memory.
     pub battery_backed_prg_ram_available: bool,
-    /// Indicates whether mirroring control is used.
+    /// Indicates whether the cartridge provides its own VRAM for four-screen mirroring (overrides `mirroring_control`).
     pub mirroring_mode: bool,
 
     // Flags 7
     // Upper nibble included in field of Flag 6
     /// Indicates whether flags 8-15 are in NES 2.0 format.
     pub nes_2_0_format: bool,
-    /// Playchoice 10 supported.
-    pub playchoice_10: bool,
-    /// VS Unisystem supported.
-    pub vs_unisystem: bool,
+    /// Console the game is made for (Vs. System and Playchoice 10 bits for iNES 1.0).
+    pub console_type: ConsoleType,
 
     // Flags 8
-    /// Size of PGRM RAM specified in multiples of 8 kB.
-    pub prg_ram_size_in_8kb: u8,
-
-    // Flags 9
-    /// Specifies which TV system is used (0 -> NTSC, 1 -> PAL).
-    pub tv_system_mode: bool,
-
-    // Flag 10
+    /// Variant of the mapper board (NES 2.0 only, 0 otherwise).
+    pub submapper_number: u8,
+
+    // Flags 8 (iNES 1.0) or 10 and 11 (NES 2.0)
+    /// Size of volatile PRG RAM in bytes.
+    pub prg_ram_size: usize,
+    /// Size of battery-backed PRG RAM (PRG NVRAM) in bytes.
+    pub prg_nvram_size: usize,
+    /// Size of volatile CHR RAM in bytes.
+    pub chr_ram_size: usize,
+    /// Size of battery-backed CHR RAM (CHR NVRAM) in bytes.
+    pub chr_nvram_size: usize,
+
+    // Flags 9 (iNES 1.0) or 12 (NES 2.0)
+    /// Specifies which CPU/PPU timing is used.
+    pub timing: Timing,
+
+    // Flag 10 (iNES 1.0), derived from the submapper for NES 2.0
     /// Indicates whether PRG RAM is available.
     pub prg_ram_present: bool,
     /// Indicates whether bus conflicts are present on board.
     pub bus_conflicts: bool,
 
+    // Flags 14 and 15 (NES 2.0)
+    /// Number of miscellaneous ROMs stored after the CHR ROM.
+    pub misc_rom_count: u8,
+    /// Default expansion device (e.g. 1 -> standard controllers, 8 -> Zapper).
+    pub default_expansion_device: u8,
+
+    /// Contents of the PRG RAM at $6000-$7FFF, volatile and battery-backed RAM together.
+    pub prg_ram: Vec<u8>,
+    /// Trainer that is loaded to $7000-$71FF, empty if `trainer_available` is false.
+    pub trainer_data: Vec<u8>,
     /// Actual PRG ROM data stored in a Vec<u8>.
     pub prg_rom_data: Vec<u8>,
     /// Actual CHR ROM data stored in a Vec<u8>.
     pub chr_rom_data: Vec<u8>,
+    /// Contents of the CHR RAM, volatile and battery-backed RAM together.
+    pub chr_ram: Vec<u8>,
 }
 
 impl Cartridge {
@@ -59,59 +246,168 @@ impl Cartridge {
     /// * `rom` - A byte slice that contains the input .nes file.
     ///
     /// # Return
-    /// * `Cartridge` - a Cartridge instance containing the data of `rom`.
-    pub fn generate_from_rom(rom: &[u8]) -> Cartridge {
-        if rom[0] != b'N' || rom[1] != b'E' || rom[2] != b'S' || rom[3] != 0x1a {
-            panic!("Not iNES format")
-        }
-        let prg_rom_size_in_16kb = rom[4] as usize;
-        let chr_rom_size_in_8kb = rom[5] as usize;
+    /// * `Result<Cartridge, RomError>` - a Cartridge instance containing the data of `rom`, or the reason why `rom` is not a valid NES file.
+    pub fn generate_from_rom(rom: &[u8]) -> Result<Cartridge, RomError> {
+        check_header(rom)?;
+        let nes_2_0_format = (rom[7] & 0b1100) == 0b1000;
+        // Old rippers wrote text such as "DiskDude!" into bytes 7-15 of iNES 1.0 headers, so those bytes are ignored if the padding is not empty
+        let dirty = !nes_2_0_format && rom[12..16].iter().any(|byte| *byte != 0);
+        let [flags7, flags8, flags9, flags10] = if dirty {
+            [0; 4]
+        } else {
+            [rom[7], rom[8], rom[9], rom[10]]
+        };
+        let (prg_msb, chr_msb) = if nes_2_0_format {
+            (rom[9] & 0x0f, rom[9] >> 4)
+        } else {
+            (0, 0)
+        };
+        let prg_rom_size = rom_size(rom[4], prg_msb, 16384)?;
+        let chr_rom_size = rom_size(rom[5], chr_msb, 8192)?;
         let trainer_avail = (rom[6] & 0b100) == 0b100;
-
-        let mut prg_data: Vec<u8> = Vec::new();
-        let mut chr_data: Vec<u8> = Vec::new();
-
-        let mut data_offset: usize = if trainer_avail { 16 + 512 } else { 16 };
-
-        for i in rom
-            .iter()
-            .skip(data_offset)
-            .take((prg_rom_size_in_16kb * 16384) as usize)
-        {
-            prg_data.push(*i);
+        let battery = (rom[6] & 0b10) == 0b10;
+
+        let mut data_offset = HEADER_SIZE;
+        let mut trainer_data = Vec::new();
+        if trainer_avail {
+            trainer_data = section(rom, data_offset, TRAINER_SIZE)
+                .map_err(|_| RomError::TruncatedTrainer)?
+                .to_vec();
+            data_offset += TRAINER_SIZE;
         }
-        data_offset += prg_rom_size_in_16kb * 16384;
-
-        for i in rom
-            .iter()
-            .skip(data_offset)
-            .take((chr_rom_size_in_8kb * 8192) as usize)
-        {
-            chr_data.push(*i);
-        }
-        Cartridge {
-            prg_rom_size_in_16kb: rom[4],
-            chr_rom_size_in_8kb: rom[5],
 
-            mapper_number: (rom[6] >> 4) | (rom[7] & 0b11110000),
+        let prg_data = section(rom, data_offset, prg_rom_size)
+            .map_err(|found| RomError::TruncatedPrgRom {
+                expected: prg_rom_size,
+                found,
+            })?
+            .to_vec();
+        data_offset += prg_rom_size;
+
+        let chr_data = section(rom, data_offset, chr_rom_size)
+            .map_err(|found| RomError::TruncatedChrRom {
+                expected: chr_rom_size,
+                found,
+            })?
+            .to_vec();
+
+        let mut cartridge = Cartridge {
+            prg_rom_size_in_16kb: ((prg_rom_size + 16383) / 16384) as u16,
+            chr_rom_size_in_8kb: ((chr_rom_size + 8191) / 8192) as u16,
+
+            mapper_number: ((rom[6] >> 4) | (flags7 & 0b11110000)) as u16,
             mirroring_control: (rom[6] & 0x1) == 1,
-            battery_backed_prg_ram_available: (rom[6] & 0b10) == 0b10,
+            battery_backed_prg_ram_available: battery,
             trainer_available: trainer_avail,
             mirroring_mode: (rom[6] & 0b1000) == 0b1000,
 
-            vs_unisystem: (rom[7] & 0b1) == 0b1,
-            playchoice_10: (rom[7] & 0b10) == 0b10,
-            nes_2_0_format: (rom[7] & 0b1100) == 0b1000,
-
-            prg_ram_size_in_8kb: rom[8],
+            nes_2_0_format,
+            console_type: if (flags7 & 0b1) == 0b1 {
+                ConsoleType::VsSystem {
+                    ppu_type: 0,
+                    hardware_type: 0,
+                }
+            } else if (flags7 & 0b10) == 0b10 {
+                ConsoleType::Playchoice10
+            } else {
+                ConsoleType::Nes
+            },
+
+            submapper_number: 0,
+
+            // iNES 1.0: a size of 0 means 8 kB for compatibility, the battery bit makes it non-volatile
+            prg_ram_size: if battery {
+                0
+            } else {
+                flags8.max(1) as usize * 8192
+            },
+            prg_nvram_size: if battery {
+                flags8.max(1) as usize * 8192
+            } else {
+                0
+            },
+            chr_ram_size: if chr_rom_size == 0 { 8192 } else { 0 },
+            chr_nvram_size: 0,
+
+            timing: if flags9 & 0b1 == 0b1 {
+                Timing::Pal
+            } else {
+                Timing::Ntsc
+            },
+
+            prg_ram_present: (flags10 & 0b10000) == 0b10000,
+            bus_conflicts: (flags10 & 0b100000) == 0b100000,
+
+            misc_rom_count: 0,
+            default_expansion_device: 0,
+
+            prg_ram: Vec::new(),
+            trainer_data,
+            prg_rom_data: prg_data,
+            chr_rom_data: chr_data,
+            chr_ram: Vec::new(),
+        };
 
-            tv_system_mode: false, // Not used for now
+        if nes_2_0_format {
+            cartridge.parse_nes_2_0(rom);
+        }
+        cartridge.prg_ram = vec![0; cartridge.prg_ram_size + cartridge.prg_nvram_size];
+        cartridge.chr_ram = vec![0; cartridge.chr_ram_size + cartridge.chr_nvram_size];
+        Ok(cartridge)
+    }
 
-            prg_ram_present: (rom[10] & 0b10000) == 0b10000,
-            bus_conflicts: (rom[10] & 0b100000) == 0b100000,
+    /// Overwrites the fields that are encoded differently in the NES 2.0 header (bytes 7-15).
+    ///
+    /// # Arguments
+    ///
+    /// * `rom` - A byte slice that contains the input .nes file, with a header that is already checked.
+    ///
+    /// Nothing is returned.
+    fn parse_nes_2_0(&mut self, rom: &[u8]) {
+        self.mapper_number |= ((rom[8] & 0x0f) as u16) << 8;
+        self.submapper_number = rom[8] >> 4;
+
+        self.console_type = match rom[7] & 0b11 {
+            0 => ConsoleType::Nes,
+            1 => ConsoleType::VsSystem {
+                ppu_type: rom[13] & 0x0f,
+                hardware_type: rom[13] >> 4,
+            },
+            2 => ConsoleType::Playchoice10,
+            _ => ConsoleType::Extended(rom[13] & 0x0f),
+        };
+
+        self.prg_ram_size = ram_size(rom[10] & 0x0f);
+        self.prg_nvram_size = ram_size(rom[10] >> 4);
+        self.chr_ram_size = ram_size(rom[11] & 0x0f);
+        self.chr_nvram_size = ram_size(rom[11] >> 4);
+
+        self.timing = match rom[12] & 0b11 {
+            0 => Timing::Ntsc,
+            1 => Timing::Pal,
+            2 => Timing::MultiRegion,
+            _ => Timing::Dendy,
+        };
+
+        self.prg_ram_present = self.prg_ram_size + self.prg_nvram_size > 0;
+        // UxROM, CNROM and AxROM: submapper 1 has no bus conflicts, submapper 2 has them
+        self.bus_conflicts = matches!(self.mapper_number, 2 | 3 | 7) && self.submapper_number == 2;
+
+        self.misc_rom_count = rom[14] & 0b11;
+        self.default_expansion_device = rom[15] & 0b11_1111;
+    }
 
-            prg_rom_data: prg_data,
-            chr_rom_data: chr_data,
+    /// Returns the nametable mirroring that is hard-wired on the cartridge board.
+    ///
+    /// # Return
+    /// * `Mirroring` - Four-screen if the board has its own VRAM, otherwise horizontal or vertical as set by the header.
+    pub fn mirroring(&self) -> Mirroring {
+        if self.mirroring_mode {
+            Mirroring::FourScreen
+        } else if self.mirroring_control {
+            Mirroring::Vertical
+        } else {
+            Mirroring::Horizontal
         }
     }
 }
@@ -127,10 +423,10 @@ impl Default for Cartridge {
         let mut prg_data: Vec<u8> = Vec::new();
         let mut chr_data: Vec<u8> = Vec::new();
         // Fill cartridge and rom with same data
-        for _i in 0..16384 * (prg_rom_size_in_16kb as u16) {
+        for _i in 0..16384 * prg_rom_size_in_16kb as u32 {
             prg_data.push(0);
         }
-        for _i in 0..8192 * (chr_rom_size_in_8kb as u16) {
+        for _i in 0..8192 * chr_rom_size_in_8kb as u32 {
             chr_data.push(0);
         }
 
@@ -144,32 +440,192 @@ impl Default for Cartridge {
             trainer_available: false,
             mirroring_mode: false,
 
-            vs_unisystem: false,
-            playchoice_10: false,
             nes_2_0_format: false,
+            console_type: ConsoleType::Nes,
 
-            prg_ram_size_in_8kb: 1,
+            submapper_number: 0,
 
-            tv_system_mode: false, // Not used for now
+            prg_ram_size: 8192,
+            prg_nvram_size: 0,
+            chr_ram_size: 0,
+            chr_nvram_size: 0,
+
+            timing: Timing::Ntsc,
 
             prg_ram_present: false,
             bus_conflicts: false,
 
+            misc_rom_count: 0,
+            default_expansion_device: 0,
+
+            prg_ram: vec![0; 8192],
+            trainer_data: Vec::new(),
             prg_rom_data: prg_data,
             chr_rom_data: chr_data,
+            chr_ram: Vec::new(),
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::manual_memcpy)]
 mod cartridge_tests {
-    use crate::cartridge::Cartridge;
+    use crate::cartridge::{Cartridge, ConsoleType, RomError, Timing};
+    use tudelft_nes_ppu::Mirroring;
+
+    /// Builds an NES file with the given header and zero-filled PRG and CHR ROM of the given sizes.
+    fn nes_file(header: [u8; 16], prg_size: usize, chr_size: usize) -> Vec<u8> {
+        let mut rom = header.to_vec();
+        rom.resize(16 + prg_size + chr_size, 0);
+        rom
+    }
 
     #[test]
-    fn test_panic() {
+    fn test_nes_2_0() {
+        let header = [
+            b'N', b'E', b'S', 0x1a, 0x02, 0x00, 0x42, 0x1b, 0x31, 0x01, 0x70, 0x07, 0x01, 0x00,
+            0x02, 0x08,
+        ];
+        let cart = Cartridge::generate_from_rom(&nes_file(header, 0x40_8000, 0)).unwrap();
+        assert!(cart.nes_2_0_format);
+        assert_eq!(cart.mapper_number, 0x114);
+        assert_eq!(cart.submapper_number, 3);
+        assert_eq!(cart.prg_rom_size_in_16kb, 0x102);
+        assert_eq!(cart.prg_rom_data.len(), 0x40_8000);
+        assert_eq!(cart.chr_rom_size_in_8kb, 0);
+        assert_eq!(cart.console_type, ConsoleType::Extended(0));
+        assert_eq!(cart.prg_ram_size, 0);
+        assert_eq!(cart.prg_nvram_size, 8192);
+        assert_eq!(cart.chr_ram_size, 8192);
+        assert_eq!(cart.chr_nvram_size, 0);
+        assert_eq!(cart.timing, Timing::Pal);
+        assert!(cart.prg_ram_present);
+        assert_eq!(cart.misc_rom_count, 2);
+        assert_eq!(cart.default_expansion_device, 8);
+    }
+
+    #[test]
+    fn test_nes_2_0_exponent_size() {
+        // PRG: 2^14 * 3 = 48 kB, CHR: 2^10 * 1 = 1 kB, Vs. System with UxROM submapper 2
+        let header = [
+            b'N', b'E', b'S', 0x1a, 0b111001, 0b101000, 0x20, 0x09, 0x20, 0xff, 0x00, 0x00, 0x00,
+            0x21, 0x00, 0x00,
+        ];
+        let cart = Cartridge::generate_from_rom(&nes_file(header, 49152, 1024)).unwrap();
+        assert_eq!(cart.mapper_number, 2);
+        assert_eq!(cart.prg_rom_data.len(), 49152);
+        assert_eq!(cart.prg_rom_size_in_16kb, 3);
+        assert_eq!(cart.chr_rom_data.len(), 1024);
+        assert_eq!(cart.chr_rom_size_in_8kb, 1);
+        assert!(cart.bus_conflicts);
+        assert_eq!(
+            cart.console_type,
+            ConsoleType::VsSystem {
+                ppu_type: 1,
+                hardware_type: 2
+            }
+        );
+
+        let mut header = header;
+        header[4] = 0b1111_1100;
+        assert_eq!(
+            Cartridge::generate_from_rom(&nes_file(header, 0, 0)),
+            Err(RomError::RomSizeOverflow)
+        );
+    }
+
+    #[test]
+    fn test_ines_1_0_flags() {
+        // PAL, battery-backed 16 kB RAM, CHR RAM and bus conflicts
+        let header = [
+            b'N', b'E', b'S', 0x1a, 0x01, 0x00, 0x12, 0x10, 0x02, 0x01, 0x20, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+        let cart = Cartridge::generate_from_rom(&nes_file(header, 16384, 0)).unwrap();
+        assert!(!cart.nes_2_0_format);
+        assert_eq!(cart.mapper_number, 0x11);
+        assert_eq!(cart.prg_ram_size, 0);
+        assert_eq!(cart.prg_nvram_size, 16384);
+        assert_eq!(cart.chr_ram_size, 8192);
+        assert_eq!(cart.chr_ram.len(), 8192);
+        assert_eq!(cart.timing, Timing::Pal);
+        assert!(cart.bus_conflicts);
+    }
+
+    #[test]
+    fn test_dirty_header() {
+        // MMC1 dumped with "DiskDude!" in bytes 7-15, which would read as mapper 0x41, PAL and bus conflicts
+        let mut header = [
+            b'N', b'E', b'S', 0x1a, 0x02, 0x01, 0x10, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        header[7..16].copy_from_slice(b"DiskDude!");
+        let cart = Cartridge::generate_from_rom(&nes_file(header, 32768, 8192)).unwrap();
+        assert!(!cart.nes_2_0_format);
+        assert_eq!(cart.mapper_number, 1);
+        assert_eq!(cart.console_type, ConsoleType::Nes);
+        assert_eq!(cart.prg_ram_size, 8192);
+        assert_eq!(cart.prg_nvram_size, 0);
+        assert_eq!(cart.timing, Timing::Ntsc);
+        assert!(!cart.prg_ram_present);
+        assert!(!cart.bus_conflicts);
+    }
+
+    #[test]
+    fn test_mirroring() {
+        let mut cart = Cartridge::default();
+        assert_eq!(cart.mirroring(), Mirroring::Horizontal);
+        cart.mirroring_control = true;
+        assert_eq!(cart.mirroring(), Mirroring::Vertical);
+        cart.mirroring_mode = true;
+        assert_eq!(cart.mirroring(), Mirroring::FourScreen);
+    }
+
+    #[test]
+    fn test_bad_magic() {
         let rom: [u8; 0xffff] = [0; 0xffff]; // NOTE this ROM is only 32kb
-        let panic_result = std::panic::catch_unwind(|| Cartridge::generate_from_rom(&rom));
-        assert!(panic_result.is_err());
+        assert_eq!(Cartridge::generate_from_rom(&rom), Err(RomError::BadMagic));
+    }
+
+    #[test]
+    fn test_truncated() {
+        let header = [
+            b'N', b'E', b'S', 0x1a, 2, 1, 0x04, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(
+            Cartridge::generate_from_rom(&header[..10]),
+            Err(RomError::TruncatedHeader { size: 10 })
+        );
+
+        let mut rom = header.to_vec();
+        rom.resize(16 + 100, 0);
+        assert_eq!(
+            Cartridge::generate_from_rom(&rom),
+            Err(RomError::TruncatedTrainer)
+        );
+
+        rom.resize(16 + 512 + 20000, 0);
+        assert_eq!(
+            Cartridge::generate_from_rom(&rom),
+            Err(RomError::TruncatedPrgRom {
+                expected: 32768,
+                found: 20000
+            })
+        );
+
+        rom.resize(16 + 512 + 32768 + 4096, 0);
+        assert_eq!(
+            Cartridge::generate_from_rom(&rom),
+            Err(RomError::TruncatedChrRom {
+                expected: 8192,
+                found: 4096
+            })
+        );
+
+        rom[4] = 0;
+        assert_eq!(
+            Cartridge::generate_from_rom(&rom),
+            Err(RomError::MissingPrgRom)
+        );
     }
 
     #[test]
@@ -219,9 +675,9 @@ mod cartridge_tests {
         }
         assert_eq!(
             Cartridge::generate_from_rom(&rom),
-            Cartridge {
-                prg_rom_size_in_16kb,
-                chr_rom_size_in_8kb,
+            Ok(Cartridge {
+                prg_rom_size_in_16kb: prg_rom_size_in_16kb as u16,
+                chr_rom_size_in_8kb: chr_rom_size_in_8kb as u16,
 
                 mapper_number: 1,
                 mirroring_control: false,
@@ -229,20 +685,30 @@ mod cartridge_tests {
                 trainer_available: false,
                 mirroring_mode: false,
 
-                vs_unisystem: false,
-                playchoice_10: false,
                 nes_2_0_format: false,
+                console_type: ConsoleType::Nes,
+
+                submapper_number: 0,
 
-                prg_ram_size_in_8kb: 1,
+                prg_ram_size: 8192,
+                prg_nvram_size: 0,
+                chr_ram_size: 0,
+                chr_nvram_size: 0,
 
-                tv_system_mode: false, // Not used for now
+                timing: Timing::Ntsc,
 
                 prg_ram_present: false,
                 bus_conflicts: false,
 
+                misc_rom_count: 0,
+                default_expansion_device: 0,
+
+                prg_ram: vec![0; 8192],
+                trainer_data: Vec::new(),
                 prg_rom_data: prg_data,
                 chr_rom_data: chr_data,
-            }
+                chr_ram: Vec::new(),
+            })
         );
     }
 
@@ -295,11 +761,12 @@ mod cartridge_tests {
         }
         // Try the same but with trainer available (and Mapper 0)
         rom[16] = 0x04;
+        let trainer_data = rom[16..16 + 512].to_vec();
         assert_eq!(
             Cartridge::generate_from_rom(&rom),
-            Cartridge {
-                prg_rom_size_in_16kb,
-                chr_rom_size_in_8kb,
+            Ok(Cartridge {
+                prg_rom_size_in_16kb: prg_rom_size_in_16kb as u16,
+                chr_rom_size_in_8kb: chr_rom_size_in_8kb as u16,
 
                 mapper_number: 0,
                 mirroring_control: false,
@@ -307,20 +774,30 @@ mod cartridge_tests {
                 trainer_available: true,
                 mirroring_mode: false,
 
-                vs_unisystem: false,
-                playchoice_10: false,
                 nes_2_0_format: false,
+                console_type: ConsoleType::Nes,
 
-                prg_ram_size_in_8kb: 1,
+                submapper_number: 0,
 
-                tv_system_mode: false, // Not used for now
+                prg_ram_size: 8192,
+                prg_nvram_size: 0,
+                chr_ram_size: 0,
+                chr_nvram_size: 0,
+
+                timing: Timing::Ntsc,
 
                 prg_ram_present: false,
                 bus_conflicts: false,
 
+                misc_rom_count: 0,
+                default_expansion_device: 0,
+
+                prg_ram: vec![0; 8192],
+                trainer_data,
                 prg_rom_data: prg_data,
                 chr_rom_data: chr_data,
-            }
+                chr_ram: Vec::new(),
+            })
         );
     }
 }
@@ -0,0 +1,302 @@
+//! This module parses the command-line arguments with which the emulator is started.
+use log::LevelFilter;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use tudelft_nes_ppu::Mirroring;
+
+/// Usage text printed for `--help` and after an invalid command line.
+pub const USAGE: &str = "\
+Usage: nes-emulator [OPTIONS] <ROM>
+
+Arguments:
+  <ROM>                      Path to the .nes file that shall be run
+
+Options:
+  -r, --region <REGION>      Console region: ntsc (default: taken from the ROM),
+                             pal is not supported yet
+  -l, --log-level <LEVEL>    Log level: off, error, warn, info, debug or trace (default: info)
+  -m, --mirroring <MODE>     Override the nametable mirroring: horizontal, vertical, four-screen,
+                             single-screen-lower or single-screen-upper
+      --random-ram           Fill the RAM with random values at power-on instead of zeros
+  -w, --wav <FILE>           Record the audio to a 16-bit PCM WAV file
+      --sample-rate <HZ>     Sample rate of the recording, 8000-192000 (default: 44100)
+  -f, --frames <N>           Run for N frames without opening a window, then exit. The frames
+                             are run at the speed of the console
+  -h, --help                 Print this help text
+
+Console buttons:
+  Up + Down + Select         Reset
+  Up + Down + Start          Power cycle";
+
+/// TV system the emulated console is built for.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Region {
+    /// North American and Japanese consoles (60 Hz).
+    Ntsc,
+    /// European and Australian consoles (50 Hz).
+    Pal,
+}
+
+/// The settings the user passed on the command line.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Options {
+    /// Path to the ROM file.
+    pub rom_path: PathBuf,
+    /// Region forced by the user, `None` if it should be derived from the ROM.
+    pub region: Option<Region>,
+    /// Maximum level of log messages that are printed.
+    pub log_level: LevelFilter,
+    /// Mirroring forced by the user, `None` if it should be derived from the ROM.
+    pub mirroring: Option<Mirroring>,
+    /// Whether the RAM is filled with random values at power-on.
+    pub random_ram: bool,
+    /// File the audio is recorded to, `None` if it is not recorded.
+    pub wav_path: Option<PathBuf>,
+    /// Sample rate of the recording in Hz.
+    pub sample_rate: u32,
+    /// Number of frames to run without a window, `None` to open a window.
+    pub frames: Option<u32>,
+}
+
+/// Reasons why the command line could not be turned into `Options`.
+#[derive(PartialEq, Eq, Debug)]
+pub enum CliError {
+    /// The user asked for the usage text.
+    Help,
+    /// No ROM path was given.
+    MissingRom,
+    /// More than one ROM path was given.
+    UnexpectedArgument(String),
+    /// An option that is not known was given.
+    UnknownOption(String),
+    /// An option that needs a value was the last argument.
+    MissingValue(String),
+    /// The value of an option could not be parsed.
+    InvalidValue { option: String, value: String },
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Help => write!(f, "help requested"),
+            CliError::MissingRom => write!(f, "no ROM file given"),
+            CliError::UnexpectedArgument(arg) => write!(f, "unexpected argument '{arg}'"),
+            CliError::UnknownOption(option) => write!(f, "unknown option '{option}'"),
+            CliError::MissingValue(option) => write!(f, "option '{option}' needs a value"),
+            CliError::InvalidValue { option, value } => {
+                write!(f, "invalid value '{value}' for option '{option}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl FromStr for Region {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ntsc" => Ok(Region::Ntsc),
+            "pal" => Ok(Region::Pal),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Parses the name of a mirroring mode as used on the command line.
+///
+/// # Arguments
+///
+/// * `s` - Name of the mirroring mode.
+///
+/// # Return
+/// * `Option<Mirroring>` - The mirroring mode, `None` if the name is not known.
+fn parse_mirroring(s: &str) -> Option<Mirroring> {
+    match s.to_ascii_lowercase().as_str() {
+        "horizontal" | "h" => Some(Mirroring::Horizontal),
+        "vertical" | "v" => Some(Mirroring::Vertical),
+        "four-screen" | "4" => Some(Mirroring::FourScreen),
+        "single-screen-lower" => Some(Mirroring::SingleScreenLower),
+        "single-screen-upper" => Some(Mirroring::SingleScreenUpper),
+        _ => None,
+    }
+}
+
+/// Parses the command-line arguments (without the program name).
+///
+/// # Arguments
+///
+/// * `args` - The arguments, usually `std::env::args().skip(1)`.
+///
+/// # Return
+/// * `Result<Options, CliError>` - The parsed options or the reason why they are invalid.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+    let mut args = args.into_iter();
+    let mut rom_path = None;
+    let mut region = None;
+    let mut log_level = LevelFilter::Info;
+    let mut mirroring = None;
+    let mut random_ram = false;
+    let mut wav_path = None;
+    let mut sample_rate = 44100;
+    let mut frames = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(CliError::Help),
+            "--random-ram" => random_ram = true,
+            "-r" | "--region" | "-l" | "--log-level" | "-m" | "--mirroring" | "-w" | "--wav"
+            | "--sample-rate" | "-f" | "--frames" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| CliError::MissingValue(arg.clone()))?;
+                let invalid = || CliError::InvalidValue {
+                    option: arg.clone(),
+                    value: value.clone(),
+                };
+                match arg.as_str() {
+                    "-r" | "--region" => {
+                        // The PPU only runs at NTSC speed, so PAL cannot be forced yet
+                        region = Some(
+                            value
+                                .parse()
+                                .ok()
+                                .filter(|&region| region == Region::Ntsc)
+                                .ok_or_else(invalid)?,
+                        );
+                    }
+                    "-l" | "--log-level" => log_level = value.parse().map_err(|_| invalid())?,
+                    "-w" | "--wav" => wav_path = Some(PathBuf::from(&value)),
+                    "--sample-rate" => {
+                        sample_rate = value
+                            .parse()
+                            .ok()
+                            .filter(|rate| (8000..=192000).contains(rate))
+                            .ok_or_else(invalid)?;
+                    }
+                    "-f" | "--frames" => frames = Some(value.parse().map_err(|_| invalid())?),
+                    _ => mirroring = Some(parse_mirroring(&value).ok_or_else(invalid)?),
+                }
+            }
+            option if option.starts_with('-') && option.len() > 1 => {
+                return Err(CliError::UnknownOption(arg));
+            }
+            _ => {
+                if rom_path.is_some() {
+                    return Err(CliError::UnexpectedArgument(arg));
+                }
+                rom_path = Some(PathBuf::from(arg));
+            }
+        }
+    }
+
+    Ok(Options {
+        rom_path: rom_path.ok_or(CliError::MissingRom)?,
+        region,
+        log_level,
+        mirroring,
+        random_ram,
+        wav_path,
+        sample_rate,
+        frames,
+    })
+}
+
+#[cfg(test)]
+mod cli_tests {
+    use crate::cli::{parse_args, CliError, Options, Region};
+    use log::LevelFilter;
+    use std::path::PathBuf;
+    use tudelft_nes_ppu::Mirroring;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rom_only() {
+        assert_eq!(
+            parse_args(args(&["game.nes"])),
+            Ok(Options {
+                rom_path: PathBuf::from("game.nes"),
+                region: None,
+                log_level: LevelFilter::Info,
+                mirroring: None,
+                random_ram: false,
+                wav_path: None,
+                sample_rate: 44100,
+                frames: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_all_options() {
+        let options = parse_args(args(&[
+            "--region",
+            "NTSC",
+            "game.nes",
+            "-l",
+            "debug",
+            "-m",
+            "vertical",
+            "--random-ram",
+            "--wav",
+            "out.wav",
+            "--sample-rate",
+            "48000",
+            "-f",
+            "600",
+        ]))
+        .unwrap();
+        assert_eq!(options.rom_path, PathBuf::from("game.nes"));
+        assert_eq!(options.region, Some(Region::Ntsc));
+        assert_eq!(options.log_level, LevelFilter::Debug);
+        assert_eq!(options.mirroring, Some(Mirroring::Vertical));
+        assert!(options.random_ram);
+        assert_eq!(options.wav_path, Some(PathBuf::from("out.wav")));
+        assert_eq!(options.sample_rate, 48000);
+        assert_eq!(options.frames, Some(600));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_args(args(&[])), Err(CliError::MissingRom));
+        assert_eq!(parse_args(args(&["-h", "game.nes"])), Err(CliError::Help));
+        assert_eq!(
+            parse_args(args(&["a.nes", "b.nes"])),
+            Err(CliError::UnexpectedArgument("b.nes".to_string()))
+        );
+        assert_eq!(
+            parse_args(args(&["--turbo", "a.nes"])),
+            Err(CliError::UnknownOption("--turbo".to_string()))
+        );
+        assert_eq!(
+            parse_args(args(&["a.nes", "--region"])),
+            Err(CliError::MissingValue("--region".to_string()))
+        );
+        assert_eq!(
+            parse_args(args(&["a.nes", "--region", "pal"])),
+            Err(CliError::InvalidValue {
+                option: "--region".to_string(),
+                value: "pal".to_string()
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["a.nes", "--mirroring", "diagonal"])),
+            Err(CliError::InvalidValue {
+                option: "--mirroring".to_string(),
+                value: "diagonal".to_string()
+            })
+        );
+        assert_eq!(
+            parse_args(args(&["a.nes", "--sample-rate", "100"])),
+            Err(CliError::InvalidValue {
+                option: "--sample-rate".to_string(),
+                value: "100".to_string()
+            })
+        );
+    }
+}
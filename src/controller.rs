@@ -1,5 +1,37 @@
-//! This module provides the standard controller.
-use tudelft_nes_ppu::{Buttons, Ppu};
+//! This module provides the standard controller and the devices on the expansion port.
+use tudelft_nes_ppu::Buttons;
+
+/// A device on the expansion port. It sees the outputs written to $4016 and drives data lines of both controller ports.
+pub trait ExpansionDevice: Send {
+    /// Receives the outputs OUT0-OUT2 written to bits 0-2 of $4016.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The outputs in bits 0-2.
+    ///
+    /// Nothing is returned.
+    fn write(&mut self, out: u8);
+
+    /// Reads the data lines the device drives on a controller port.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - Number of the port, 0 for $4016 and 1 for $4017.
+    ///
+    /// # Return
+    /// * `u8` - The data lines in bits 0-4, lines the device does not drive are 0.
+    fn read(&mut self, port: usize) -> u8;
+}
+
+/// Buttons on the console itself. The window only passes on the controller, so they are pressed with chords that a real controller cannot produce.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum ConsoleButton {
+    /// Reset button, pressed with up, down and select.
+    Reset,
+    /// Power switch, pressed with up, down and start.
+    Power,
+}
+
 #[derive(PartialEq, Default, Eq, Debug)]
 /// This struct handles the user input controller
 pub struct Controller {
@@ -7,6 +39,8 @@ pub struct Controller {
     shift_register_index: u8,
     /// Strobe bit to continuously reset the shift register (returning only button 'a').
     strobe: bool,
+    /// Console button whose chord is held, so that it only triggers once.
+    held_console_button: Option<ConsoleButton>,
 }
 
 impl Controller {
@@ -14,8 +48,27 @@ impl Controller {
         Controller {
             shift_register_index: 0,
             strobe: false,
+            held_console_button: None,
         }
     }
+
+    /// Checks whether the user pressed the chord of a console button.
+    /// # Arguments
+    ///
+    /// * `buttons` - The buttons that are currently pressed.
+    ///
+    /// # Return
+    /// * `Option<ConsoleButton>` - The console button whose chord was completed since the last call, `None` if there is none.
+    pub fn console_button(&mut self, buttons: Buttons) -> Option<ConsoleButton> {
+        let button = match (buttons.up && buttons.down, buttons.select, buttons.start) {
+            (true, true, false) => Some(ConsoleButton::Reset),
+            (true, false, true) => Some(ConsoleButton::Power),
+            _ => None,
+        };
+        let held = self.held_console_button;
+        self.held_console_button = button;
+        button.filter(|&button| held != Some(button))
+    }
     /// Set the strobe bit by inserting one byte.
     /// # Arguments
     ///
@@ -31,12 +84,11 @@ impl Controller {
     /// Retrieve the button state and shift to the next button if strobe is low
     /// # Arguments
     ///
-    /// * `ppu` - Borrowed instance of PPU.
+    /// * `button` - The buttons that are currently pressed on this controller.
     ///
     /// # Return
     /// * `u8` - Button state on LSB.
-    pub fn get_controller_byte(&mut self, ppu: &mut Ppu) -> u8 {
-        let button: Buttons = ppu.get_joypad_state();
+    pub fn get_controller_byte(&mut self, button: Buttons) -> u8 {
         let mut ret: bool = button.get_by_index(self.shift_register_index);
         self.shift_register_index += 1;
 
@@ -50,3 +102,47 @@ impl Controller {
         (ret as u8) & 0x1
     }
 }
+
+#[cfg(test)]
+mod controller_tests {
+    use crate::controller::{ConsoleButton, Controller};
+    use tudelft_nes_ppu::Buttons;
+
+    #[test]
+    fn test_console_buttons() {
+        let mut controller = Controller::new();
+        let mut buttons = Buttons {
+            up: true,
+            down: true,
+            ..Default::default()
+        };
+        assert_eq!(controller.console_button(buttons), None);
+        buttons.select = true;
+        assert_eq!(
+            controller.console_button(buttons),
+            Some(ConsoleButton::Reset)
+        );
+        assert_eq!(controller.console_button(buttons), None); // Held
+
+        buttons.select = false;
+        buttons.start = true;
+        assert_eq!(
+            controller.console_button(buttons),
+            Some(ConsoleButton::Power)
+        );
+        buttons.select = true;
+        assert_eq!(controller.console_button(buttons), None); // Both is neither
+        buttons.start = false;
+        assert_eq!(
+            controller.console_button(buttons),
+            Some(ConsoleButton::Reset)
+        );
+        assert_eq!(
+            controller.console_button(Buttons {
+                select: true,
+                ..Default::default()
+            }),
+            None
+        );
+    }
+}
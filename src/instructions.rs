@@ -104,11 +104,111 @@ pub enum AddressingMode {
     IndirectY,
 }
 
-/// A struct representing a single Instruction, which is identified by an instruction name, an addressing mode, and the needed CPU cycles to execute it.
+/// How an instruction uses the memory at its effective address, which decides the bus accesses of its cycles.
+enum Access {
+    Read,
+    Write,
+    ReadModifyWrite,
+}
+
+/// Interrupts, which run the cycles of `BRK` with their own vector.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Interrupt {
+    Nmi,
+    Irq,
+    /// The reset button, which turns the pushes into reads.
+    Reset,
+}
+
+/// Progress of the instruction the CPU is executing, the CPU does one bus access per cycle.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub struct InstructionState {
+    /// Opcode of the instruction, `BRK` for interrupts.
+    pub opcode: u8,
+    /// Cycle of the instruction that runs next, 0 means that the next opcode is fetched.
+    pub cycle: u8,
+    /// Interrupt that is being handled instead of an instruction.
+    pub interrupt: Option<Interrupt>,
+    /// Effective address, built up over the addressing cycles.
+    pub address: u16,
+    /// Zero page pointer of the indirect addressing modes.
+    pub pointer: u8,
+    /// Operand or address byte that is kept between cycles.
+    pub data: u8,
+    /// Indicates whether an index carried into the high byte of the address.
+    pub page_crossed: bool,
+}
+
+/// State the CPU had at the start of a cycle. A DMC DMA halts the CPU on a read, which is repeated after the DMA, so the halted cycle is undone with it.
+struct CycleStart {
+    /// Accumulator, index registers and status register.
+    registers: [u8; 4],
+    /// Program counter.
+    pc: u16,
+    /// Stack pointer.
+    sp: u16,
+    /// The b flag, which is not part of the status register.
+    b: bool,
+    /// Progress of the instruction.
+    instruction: InstructionState,
+    /// Interrupt seen at the end of the last cycle.
+    pending_interrupt: Option<Interrupt>,
+    /// Interrupt seen at the end of the cycle before.
+    polled_interrupt: Option<Interrupt>,
+    /// Indicates whether an NMI had not been handled yet.
+    nmi_pending: bool,
+}
+
+impl CycleStart {
+    /// Takes the state of the CPU before a cycle.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    ///
+    /// # Return
+    /// * `CycleStart` - The state.
+    fn save(bus: &Bus) -> CycleStart {
+        let cpu = &bus.cpu;
+        CycleStart {
+            registers: [cpu.a, cpu.x, cpu.y, cpu.status()],
+            pc: cpu.pc,
+            sp: cpu.sp,
+            b: cpu.b,
+            instruction: bus.instruction,
+            pending_interrupt: bus.pending_interrupt,
+            polled_interrupt: bus.polled_interrupt,
+            nmi_pending: bus.nmi_pending,
+        }
+    }
+
+    /// Puts the CPU back into the state before the cycle. The halted cycle does not write, so the memory is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    ///
+    /// Nothing is returned.
+    fn restore(&self, bus: &mut Bus) {
+        let [a, x, y, p] = self.registers;
+        bus.cpu.a = a;
+        bus.cpu.x = x;
+        bus.cpu.y = y;
+        bus.cpu.set_status(p);
+        bus.cpu.pc = self.pc;
+        bus.cpu.sp = self.sp;
+        bus.cpu.b = self.b;
+        bus.instruction = self.instruction;
+        bus.pending_interrupt = self.pending_interrupt;
+        bus.polled_interrupt = self.polled_interrupt;
+        bus.nmi_pending = self.nmi_pending;
+    }
+}
+
+/// A struct representing a single Instruction, which is identified by an instruction name and an addressing mode. Its cycles follow from both.
 pub struct Instruction {
     instruction_name: InstructionName,
     addressing_mode: AddressingMode,
-    cycle: u16,
 }
 
 impl Instruction {
@@ -120,2296 +220,2057 @@ impl Instruction {
     ///
     /// # Return
     ///
-    /// * `Instruction` - the instructions associated opcode, addressing mode, and cycles.
+    /// * `Instruction` - the instructions associated opcode and addressing mode.
     pub fn get_instruction(opcode: u8) -> Instruction {
         match opcode {
             // ADC
             0x69 => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x65 => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x75 => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x6d => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x7d => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0x79 => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             0x61 => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0x71 => Instruction {
                 instruction_name: InstructionName::ADC,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5,
             },
             // AND
             0x29 => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x25 => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x35 => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x2d => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x3d => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             0x39 => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             0x21 => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0x31 => Instruction {
                 instruction_name: InstructionName::AND,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 6,
             },
             // ASL
             0x0a => Instruction {
                 instruction_name: InstructionName::ASL,
                 addressing_mode: AddressingMode::Accumulator,
-                cycle: 2,
             },
             0x06 => Instruction {
                 instruction_name: InstructionName::ASL,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x16 => Instruction {
                 instruction_name: InstructionName::ASL,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x0e => Instruction {
                 instruction_name: InstructionName::ASL,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x1e => Instruction {
                 instruction_name: InstructionName::ASL,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             // BCC
             0x90 => Instruction {
                 instruction_name: InstructionName::BCC,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BCS
             0xb0 => Instruction {
                 instruction_name: InstructionName::BCS,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BEQ
             0xf0 => Instruction {
                 instruction_name: InstructionName::BEQ,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BIT
             0x24 => Instruction {
                 instruction_name: InstructionName::BIT,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x2c => Instruction {
                 instruction_name: InstructionName::BIT,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             // BMI
             0x30 => Instruction {
                 instruction_name: InstructionName::BMI,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BNE
             0xd0 => Instruction {
                 instruction_name: InstructionName::BNE,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BPL
             0x10 => Instruction {
                 instruction_name: InstructionName::BPL,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BRK
             0x00 => Instruction {
                 instruction_name: InstructionName::BRK,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 7,
             },
             // BVC
             0x50 => Instruction {
                 instruction_name: InstructionName::BVC,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // BVS
             0x70 => Instruction {
                 instruction_name: InstructionName::BVS,
                 addressing_mode: AddressingMode::Relative,
-                cycle: 2, // NOTE this is variable on branch success or new page
             },
             // CLC
             0x18 => Instruction {
                 instruction_name: InstructionName::CLC,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // CLD
             0xd8 => Instruction {
                 instruction_name: InstructionName::CLD,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // CLI
             0x58 => Instruction {
                 instruction_name: InstructionName::CLI,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // CLV
             0xb8 => Instruction {
                 instruction_name: InstructionName::CLV,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // CMP
             0xc9 => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xc5 => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xd5 => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0xcd => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0xdd => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0xd9 => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4,
             },
             0xc1 => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0xd1 => Instruction {
                 instruction_name: InstructionName::CMP,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5,
             },
             // CPX
             0xe0 => Instruction {
                 instruction_name: InstructionName::CPX,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xe4 => Instruction {
                 instruction_name: InstructionName::CPX,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xec => Instruction {
                 instruction_name: InstructionName::CPX,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             // CPY
             0xc0 => Instruction {
                 instruction_name: InstructionName::CPY,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xc4 => Instruction {
                 instruction_name: InstructionName::CPY,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xcc => Instruction {
                 instruction_name: InstructionName::CPY,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             // DEC
             0xc6 => Instruction {
                 instruction_name: InstructionName::DEC,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0xd6 => Instruction {
                 instruction_name: InstructionName::DEC,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0xce => Instruction {
                 instruction_name: InstructionName::DEC,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0xde => Instruction {
                 instruction_name: InstructionName::DEC,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             // DEX
             0xca => Instruction {
                 instruction_name: InstructionName::DEX,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // DEY
             0x88 => Instruction {
                 instruction_name: InstructionName::DEY,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // EOR
             0x49 => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x45 => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x55 => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x4D => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x5D => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0x59 => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4,
             },
             0x41 => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0x51 => Instruction {
                 instruction_name: InstructionName::EOR,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5,
             },
             // LDA
             0xa9 => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xa5 => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xb5 => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0xad => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0xbd => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             0xb9 => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             0xa1 => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0xb1 => Instruction {
                 instruction_name: InstructionName::LDA,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5, // NOTE this is variable on page crossed
             },
             // LDX
             0xa2 => Instruction {
                 instruction_name: InstructionName::LDX,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xa6 => Instruction {
                 instruction_name: InstructionName::LDX,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xb6 => Instruction {
                 instruction_name: InstructionName::LDX,
                 addressing_mode: AddressingMode::ZeroPageY,
-                cycle: 4,
             },
             0xae => Instruction {
                 instruction_name: InstructionName::LDX,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0xbe => Instruction {
                 instruction_name: InstructionName::LDX,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             // LDY
             0xa0 => Instruction {
                 instruction_name: InstructionName::LDY,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xa4 => Instruction {
                 instruction_name: InstructionName::LDY,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xb4 => Instruction {
                 instruction_name: InstructionName::LDY,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0xac => Instruction {
                 instruction_name: InstructionName::LDY,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0xbc => Instruction {
                 instruction_name: InstructionName::LDY,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4, // NOTE this is variable on page crossed
             },
             // LSR
             0x4a => Instruction {
                 instruction_name: InstructionName::LSR,
                 addressing_mode: AddressingMode::Accumulator,
-                cycle: 2,
             },
             0x46 => Instruction {
                 instruction_name: InstructionName::LSR,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x56 => Instruction {
                 instruction_name: InstructionName::LSR,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x4e => Instruction {
                 instruction_name: InstructionName::LSR,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x5e => Instruction {
                 instruction_name: InstructionName::LSR,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             // NOP
             0xea => Instruction {
                 instruction_name: InstructionName::NOP,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // ORA
             0x09 => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x05 => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x15 => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x0D => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x1d => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0x19 => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4,
             },
             0x01 => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0x11 => Instruction {
                 instruction_name: InstructionName::ORA,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5,
             },
             // PHA
             0x48 => Instruction {
                 instruction_name: InstructionName::PHA,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 3,
             },
             // PHP
             0x08 => Instruction {
                 instruction_name: InstructionName::PHP,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 3,
             },
             // PLA
             0x68 => Instruction {
                 instruction_name: InstructionName::PLA,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 4,
             },
             // PLP
             0x28 => Instruction {
                 instruction_name: InstructionName::PLP,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 4,
             },
             // RTI
             0x40 => Instruction {
                 instruction_name: InstructionName::RTI,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 6,
             },
             // RTS
             0x60 => Instruction {
                 instruction_name: InstructionName::RTS,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 6,
             },
 
             // STA
             0x85 => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x95 => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x8d => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x9d => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 5,
             },
             0x99 => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 5,
             },
             0x81 => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0x91 => Instruction {
                 instruction_name: InstructionName::STA,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 6,
             },
 
             // STX
             0x86 => Instruction {
                 instruction_name: InstructionName::STX,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x96 => Instruction {
                 instruction_name: InstructionName::STX,
                 addressing_mode: AddressingMode::ZeroPageY,
-                cycle: 4,
             },
             0x8e => Instruction {
                 instruction_name: InstructionName::STX,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             // STY
             0x84 => Instruction {
                 instruction_name: InstructionName::STY,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x94 => Instruction {
                 instruction_name: InstructionName::STY,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 3,
             },
             0x8c => Instruction {
                 instruction_name: InstructionName::STY,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 3,
             },
             // INC
             0xe6 => Instruction {
                 instruction_name: InstructionName::INC,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0xf6 => Instruction {
                 instruction_name: InstructionName::INC,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0xee => Instruction {
                 instruction_name: InstructionName::INC,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0xfe => Instruction {
                 instruction_name: InstructionName::INC,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             // INX
             0xe8 => Instruction {
                 instruction_name: InstructionName::INX,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // INY
             0xc8 => Instruction {
                 instruction_name: InstructionName::INY,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // JMP
             0x4c => Instruction {
                 instruction_name: InstructionName::JMP,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 3,
             },
             0x6c => Instruction {
                 instruction_name: InstructionName::JMP,
                 addressing_mode: AddressingMode::Indirect,
-                cycle: 5,
             },
             // JSR
             0x20 => Instruction {
                 instruction_name: InstructionName::JSR,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             // ROR
             0x6a => Instruction {
                 instruction_name: InstructionName::ROR,
                 addressing_mode: AddressingMode::Accumulator,
-                cycle: 2,
             },
             0x66 => Instruction {
                 instruction_name: InstructionName::ROR,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x76 => Instruction {
                 instruction_name: InstructionName::ROR,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x6e => Instruction {
                 instruction_name: InstructionName::ROR,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x7e => Instruction {
                 instruction_name: InstructionName::ROR,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             // ROL
             0x2a => Instruction {
                 instruction_name: InstructionName::ROL,
                 addressing_mode: AddressingMode::Accumulator,
-                cycle: 2,
             },
             0x26 => Instruction {
                 instruction_name: InstructionName::ROL,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x36 => Instruction {
                 instruction_name: InstructionName::ROL,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x2e => Instruction {
                 instruction_name: InstructionName::ROL,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x3e => Instruction {
                 instruction_name: InstructionName::ROL,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             // SEC
             0x38 => Instruction {
                 instruction_name: InstructionName::SEC,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // SED
             0xf8 => Instruction {
                 instruction_name: InstructionName::SED,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // SEI
             0x78 => Instruction {
                 instruction_name: InstructionName::SEI,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // SBC
             0xe9 => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xe5 => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xf5 => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0xed => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0xfd => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0xf9 => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4,
             },
             0xe1 => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0xf1 => Instruction {
                 instruction_name: InstructionName::SBC,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5,
             },
             // TAX
             0xaa => Instruction {
                 instruction_name: InstructionName::TAX,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // TAY
             0xa8 => Instruction {
                 instruction_name: InstructionName::TAY,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // TSX
             0xba => Instruction {
                 instruction_name: InstructionName::TSX,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // TXA
             0x8a => Instruction {
                 instruction_name: InstructionName::TXA,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // TXS
             0x9a => Instruction {
                 instruction_name: InstructionName::TXS,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // TYA
             0x98 => Instruction {
                 instruction_name: InstructionName::TYA,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // Unofficial instructions
             // ALR
             0x4b => Instruction {
                 instruction_name: InstructionName::ALR,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // ANC
             0x0b => Instruction {
                 instruction_name: InstructionName::ANC,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x2b => Instruction {
                 instruction_name: InstructionName::ANC,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // ANE
             0x8b => Instruction {
                 instruction_name: InstructionName::ANE,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // ARR
             0x6b => Instruction {
                 instruction_name: InstructionName::ARR,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // DCP
             0xc7 => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0xd7 => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0xcf => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0xdf => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             0xdb => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 7,
             },
             0xc3 => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 8,
             },
             0xd3 => Instruction {
                 instruction_name: InstructionName::DCP,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 8,
             },
             // ISC
             0xe7 => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0xf7 => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0xef => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0xff => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             0xfb => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 7,
             },
             0xe3 => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 8,
             },
             0xf3 => Instruction {
                 instruction_name: InstructionName::ISC,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 8,
             },
             // LAS
             0xbb => Instruction {
                 instruction_name: InstructionName::LAS,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4,
             },
             // LAX
             0xa7 => Instruction {
                 instruction_name: InstructionName::LAX,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0xb7 => Instruction {
                 instruction_name: InstructionName::LAX,
                 addressing_mode: AddressingMode::ZeroPageY,
-                cycle: 4,
             },
             0xaf => Instruction {
                 instruction_name: InstructionName::LAX,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0xbf => Instruction {
                 instruction_name: InstructionName::LAX,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 4,
             },
             0xa3 => Instruction {
                 instruction_name: InstructionName::LAX,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             0xb3 => Instruction {
                 instruction_name: InstructionName::LAX,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 5,
             },
             // LXA
             0xab => Instruction {
                 instruction_name: InstructionName::LXA,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // RLA
             0x27 => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x37 => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x2f => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x3f => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             0x3b => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 7,
             },
             0x23 => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 8,
             },
             0x33 => Instruction {
                 instruction_name: InstructionName::RLA,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 8,
             },
             // RRA
             0x67 => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x77 => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x6f => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x7f => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             0x7b => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 7,
             },
             0x63 => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 8,
             },
             0x73 => Instruction {
                 instruction_name: InstructionName::RRA,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 8,
             },
             // SAX
             0x87 => Instruction {
                 instruction_name: InstructionName::SAX,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x97 => Instruction {
                 instruction_name: InstructionName::SAX,
                 addressing_mode: AddressingMode::ZeroPageY,
-                cycle: 4,
             },
             0x8f => Instruction {
                 instruction_name: InstructionName::SAX,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x83 => Instruction {
                 instruction_name: InstructionName::SAX,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 6,
             },
             // SBX
             0xcb => Instruction {
                 instruction_name: InstructionName::SBX,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // SHA
             0x9f => Instruction {
                 instruction_name: InstructionName::SHA,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 5,
             },
             0x93 => Instruction {
                 instruction_name: InstructionName::SHA,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 6,
             },
             // SHX
             0x9e => Instruction {
                 instruction_name: InstructionName::SHX,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 5,
             },
             // SHY
             0x9c => Instruction {
                 instruction_name: InstructionName::SHY,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 5,
             },
             // SLO
             0x07 => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x17 => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x0f => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x1f => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             0x1b => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 7,
             },
             0x03 => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 8,
             },
             0x13 => Instruction {
                 instruction_name: InstructionName::SLO,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 8,
             },
             // SRE
             0x47 => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 5,
             },
             0x57 => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 6,
             },
             0x4f => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 6,
             },
             0x5f => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 7,
             },
             0x5b => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 7,
             },
             0x43 => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::IndirectX,
-                cycle: 8,
             },
             0x53 => Instruction {
                 instruction_name: InstructionName::SRE,
                 addressing_mode: AddressingMode::IndirectY,
-                cycle: 8,
             },
             // TAS
             0x9b => Instruction {
                 instruction_name: InstructionName::TAS,
                 addressing_mode: AddressingMode::AbsoluteY,
-                cycle: 5,
             },
             // USBC
             0xeb => Instruction {
                 instruction_name: InstructionName::USBC,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // NOPs
             // do nothing
             0x1a => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             0x3a => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             0x5a => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             0x7a => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             0xda => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             0xfa => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 2,
             },
             // NOPs: SKB
             0x80 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x82 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0x89 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xc2 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             0xe2 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Immediate,
-                cycle: 2,
             },
             // NOPs: IGN
             0x04 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x44 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x64 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPage,
-                cycle: 3,
             },
             0x14 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x34 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x54 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x74 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0xd4 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0xf4 => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::ZeroPageX,
-                cycle: 4,
             },
             0x0c => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::Absolute,
-                cycle: 4,
             },
             0x1c => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0x3c => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0x5c => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0x7c => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0xdc => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             0xfc => Instruction {
                 instruction_name: InstructionName::NOPs,
                 addressing_mode: AddressingMode::AbsoluteX,
-                cycle: 4,
             },
             // JAM
             0x02 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x12 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x22 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x32 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x42 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x52 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x62 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x72 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0x92 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0xb2 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0xd2 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
             0xf2 => Instruction {
                 instruction_name: InstructionName::JAM,
                 addressing_mode: AddressingMode::Implied,
-                cycle: 1,
             },
         }
     }
 
-    /// Gets the data address used by an `Instruction` depending on the addressing mode. The mapper module, managed by the bus, is used to translate the given address to the correct physical location.
+    /// Determines how the instruction uses the memory at its effective address.
+    ///
+    /// # Return
+    /// * `Access` - Whether the instruction reads, writes, or reads, modifies and writes back its operand.
+    fn access(&self) -> Access {
+        match self.instruction_name {
+            InstructionName::STA
+            | InstructionName::STX
+            | InstructionName::STY
+            | InstructionName::SAX
+            | InstructionName::SHA
+            | InstructionName::SHX
+            | InstructionName::SHY
+            | InstructionName::TAS => Access::Write,
+            InstructionName::ASL
+            | InstructionName::LSR
+            | InstructionName::ROL
+            | InstructionName::ROR
+            | InstructionName::INC
+            | InstructionName::DEC
+            | InstructionName::SLO
+            | InstructionName::RLA
+            | InstructionName::SRE
+            | InstructionName::RRA
+            | InstructionName::DCP
+            | InstructionName::ISC => Access::ReadModifyWrite,
+            _ => Access::Read,
+        }
+    }
+
+    /// Runs a single cycle of the CPU, which does exactly one access on the bus. Between two instructions the cycle fetches the next opcode, or starts an interrupt if one is pending. While a DMC DMA halts the CPU, the cycle belongs to the DMA instead and the instruction does not progress.
     ///
     /// # Arguments
     ///
-    /// * `bus` - borrowed instance of Bus, which holds the CPU.
-    /// * `address_mode` - The addressing mode used to determine the address.
-    /// * `ppu` - borrowed instance of PPU in case PPU memory needs to be accessed.
+    /// * `bus` - A borrowed instance of `Bus` which holds the CPU and the state of the current instruction.
+    /// * `ppu` - A borrowed instance of `Ppu` in case the cycle accesses PPU memory.
     ///
-    /// # Return
-    /// * `u16` - address of the opcode. If the addressing modes is `AddressingMode::Accumulator`, the function panics because the accumulator must be addressed directly through the CPU struct.
-    pub fn get_data_address(bus: &mut Bus, address_mode: AddressingMode, ppu: &mut Ppu) -> u16 {
-        match address_mode {
-            AddressingMode::Implied => bus.cpu.pc,
-            AddressingMode::Immediate => {
-                bus.cpu.pc += 1;
-                bus.cpu.pc
-            }
-            AddressingMode::Absolute => {
-                let ret_addr: u16 = ((bus.data_read(ppu, bus.cpu.pc + 2) as u16) << 8)
-                    | (bus.data_read(ppu, bus.cpu.pc + 1) as u16);
-                bus.cpu.pc += 2;
-                ret_addr
-            }
-            AddressingMode::AbsoluteX => {
-                let ret_addr: u16 = ((((bus.data_read(ppu, bus.cpu.pc + 2) as u16) << 8)
-                    | (bus.data_read(ppu, bus.cpu.pc + 1) as u16))
-                    .wrapping_add(bus.cpu.x as u16) as u16)
-                    as u16;
-                bus.cpu.pc += 2;
-                ret_addr
-            }
-            AddressingMode::AbsoluteY => {
-                let ret_addr: u16 = ((((bus.data_read(ppu, bus.cpu.pc + 2) as u16) << 8)
-                    | (bus.data_read(ppu, bus.cpu.pc + 1) as u16))
-                    .wrapping_add(bus.cpu.y as u16) as u16)
-                    as u16;
-                bus.cpu.pc += 2;
-                ret_addr
-            }
-            AddressingMode::ZeroPage => {
-                bus.cpu.pc += 1;
-                bus.data_read(ppu, bus.cpu.pc) as u16 & 0xFF
-            }
-            AddressingMode::ZeroPageX => {
-                bus.cpu.pc += 1;
-                (bus.data_read(ppu, bus.cpu.pc).wrapping_add(bus.cpu.x)) as u16 & 0xFF
-            }
-            AddressingMode::ZeroPageY => {
-                bus.cpu.pc += 1;
-                (bus.data_read(ppu, bus.cpu.pc).wrapping_add(bus.cpu.y)) as u16 & 0xFF
-            }
-            AddressingMode::Relative => {
-                let add = bus.data_read(ppu, bus.cpu.pc + 1);
-                if add & 0b1000_0000 != 0b1000_0000 {
-                    // Positive
-                    bus.cpu.pc += (add & 0b111_1111) as u16;
-                } else {
-                    // Negative
-                    bus.cpu.pc -= add.wrapping_neg() as u16;
-                }
-                bus.cpu.pc + 2
-            }
-            AddressingMode::Indirect => {
-                let pl_addr1: u16 = ((bus.data_read(ppu, bus.cpu.pc + 2) as u16) << 8)
-                    | (bus.data_read(ppu, bus.cpu.pc + 1) as u16);
-                let pl_addr2 = if bus.data_read(ppu, bus.cpu.pc + 1) == 0xff {
-                    (bus.data_read(ppu, bus.cpu.pc + 2) as u16) << 8
-                } else {
-                    pl_addr1.wrapping_add(1)
-                };
-                let ret_addr: u16 = (bus.data_read(ppu, pl_addr1) as u16)
-                    | ((bus.data_read(ppu, pl_addr2) as u16) << 8);
-                bus.cpu.pc += 1;
-                ret_addr
-            }
-            AddressingMode::IndirectX => {
-                let ret_addr_low_addr = ((bus.data_read(ppu, bus.cpu.pc + 1) as u16)
-                    .wrapping_add(bus.cpu.x as u16)) as u16;
-                let ret_addr_low: u16 = (bus.data_read(ppu, ret_addr_low_addr & 0xff)) as u16;
-                let ret_addr_high_addr = (bus
-                    .data_read(ppu, bus.cpu.pc + 1)
-                    .wrapping_add(bus.cpu.x + 1)) as u16;
-                let ret_addr_high: u16 = (bus.data_read(ppu, ret_addr_high_addr)) as u16;
-                let ret_addr = (ret_addr_high << 8) | ret_addr_low;
-                bus.cpu.pc += 1;
-                ret_addr
-            }
-            AddressingMode::IndirectY => {
-                let ret_addr_low_addr = bus.data_read(ppu, bus.cpu.pc + 1) as u16;
-                let ret_addr_low: u16 = (bus.data_read(ppu, ret_addr_low_addr)) as u16;
-                let ret_addr_high_addr =
-                    (bus.data_read(ppu, bus.cpu.pc + 1).wrapping_add(1)) as u16;
-                let ret_addr_high: u16 = (bus.data_read(ppu, ret_addr_high_addr)) as u16;
-                let ret_addr = ((ret_addr_high << 8) | ret_addr_low).wrapping_add(bus.cpu.y as u16);
-                bus.cpu.pc += 1;
-                ret_addr
-            }
-            AddressingMode::Accumulator => panic!("Accumulator does not have an address!"),
+    /// Nothing is returned.
+    pub fn execute_cycle(bus: &mut Bus, ppu: &mut Ppu) {
+        if bus.dmc_dma_cycles != 0 {
+            bus.dmc_dma_cycle();
+        } else if bus.apu.dmc.sample_request().is_some() {
+            // The DMA halts the CPU if this cycle reads, the read is then repeated after the DMA
+            let start = CycleStart::save(bus);
+            Self::run_cycle(bus, ppu);
+            if bus.dmc_dma_cycles != 0 {
+                start.restore(bus);
+            }
+        } else {
+            Self::run_cycle(bus, ppu);
         }
+        Self::poll_interrupts(bus);
     }
 
-    /// Finds and executes the next `Instruction` of the CPU on the bus.
+    /// Runs the bus access and the operation of the current cycle of the instruction.
     ///
     /// # Arguments
     ///
-    /// * `bus` - A borrowed instance of `Bus` on which holds the CPU on which the instruction shall be executed
-    /// * `ppu` - A borrowed instance of `Ppu` in case the instruction needs to access PPU memory
+    /// * `bus` - A borrowed instance of `Bus` which holds the CPU and the state of the current instruction.
+    /// * `ppu` - A borrowed instance of `Ppu` in case the cycle accesses PPU memory.
     ///
-    /// # Return
-    /// Returns nothing. If the opcode for the next instruction is invalid, either the function `get_mapper_address()` or `get_instruction()` will panic.
-    pub fn do_instruction(bus: &mut Bus, ppu: &mut Ppu) {
-        let opcode: u8 = bus.data_read(ppu, bus.cpu.pc);
-        let instr = Instruction::get_instruction(opcode);
-        bus.cycle = instr.cycle;
-
-        match instr.instruction_name {
-            InstructionName::ADC => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr) as u16;
+    /// Nothing is returned.
+    fn run_cycle(bus: &mut Bus, ppu: &mut Ppu) {
+        if bus.instruction.cycle == 0 {
+            Self::start_instruction(bus, ppu);
+            return;
+        }
 
-                let mut res: u16 = (bus.cpu.a as u16) + m;
-                if bus.cpu.carry {
-                    res += 1;
+        let instr = Instruction::get_instruction(bus.instruction.opcode);
+        let done = match instr.instruction_name {
+            InstructionName::BRK => Self::break_cycle(bus, ppu),
+            InstructionName::JSR => Self::jsr_cycle(bus, ppu),
+            InstructionName::RTI => Self::rti_cycle(bus, ppu),
+            InstructionName::RTS => Self::rts_cycle(bus, ppu),
+            InstructionName::PHA | InstructionName::PHP => Self::push_cycle(bus, ppu, &instr),
+            InstructionName::PLA | InstructionName::PLP => Self::pull_cycle(bus, ppu, &instr),
+            InstructionName::JMP => Self::jmp_cycle(bus, ppu, &instr),
+            InstructionName::JAM => {
+                // To kill the program
+                bus.jam = true;
+                true
+            }
+            _ => match instr.addressing_mode {
+                AddressingMode::Relative => Self::branch_cycle(bus, ppu, &instr),
+                AddressingMode::Implied | AddressingMode::Accumulator => {
+                    bus.data_read(ppu, bus.cpu.pc); // Dummy read of the next byte
+                    Self::implied_operation(bus, &instr);
+                    true
                 }
+                _ => Self::memory_cycle(bus, ppu, &instr),
+            },
+        };
+        bus.instruction.cycle = if done { 0 } else { bus.instruction.cycle + 1 };
+    }
 
-                bus.cpu.overflow =
-                    ((bus.cpu.a ^ (res as u8)) & ((m as u8) ^ (res as u8)) & 0x80) == 0x80;
-                bus.cpu.a = res as u8;
+    /// Samples the interrupt lines at the end of a cycle. Whether an interrupt runs after an instruction depends on the state at the end of its second-to-last cycle, so the flag changes of `CLI`, `SEI` and `PLP` in their last cycle only take effect after the next instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the interrupt lines.
+    ///
+    /// Nothing is returned.
+    pub fn poll_interrupts(bus: &mut Bus) {
+        bus.polled_interrupt = bus.pending_interrupt;
+        bus.pending_interrupt = if bus.nmi_pending {
+            Some(Interrupt::Nmi)
+        } else if bus.irq_line() && !bus.cpu.irq_dis {
+            Some(Interrupt::Irq)
+        } else {
+            None
+        };
+    }
 
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
+    /// Runs the CPU until the next instruction, or the interrupt sequence that replaces it, has finished.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - A borrowed instance of `Bus` on which holds the CPU on which the instruction shall be executed
+    /// * `ppu` - A borrowed instance of `Ppu` in case the instruction needs to access PPU memory
+    ///
+    /// # Return
+    /// * `u8` - The number of cycles the instruction took.
+    #[cfg(test)]
+    pub fn do_instruction(bus: &mut Bus, ppu: &mut Ppu) -> u8 {
+        let mut cycles = 0;
+        let mut started = false;
+        loop {
+            Self::execute_cycle(bus, ppu);
+            cycles += 1;
+            // A DMC DMA can halt the opcode fetch, the instruction only starts after it
+            if bus.instruction.cycle != 0 {
+                started = true;
+            } else if started {
+                return cycles;
+            }
+        }
+    }
 
-                bus.cpu.carry = res > 0xff;
-                bus.cpu.pc += 1; // Next instruction
+    /// Fetches the opcode of the next instruction. If an interrupt was polled during the previous instruction, the fetch is a dummy read and the `BRK` sequence runs instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// Nothing is returned.
+    fn start_instruction(bus: &mut Bus, ppu: &mut Ppu) {
+        let interrupt = bus.polled_interrupt;
+        let opcode = bus.data_read(ppu, bus.cpu.pc);
+        bus.instruction = match interrupt {
+            Some(_) => InstructionState {
+                opcode: 0x00, // BRK
+                cycle: 1,
+                interrupt,
+                ..Default::default()
+            },
+            None => {
+                bus.cpu.pc = bus.cpu.pc.wrapping_add(1);
+                InstructionState {
+                    opcode,
+                    cycle: 1,
+                    ..Default::default()
+                }
             }
-            InstructionName::AND => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
+        };
+    }
 
-                bus.cpu.a &= m;
+    /// Reads the byte at the program counter and moves the program counter past it.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// # Return
+    /// * `u8` - The operand byte.
+    fn fetch(bus: &mut Bus, ppu: &mut Ppu) -> u8 {
+        let data = bus.data_read(ppu, bus.cpu.pc);
+        bus.cpu.pc = bus.cpu.pc.wrapping_add(1);
+        data
+    }
 
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::ASL => {
-                let shift_accumulator =
-                    matches!(instr.addressing_mode, AddressingMode::Accumulator);
-                let mut addr = 0x00;
+    /// Pushes a byte onto the stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `value` - The byte that is pushed.
+    ///
+    /// Nothing is returned.
+    fn push(bus: &mut Bus, ppu: &mut Ppu, value: u8) {
+        let addr = bus.cpu.stack_push();
+        bus.data_write(ppu, addr, value);
+    }
 
-                let m = if shift_accumulator {
-                    bus.cpu.a
-                } else {
-                    addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr)
-                };
+    /// Pulls a byte from the stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// # Return
+    /// * `u8` - The pulled byte.
+    fn pull(bus: &mut Bus, ppu: &mut Ppu) -> u8 {
+        let addr = bus.cpu.stack_pop();
+        bus.data_read(ppu, addr)
+    }
 
-                let res: u16 = (m as u16) << 1;
+    /// Adds an index register to the low byte of the address that is being built. The carry into the high byte is only applied in the next cycle, after a dummy read from the unfixed address.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `high` - High byte of the base address, its low byte is already in `bus.instruction.address`.
+    /// * `index` - Value of the index register.
+    ///
+    /// Nothing is returned.
+    fn index_address(bus: &mut Bus, high: u8, index: u8) {
+        let (low, page_crossed) = (bus.instruction.address as u8).overflowing_add(index);
+        bus.instruction.address = u16::from_le_bytes([low, high]);
+        bus.instruction.page_crossed = page_crossed;
+    }
 
-                if shift_accumulator {
-                    bus.cpu.a = (res & 0xFF) as u8;
-                } else {
-                    bus.data_write(ppu, addr, (res & 0xFF) as u8);
-                }
+    /// Number of cycles after the opcode fetch that an addressing mode needs before the effective address is known. Reading instructions skip the cycle that fixes the high byte when the index did not cross a page.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the state of the instruction.
+    /// * `instr` - The instruction that is executed.
+    ///
+    /// # Return
+    /// * `u8` - The number of addressing cycles.
+    fn address_cycles(bus: &Bus, instr: &Instruction) -> u8 {
+        let fix_cycle = !matches!(instr.access(), Access::Read) || bus.instruction.page_crossed;
+        match instr.addressing_mode {
+            AddressingMode::ZeroPage => 1,
+            AddressingMode::ZeroPageX | AddressingMode::ZeroPageY | AddressingMode::Absolute => 2,
+            AddressingMode::AbsoluteX | AddressingMode::AbsoluteY => 2 + fix_cycle as u8,
+            AddressingMode::IndirectX => 4,
+            AddressingMode::IndirectY => 3 + fix_cycle as u8,
+            _ => 0,
+        }
+    }
 
-                bus.cpu.negative = res & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = res & 0xFF == 0;
-                bus.cpu.carry = res & 0x100 == 0x100;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::BCC => {
-                if !bus.cpu.carry {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2; // Next instruction
-                }
-            }
-            InstructionName::BCS => {
-                if bus.cpu.carry {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2; // Next instruction
-                }
-            }
-            InstructionName::BEQ => {
-                if bus.cpu.zero {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2; // Next instruction
+    /// Runs one of the cycles that build the effective address, including the dummy reads the 6502 does while it adds an index.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU in case PPU memory is accessed.
+    /// * `addressing_mode` - The addressing mode of the instruction.
+    ///
+    /// Nothing is returned.
+    fn address_cycle(bus: &mut Bus, ppu: &mut Ppu, addressing_mode: &AddressingMode) {
+        let state = bus.instruction;
+        match (addressing_mode, state.cycle) {
+            (
+                AddressingMode::ZeroPage
+                | AddressingMode::ZeroPageX
+                | AddressingMode::ZeroPageY
+                | AddressingMode::Absolute
+                | AddressingMode::AbsoluteX
+                | AddressingMode::AbsoluteY,
+                1,
+            ) => bus.instruction.address = Self::fetch(bus, ppu) as u16,
+            (AddressingMode::ZeroPageX, 2) => {
+                bus.data_read(ppu, state.address);
+                bus.instruction.address = (state.address as u8).wrapping_add(bus.cpu.x) as u16;
+            }
+            (AddressingMode::ZeroPageY, 2) => {
+                bus.data_read(ppu, state.address);
+                bus.instruction.address = (state.address as u8).wrapping_add(bus.cpu.y) as u16;
+            }
+            (AddressingMode::Absolute, 2) => {
+                bus.instruction.address |= (Self::fetch(bus, ppu) as u16) << 8;
+            }
+            (AddressingMode::AbsoluteX, 2) => {
+                let high = Self::fetch(bus, ppu);
+                Self::index_address(bus, high, bus.cpu.x);
+            }
+            (AddressingMode::AbsoluteY, 2) => {
+                let high = Self::fetch(bus, ppu);
+                Self::index_address(bus, high, bus.cpu.y);
+            }
+            (AddressingMode::IndirectX | AddressingMode::IndirectY, 1) => {
+                bus.instruction.pointer = Self::fetch(bus, ppu);
+            }
+            (AddressingMode::IndirectX, 2) => {
+                bus.data_read(ppu, state.pointer as u16);
+                bus.instruction.pointer = state.pointer.wrapping_add(bus.cpu.x);
+            }
+            (AddressingMode::IndirectX, 3) | (AddressingMode::IndirectY, 2) => {
+                bus.instruction.address = bus.data_read(ppu, state.pointer as u16) as u16;
+            }
+            (AddressingMode::IndirectX, 4) => {
+                let high = bus.data_read(ppu, state.pointer.wrapping_add(1) as u16);
+                bus.instruction.address |= (high as u16) << 8;
+            }
+            (AddressingMode::IndirectY, 3) => {
+                let high = bus.data_read(ppu, state.pointer.wrapping_add(1) as u16);
+                Self::index_address(bus, high, bus.cpu.y);
+            }
+            (
+                AddressingMode::AbsoluteX | AddressingMode::AbsoluteY | AddressingMode::IndirectY,
+                _,
+            ) => {
+                // Dummy read from the address before the carry reached the high byte
+                bus.data_read(ppu, state.address);
+                if state.page_crossed {
+                    bus.instruction.address = state.address.wrapping_add(0x100);
                 }
             }
-            InstructionName::BIT => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let data = bus.data_read(ppu, addr);
+            _ => unreachable!("no addressing cycle {} for this mode", state.cycle),
+        }
+    }
 
-                bus.cpu.zero = bus.cpu.a & data == 0x00;
-                bus.cpu.negative = (data & 0b1000_0000) == 0b1000_0000;
-                bus.cpu.overflow = (data & 0b0100_0000) == 0b0100_0000;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::BMI => {
-                if bus.cpu.negative {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
+    /// Runs a cycle of an instruction that accesses memory through its addressing mode. After the address is known, reading instructions take one more cycle, writing instructions as well, and read-modify-write instructions take three: the read, a dummy write of the unmodified value and the write of the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU in case PPU memory is accessed.
+    /// * `instr` - The instruction that is executed.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn memory_cycle(bus: &mut Bus, ppu: &mut Ppu, instr: &Instruction) -> bool {
+        let address_cycles = Self::address_cycles(bus, instr);
+        let cycle = bus.instruction.cycle;
+        if cycle <= address_cycles {
+            Self::address_cycle(bus, ppu, &instr.addressing_mode);
+            return false;
+        }
+
+        let address = bus.instruction.address;
+        match (instr.access(), cycle - address_cycles) {
+            (Access::Read, _) => {
+                let value = if matches!(instr.addressing_mode, AddressingMode::Immediate) {
+                    Self::fetch(bus, ppu)
                 } else {
-                    bus.cpu.pc += 2;
-                }
+                    bus.data_read(ppu, address)
+                };
+                Self::read_operation(bus, instr, value);
+                true
             }
-            InstructionName::BNE => {
-                if !bus.cpu.zero {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2;
-                }
+            (Access::Write, _) => {
+                let (address, value) = Self::write_operation(bus, instr);
+                bus.data_write(ppu, address, value);
+                true
             }
-            InstructionName::BPL => {
-                if !bus.cpu.negative {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2;
-                }
+            (Access::ReadModifyWrite, 1) => {
+                bus.instruction.data = bus.data_read(ppu, address);
+                false
             }
-            InstructionName::BRK => {
-                bus.cpu.b = true;
-                let p = bus.cpu.carry as u8
-                    | (bus.cpu.zero as u8) << 1
-                    | (bus.cpu.irq_dis as u8) << 2
-                    | (bus.cpu.dec as u8) << 3
-                    | (bus.cpu.b as u8) << 4
-                    | 0b0011_0000
-                    | (bus.cpu.overflow as u8) << 6
-                    | (bus.cpu.negative as u8) << 7;
-                bus.cpu.pc += 2;
-                bus.cpu.stack_push(((bus.cpu.pc >> 8) & 0xff) as u8);
-                bus.cpu.stack_push((bus.cpu.pc & 0xff) as u8);
-                bus.cpu.stack_push(p);
-                bus.cpu.pc =
-                    ((bus.data_read(ppu, 0xffff) as u16) << 8) | bus.data_read(ppu, 0xfffe) as u16;
-                bus.cpu.irq_dis = true;
+            (Access::ReadModifyWrite, 2) => {
+                let value = bus.instruction.data;
+                bus.data_write(ppu, address, value); // Dummy write of the unmodified value
+                bus.instruction.data = Self::modify_operation(bus, instr, value);
+                false
             }
-            InstructionName::BVC => {
-                if !bus.cpu.overflow {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2; // Next instruction
-                }
+            (Access::ReadModifyWrite, _) => {
+                bus.data_write(ppu, address, bus.instruction.data);
+                true
             }
-            InstructionName::BVS => {
-                if bus.cpu.overflow {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.cpu.pc = addr;
-                } else {
-                    bus.cpu.pc += 2; // Next instruction
+        }
+    }
+
+    /// Runs a cycle of a conditional branch. The offset is fetched in the first cycle, a taken branch adds a cycle to move the program counter and another one if it ends up on a different page.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `instr` - The branch instruction.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn branch_cycle(bus: &mut Bus, ppu: &mut Ppu, instr: &Instruction) -> bool {
+        match bus.instruction.cycle {
+            1 => {
+                bus.instruction.data = Self::fetch(bus, ppu);
+                !Self::branch_taken(bus, instr)
+            }
+            2 => {
+                // A taken branch ignores interrupts that appeared during its operand fetch, the next instruction runs first
+                if bus.pending_interrupt.is_some() && bus.polled_interrupt.is_none() {
+                    bus.pending_interrupt = None;
                 }
+                bus.data_read(ppu, bus.cpu.pc); // Dummy read of the next opcode
+                let pc = bus.cpu.pc;
+                let target = pc.wrapping_add(bus.instruction.data as i8 as u16);
+                bus.instruction.address = target;
+                bus.instruction.page_crossed = pc & 0xff00 != target & 0xff00;
+                bus.cpu.pc = (pc & 0xff00) | (target & 0x00ff);
+                !bus.instruction.page_crossed
+            }
+            _ => {
+                bus.data_read(ppu, bus.cpu.pc); // Dummy read before the high byte is fixed
+                bus.cpu.pc = bus.instruction.address;
+                true
             }
-            InstructionName::CLC => {
-                bus.cpu.carry = false;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::CLD => {
-                bus.cpu.dec = false;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::CLI => {
-                bus.cpu.irq_dis = false;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::CLV => {
-                bus.cpu.overflow = false;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::CMP => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let memory = bus.data_read(ppu, addr);
-                bus.cpu.negative = (bus.cpu.a.wrapping_sub(memory)) & 0b1000_0000 == 0b1000_0000;
-                if bus.cpu.a >= memory {
-                    bus.cpu.zero = false;
-                    if bus.cpu.a == memory {
-                        bus.cpu.zero = true;
-                    }
-                    bus.cpu.carry = true;
-                } else {
-                    bus.cpu.zero = false;
-                    bus.cpu.carry = false;
-                }
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::CPX => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let memory = bus.data_read(ppu, addr);
-                bus.cpu.negative = (bus.cpu.x.wrapping_sub(memory)) & 0b1000_0000 == 0b1000_0000;
-                if bus.cpu.x >= memory {
-                    bus.cpu.zero = false;
-                    if bus.cpu.x == memory {
-                        bus.cpu.zero = true;
-                    }
-                    bus.cpu.carry = true;
-                } else {
-                    bus.cpu.zero = false;
-                    bus.cpu.carry = false;
+        }
+    }
+
+    /// Determines whether a branch instruction is taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `instr` - The branch instruction.
+    ///
+    /// # Return
+    /// * `bool` - Whether the condition of the branch holds.
+    fn branch_taken(bus: &Bus, instr: &Instruction) -> bool {
+        match instr.instruction_name {
+            InstructionName::BCC => !bus.cpu.carry,
+            InstructionName::BCS => bus.cpu.carry,
+            InstructionName::BEQ => bus.cpu.zero,
+            InstructionName::BNE => !bus.cpu.zero,
+            InstructionName::BMI => bus.cpu.negative,
+            InstructionName::BPL => !bus.cpu.negative,
+            InstructionName::BVC => !bus.cpu.overflow,
+            InstructionName::BVS => bus.cpu.overflow,
+            _ => unreachable!("not a branch instruction"),
+        }
+    }
+
+    /// Runs a cycle of `BRK`, or of the interrupt sequence that shares its cycles. It pushes the program counter and the status, then jumps through the vector of the interrupt. The vector is chosen when the status is pushed, a pending NMI wins at that point even if the sequence started as `BRK` or IRQ. A reset reads the stack instead of writing it, so only the stack pointer moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the sequence.
+    fn break_cycle(bus: &mut Bus, ppu: &mut Ppu) -> bool {
+        let interrupt = bus.instruction.interrupt;
+        match bus.instruction.cycle {
+            1 => {
+                bus.data_read(ppu, bus.cpu.pc);
+                if interrupt.is_none() {
+                    bus.cpu.pc = bus.cpu.pc.wrapping_add(1); // BRK skips its padding byte
                 }
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::CPY => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let memory = bus.data_read(ppu, addr);
-                bus.cpu.negative = (bus.cpu.y.wrapping_sub(memory)) & 0b1000_0000 == 0b1000_0000;
-                if bus.cpu.y >= memory {
-                    bus.cpu.zero = false;
-                    if bus.cpu.y == memory {
-                        bus.cpu.zero = true;
+            }
+            2..=4 if interrupt == Some(Interrupt::Reset) => {
+                let addr = bus.cpu.stack_push();
+                bus.data_read(ppu, addr);
+                bus.instruction.address = 0xfffc;
+            }
+            2 => Self::push(bus, ppu, (bus.cpu.pc >> 8) as u8),
+            3 => Self::push(bus, ppu, bus.cpu.pc as u8),
+            4 => {
+                let p = match interrupt {
+                    None => {
+                        bus.cpu.b = true;
+                        bus.cpu.status() | 0b0011_0000
                     }
-                    bus.cpu.carry = true;
+                    Some(_) => bus.cpu.status(), // b flag is clear for interrupts
+                };
+                Self::push(bus, ppu, p);
+                // An NMI that arrives before the vector is fetched takes over BRK and IRQ sequences
+                bus.instruction.address = if bus.nmi_pending {
+                    bus.nmi_pending = false;
+                    bus.pending_interrupt = None;
+                    0xfffa
                 } else {
-                    bus.cpu.zero = false;
-                    bus.cpu.carry = false;
-                }
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::DEC => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let value = bus.data_read(ppu, addr).wrapping_sub(1);
-                bus.data_write(ppu, addr, value);
-                bus.cpu.zero = value == 0;
-                bus.cpu.negative = value & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+                    0xfffe
+                };
             }
-            InstructionName::DEX => {
-                bus.cpu.x = bus.cpu.x.wrapping_sub(1);
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+            5 => {
+                bus.instruction.data = bus.data_read(ppu, bus.instruction.address);
+                bus.cpu.irq_dis = true;
             }
-            InstructionName::DEY => {
-                bus.cpu.y = bus.cpu.y.wrapping_sub(1);
-                bus.cpu.zero = bus.cpu.y == 0;
-                bus.cpu.negative = bus.cpu.y & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+            _ => {
+                let high = bus.data_read(ppu, bus.instruction.address + 1);
+                bus.cpu.pc = u16::from_le_bytes([bus.instruction.data, high]);
+                return true;
             }
-            InstructionName::EOR => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-
-                bus.cpu.a ^= m;
+        }
+        false
+    }
 
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.pc += 1; // Next instruction
+    /// Runs a cycle of `JSR`, which pushes the address of its last byte before it fetches the high byte of the target.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn jsr_cycle(bus: &mut Bus, ppu: &mut Ppu) -> bool {
+        match bus.instruction.cycle {
+            1 => bus.instruction.data = Self::fetch(bus, ppu),
+            2 => {
+                bus.data_read(ppu, bus.cpu.sp); // Dummy read of the stack
+            }
+            3 => Self::push(bus, ppu, (bus.cpu.pc >> 8) as u8),
+            4 => Self::push(bus, ppu, bus.cpu.pc as u8),
+            _ => {
+                let high = bus.data_read(ppu, bus.cpu.pc);
+                bus.cpu.pc = u16::from_le_bytes([bus.instruction.data, high]);
+                return true;
             }
-            InstructionName::LDA => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.a = bus.data_read(ppu, addr);
+        }
+        false
+    }
 
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+    /// Runs a cycle of `RTI`, which pulls the status and the program counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn rti_cycle(bus: &mut Bus, ppu: &mut Ppu) -> bool {
+        match bus.instruction.cycle {
+            1 => {
+                bus.data_read(ppu, bus.cpu.pc);
+            }
+            2 => {
+                bus.data_read(ppu, bus.cpu.sp);
+            }
+            3 => {
+                let p = Self::pull(bus, ppu);
+                bus.cpu.set_status(p);
+                bus.cpu.b = (p & 0b0001_0000) == 0b0001_0000;
             }
-            InstructionName::LDX => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.x = bus.data_read(ppu, addr);
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+            4 => bus.instruction.data = Self::pull(bus, ppu),
+            _ => {
+                let high = Self::pull(bus, ppu);
+                bus.cpu.pc = u16::from_le_bytes([bus.instruction.data, high]);
+                return true;
             }
-            InstructionName::LDY => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.y = bus.data_read(ppu, addr);
-                bus.cpu.zero = bus.cpu.y == 0;
-                bus.cpu.negative = bus.cpu.y & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::LSR => {
-                let shift_accumulator =
-                    matches!(instr.addressing_mode, AddressingMode::Accumulator);
-                let mut addr = 0x00;
-
-                let m = if shift_accumulator {
-                    bus.cpu.a
-                } else {
-                    addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr)
-                };
+        }
+        false
+    }
 
-                bus.cpu.carry = m & 0x1 == 0x1;
+    /// Runs a cycle of `RTS`, which pulls the program counter and moves it past the last byte of the `JSR`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn rts_cycle(bus: &mut Bus, ppu: &mut Ppu) -> bool {
+        match bus.instruction.cycle {
+            1 => {
+                bus.data_read(ppu, bus.cpu.pc);
+            }
+            2 => {
+                bus.data_read(ppu, bus.cpu.sp);
+            }
+            3 => bus.instruction.data = Self::pull(bus, ppu),
+            4 => {
+                let high = Self::pull(bus, ppu);
+                bus.cpu.pc = u16::from_le_bytes([bus.instruction.data, high]);
+            }
+            _ => {
+                bus.data_read(ppu, bus.cpu.pc);
+                bus.cpu.pc = bus.cpu.pc.wrapping_add(1); // Next instruction
+                return true;
+            }
+        }
+        false
+    }
 
-                let res = m >> 1;
+    /// Runs a cycle of `PHA` or `PHP`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `instr` - The push instruction.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn push_cycle(bus: &mut Bus, ppu: &mut Ppu, instr: &Instruction) -> bool {
+        if bus.instruction.cycle == 1 {
+            bus.data_read(ppu, bus.cpu.pc);
+            return false;
+        }
+        let value = match instr.instruction_name {
+            InstructionName::PHA => bus.cpu.a,
+            _ => bus.cpu.status() | 0b0011_0000, // write b flag
+        };
+        Self::push(bus, ppu, value);
+        true
+    }
 
-                if shift_accumulator {
-                    bus.cpu.a = res;
-                } else {
-                    bus.data_write(ppu, addr, res);
+    /// Runs a cycle of `PLA` or `PLP`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `instr` - The pull instruction.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn pull_cycle(bus: &mut Bus, ppu: &mut Ppu, instr: &Instruction) -> bool {
+        match bus.instruction.cycle {
+            1 => {
+                bus.data_read(ppu, bus.cpu.pc);
+                false
+            }
+            2 => {
+                bus.data_read(ppu, bus.cpu.sp);
+                false
+            }
+            _ => {
+                let value = Self::pull(bus, ppu);
+                match instr.instruction_name {
+                    InstructionName::PLA => {
+                        bus.cpu.a = value;
+                        bus.cpu.set_zero_negative(value);
+                    }
+                    _ => bus.cpu.set_status(value),
                 }
-
-                bus.cpu.negative = false; // The 7th bit cannot be 1 when shifted to the right
-                bus.cpu.zero = res == 0;
-                bus.cpu.pc += 1; // Next instruction
+                true
             }
-            InstructionName::ORA => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-
-                bus.cpu.a |= m;
+        }
+    }
 
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::PHA => {
-                bus.cpu.stack_push(bus.cpu.a);
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::PHP => {
-                let p = bus.cpu.carry as u8 |
-                    (bus.cpu.zero as u8) << 1 |
-                    (bus.cpu.irq_dis as u8) << 2 |
-                    (bus.cpu.dec as u8) << 3 |
-                    0b0011_0000 | // write b flag
-                    (bus.cpu.overflow as u8) << 6 |
-                    (bus.cpu.negative as u8) << 7;
-                bus.cpu.stack_push(p);
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::PLA => {
-                bus.cpu.a = bus.cpu.stack_pop();
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::PLP => {
-                let p = bus.cpu.stack_pop();
-                bus.cpu.carry = (p & 0b0000_0001) == 0b0000_0001;
-                bus.cpu.zero = (p & 0b0000_0010) == 0b0000_0010;
-                bus.cpu.irq_dis = (p & 0b0000_0100) == 0b0000_0100;
-                bus.cpu.dec = (p & 0b0000_1000) == 0b0000_1000;
-                bus.cpu.overflow = (p & 0b0100_0000) == 0b0100_0000;
-                bus.cpu.negative = (p & 0b1000_0000) == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+    /// Runs a cycle of `JMP`. The indirect form reads the target from a pointer and does not carry into the high byte of the pointer, just like the 6502.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `ppu` - Borrowed instance of PPU.
+    /// * `instr` - The jump instruction.
+    ///
+    /// # Return
+    /// * `bool` - Whether this was the last cycle of the instruction.
+    fn jmp_cycle(bus: &mut Bus, ppu: &mut Ppu, instr: &Instruction) -> bool {
+        let address = bus.instruction.address;
+        match (&instr.addressing_mode, bus.instruction.cycle) {
+            (_, 1) => bus.instruction.address = Self::fetch(bus, ppu) as u16,
+            (AddressingMode::Absolute, _) => {
+                let high = bus.data_read(ppu, bus.cpu.pc);
+                bus.cpu.pc = address | (high as u16) << 8;
+                return true;
+            }
+            (_, 2) => bus.instruction.address |= (Self::fetch(bus, ppu) as u16) << 8,
+            (_, 3) => bus.instruction.data = bus.data_read(ppu, address),
+            _ => {
+                let high_addr = (address & 0xff00) | (address.wrapping_add(1) & 0x00ff);
+                let high = bus.data_read(ppu, high_addr);
+                bus.cpu.pc = u16::from_le_bytes([bus.instruction.data, high]);
+                return true;
             }
+        }
+        false
+    }
 
-            InstructionName::RTI => {
-                let p = bus.cpu.stack_pop();
-                bus.cpu.carry = (p & 0b0000_0001) == 0b0000_0001;
-                bus.cpu.zero = (p & 0b0000_0010) == 0b0000_0010;
-                bus.cpu.irq_dis = (p & 0b0000_0100) == 0b0000_0100;
-                bus.cpu.dec = (p & 0b0000_1000) == 0b0000_1000;
-                bus.cpu.b = (p & 0b0001_0000) == 0b0001_0000;
-                bus.cpu.overflow = (p & 0b0100_0000) == 0b0100_0000;
-                bus.cpu.negative = (p & 0b1000_0000) == 0b1000_0000;
-                bus.cpu.pc = (bus.cpu.stack_pop() as u16) | ((bus.cpu.stack_pop() as u16) << 8);
-            }
-            InstructionName::RTS => {
-                bus.cpu.pc = (bus.cpu.stack_pop() as u16) | (bus.cpu.stack_pop() as u16) << 8;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::NOP => {
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::STX => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.data_write(ppu, addr, bus.cpu.x);
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::STY => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.data_write(ppu, addr, bus.cpu.y);
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::INC => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let value = bus.data_read(ppu, addr).wrapping_add(1);
-                bus.data_write(ppu, addr, value);
-                bus.cpu.zero = value == 0;
-                bus.cpu.negative = value & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+    /// Executes an instruction without memory operand, which takes a single cycle after its dummy read.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `instr` - The instruction that is executed.
+    ///
+    /// Nothing is returned.
+    fn implied_operation(bus: &mut Bus, instr: &Instruction) {
+        match instr.instruction_name {
+            InstructionName::ASL
+            | InstructionName::LSR
+            | InstructionName::ROL
+            | InstructionName::ROR => {
+                bus.cpu.a = Self::modify_operation(bus, instr, bus.cpu.a);
+            }
+            InstructionName::CLC => bus.cpu.carry = false,
+            InstructionName::CLD => bus.cpu.dec = false,
+            InstructionName::CLI => bus.cpu.irq_dis = false,
+            InstructionName::CLV => bus.cpu.overflow = false,
+            InstructionName::SEC => bus.cpu.carry = true,
+            InstructionName::SED => bus.cpu.dec = true,
+            InstructionName::SEI => bus.cpu.irq_dis = true,
+            InstructionName::DEX => {
+                bus.cpu.x = bus.cpu.x.wrapping_sub(1);
+                bus.cpu.set_zero_negative(bus.cpu.x);
+            }
+            InstructionName::DEY => {
+                bus.cpu.y = bus.cpu.y.wrapping_sub(1);
+                bus.cpu.set_zero_negative(bus.cpu.y);
             }
             InstructionName::INX => {
                 bus.cpu.x = bus.cpu.x.wrapping_add(1);
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.x);
             }
             InstructionName::INY => {
                 bus.cpu.y = bus.cpu.y.wrapping_add(1);
-                bus.cpu.negative = bus.cpu.y & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.y == 0;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::JMP => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.pc = addr;
-            }
-            InstructionName::JSR => {
-                bus.cpu.pc += 2;
-                bus.cpu.stack_push(((bus.cpu.pc >> 8) & 0xff) as u8);
-                bus.cpu.stack_push((bus.cpu.pc & 0xff) as u8);
-                bus.cpu.pc -= 2;
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.pc = addr;
-            }
-            InstructionName::ROL => {
-                let shift_accumulator =
-                    matches!(instr.addressing_mode, AddressingMode::Accumulator);
-                let mut addr = 0x00;
-
-                let m = if shift_accumulator {
-                    bus.cpu.a
-                } else {
-                    addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr)
-                };
-
-                let res: u16 = ((m as u16) << 1) | (bus.cpu.carry as u16);
-
-                if shift_accumulator {
-                    bus.cpu.a = (res & 0xFF) as u8;
-                } else {
-                    bus.data_write(ppu, addr, (res & 0xFF) as u8);
-                }
-
-                bus.cpu.negative = res & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = res & 0xFF == 0;
-                bus.cpu.carry = res & 0x100 == 0x100;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::ROR => {
-                let shift_accumulator =
-                    matches!(instr.addressing_mode, AddressingMode::Accumulator);
-                let mut addr = 0x00;
-
-                let m = if shift_accumulator {
-                    bus.cpu.a
-                } else {
-                    addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr)
-                };
-
-                let res = (m >> 1) | ((bus.cpu.carry as u8) << 7);
-
-                if shift_accumulator {
-                    bus.cpu.a = res;
-                } else {
-                    bus.data_write(ppu, addr, res);
-                }
-
-                bus.cpu.negative = res & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = res == 0;
-                bus.cpu.carry = m & 0x1 == 0x1;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SBC => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-
-                let a_is_positive = bus.cpu.a < 0b1000_0000;
-                let m_is_positive = m < 0b1000_0000;
-                let a_positive_and_m_negative = a_is_positive && !m_is_positive;
-                let a_negative_and_m_positive = !a_is_positive && m_is_positive;
-
-                let (temp, carry1) = bus.cpu.a.overflowing_sub(m);
-                let (res, carry2) = temp.overflowing_sub(if bus.cpu.carry { 0 } else { 1 });
-                bus.cpu.carry = !(carry1 || carry2);
-                bus.cpu.zero = res == 0;
-                bus.cpu.negative = (res & 0b1000_0000) == 0b1000_0000;
-                bus.cpu.overflow = (a_positive_and_m_negative && res > 0b0111_1111)
-                    || (a_negative_and_m_positive && res < 0b1000_0000);
-                bus.cpu.a = res as u8;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SEC => {
-                bus.cpu.carry = true;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SED => {
-                bus.cpu.dec = true;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SEI => {
-                bus.cpu.irq_dis = true;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::STA => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.data_write(ppu, addr, bus.cpu.a);
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.y);
             }
             InstructionName::TAX => {
                 bus.cpu.x = bus.cpu.a;
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.x);
             }
             InstructionName::TAY => {
                 bus.cpu.y = bus.cpu.a;
-                bus.cpu.zero = bus.cpu.y == 0;
-                bus.cpu.negative = bus.cpu.y & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.y);
             }
             InstructionName::TSX => {
                 bus.cpu.x = (bus.cpu.sp & 0xff) as u8;
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.x);
             }
             InstructionName::TXA => {
                 bus.cpu.a = bus.cpu.x;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::TXS => {
-                bus.cpu.sp = 0x100 | (bus.cpu.x as u16);
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.a);
             }
+            InstructionName::TXS => bus.cpu.sp = 0x100 | (bus.cpu.x as u16),
             InstructionName::TYA => {
                 bus.cpu.a = bus.cpu.y;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.set_zero_negative(bus.cpu.a);
+            }
+            _ => {} // NOP
+        }
+    }
+
+    /// Executes an instruction that reads its operand, in the cycle in which the operand is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `instr` - The instruction that is executed.
+    /// * `m` - The operand.
+    ///
+    /// Nothing is returned.
+    fn read_operation(bus: &mut Bus, instr: &Instruction, m: u8) {
+        match instr.instruction_name {
+            InstructionName::ADC => Self::add_with_carry(bus, m),
+            InstructionName::SBC | InstructionName::USBC => Self::add_with_carry(bus, !m),
+            InstructionName::AND => {
+                bus.cpu.a &= m;
+                bus.cpu.set_zero_negative(bus.cpu.a);
+            }
+            InstructionName::EOR => {
+                bus.cpu.a ^= m;
+                bus.cpu.set_zero_negative(bus.cpu.a);
+            }
+            InstructionName::ORA => {
+                bus.cpu.a |= m;
+                bus.cpu.set_zero_negative(bus.cpu.a);
+            }
+            InstructionName::BIT => {
+                bus.cpu.zero = bus.cpu.a & m == 0x00;
+                bus.cpu.negative = (m & 0b1000_0000) == 0b1000_0000;
+                bus.cpu.overflow = (m & 0b0100_0000) == 0b0100_0000;
+            }
+            InstructionName::CMP => Self::compare(bus, bus.cpu.a, m),
+            InstructionName::CPX => Self::compare(bus, bus.cpu.x, m),
+            InstructionName::CPY => Self::compare(bus, bus.cpu.y, m),
+            InstructionName::LDA => {
+                bus.cpu.a = m;
+                bus.cpu.set_zero_negative(m);
+            }
+            InstructionName::LDX => {
+                bus.cpu.x = m;
+                bus.cpu.set_zero_negative(m);
+            }
+            InstructionName::LDY => {
+                bus.cpu.y = m;
+                bus.cpu.set_zero_negative(m);
+            }
+            InstructionName::LAX | InstructionName::LXA => {
+                // LDA followed by TAX
+                bus.cpu.a = m;
+                bus.cpu.x = m;
+                bus.cpu.set_zero_negative(m);
             }
             InstructionName::ALR => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
                 bus.cpu.a &= m;
                 bus.cpu.carry = bus.cpu.a & 0x1 == 0x1;
-                let res = bus.cpu.a >> 1;
-
-                bus.cpu.a = res;
-
-                bus.cpu.negative = false; // The 7th bit cannot be 1 when shifted to the right
-                bus.cpu.zero = res == 0;
-                bus.cpu.pc += 1; // Next instruction
+                bus.cpu.a >>= 1;
+                bus.cpu.set_zero_negative(bus.cpu.a);
             }
             InstructionName::ANC => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-
-                // operation
                 bus.cpu.a &= m;
-
-                //flags
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
+                bus.cpu.set_zero_negative(bus.cpu.a);
                 //copy flag n to c
                 bus.cpu.carry = bus.cpu.negative;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::ANE => {
-                bus.cpu.pc += 1; // Next instruction
             }
             InstructionName::ARR => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-
-                // operation AND and shift
-                bus.cpu.a &= m;
-
-                let res = (bus.cpu.a >> 1) | ((bus.cpu.carry as u8) << 7);
-                bus.cpu.a = res;
-
-                bus.cpu.negative = res & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = res == 0;
-                bus.cpu.carry = res & 0b0100_0000 == 0b0100_0000;
+                // AND followed by ROR, with the flags taken from the result
+                bus.cpu.a = ((bus.cpu.a & m) >> 1) | ((bus.cpu.carry as u8) << 7);
+                bus.cpu.set_zero_negative(bus.cpu.a);
+                bus.cpu.carry = bus.cpu.a & 0b0100_0000 == 0b0100_0000;
                 bus.cpu.overflow = ((bus.cpu.a << 1) ^ bus.cpu.a) & 0b0100_0000 != 0;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::DCP => {
-                // do DEC
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let value = bus.data_read(ppu, addr).wrapping_sub(1);
-                let result = bus.cpu.a.wrapping_sub(value);
-                bus.data_write(ppu, addr, value);
-                // flags
-                bus.cpu.negative = result & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = result == 0;
-                bus.cpu.carry = value <= bus.cpu.a as u8;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::ISC => {
-                // do INC
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let value = bus.data_read(ppu, addr).wrapping_add(1);
-                bus.data_write(ppu, addr, value);
-                // do SBC
-                let m = bus.data_read(ppu, addr);
-
-                let a_is_positive = bus.cpu.a < 0b1000_0000;
-                let m_is_positive = m < 0b1000_0000;
-                let a_positive_and_m_negative = a_is_positive && !m_is_positive;
-                let a_negative_and_m_positive = !a_is_positive && m_is_positive;
-
-                let (temp, carry1) = bus.cpu.a.overflowing_sub(m);
-                let (res, carry2) = temp.overflowing_sub(if bus.cpu.carry { 0 } else { 1 });
-                bus.cpu.carry = !(carry1 || carry2);
-                bus.cpu.zero = res == 0;
-                bus.cpu.negative = (res & 0b1000_0000) == 0b1000_0000;
-                bus.cpu.overflow = (a_positive_and_m_negative && res > 0b0111_1111)
-                    || (a_negative_and_m_positive && res < 0b1000_0000);
-                bus.cpu.a = res as u8;
-                bus.cpu.pc += 1; // Next instruction
             }
-            InstructionName::LAS => {
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::LAX => {
-                // do LDA
-                //value & a
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.a = bus.data_read(ppu, addr);
-                //ps
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                // do TAX
-                bus.cpu.x = bus.cpu.a;
-                //ps
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::LXA => {
-                // do LDA
-                //value & a
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                bus.cpu.a = bus.data_read(ppu, addr);
-                //ps
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                // do TAX
-                bus.cpu.x = bus.cpu.a;
-                //ps
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::RLA => {
-                // ROL
-                let shift_accumulator =
-                    matches!(instr.addressing_mode, AddressingMode::Accumulator);
-                let mut addr = 0x00;
-
-                let m = if shift_accumulator {
-                    bus.cpu.a
-                } else {
-                    addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr)
-                };
-
-                let res: u16 = ((m as u16) << 1) | (bus.cpu.carry as u16);
-
-                if shift_accumulator {
-                    bus.cpu.a = (res & 0xFF) as u8;
-                } else {
-                    bus.data_write(ppu, addr, (res & 0xFF) as u8);
-                }
-                bus.cpu.carry = res & 0x100 == 0x100;
-
-                // AND
-                let m1 = bus.data_read(ppu, addr);
-                bus.cpu.a &= m1;
-
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::RRA => {
-                // ROR
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-
-                let m = bus.data_read(ppu, addr);
-
-                let res_ror = ((m >> 1) | ((bus.cpu.carry as u8) << 7)) as u16;
-                bus.data_write(ppu, addr, res_ror as u8);
-                bus.cpu.carry = m & 0b0000_0001 != 0;
-
-                // ADC according to the carry flag
-                let mut res = (bus.cpu.a as u16) + res_ror;
-                if bus.cpu.carry {
-                    res += 1;
-                }
-                //bus.data_write(ppu, addr, res as u8);
-
-                bus.cpu.overflow =
-                    ((bus.cpu.a ^ (res as u8)) & ((res_ror as u8) ^ (res as u8)) & 0x80) == 0x80;
-                bus.cpu.a = res as u8;
-
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-
-                bus.cpu.carry = res > 0xff;
-                bus.cpu.pc += 1; // Next instruction
+            InstructionName::ANE => {
+                // The unstable constant is 0xee on most consoles
+                bus.cpu.a = (bus.cpu.a | 0xee) & bus.cpu.x & m;
+                bus.cpu.set_zero_negative(bus.cpu.a);
             }
-            InstructionName::SAX => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                // operation
-                let res = bus.cpu.a & bus.cpu.x;
-                bus.data_write(ppu, addr, res);
-                bus.cpu.pc += 1; // Next instruction
+            InstructionName::LAS => {
+                let res = m & (bus.cpu.sp & 0xff) as u8;
+                bus.cpu.a = res;
+                bus.cpu.x = res;
+                bus.cpu.sp = 0x100 | res as u16;
+                bus.cpu.set_zero_negative(res);
             }
             InstructionName::SBX => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
                 let res = bus.cpu.x & bus.cpu.a;
-                let m = bus.data_read(ppu, addr);
                 bus.cpu.x = res.wrapping_sub(m);
                 //set flags as CMP
-                bus.cpu.zero = bus.cpu.x == 0;
-                bus.cpu.negative = bus.cpu.x & 0b1000_0000 == 0b1000_0000;
+                bus.cpu.set_zero_negative(bus.cpu.x);
                 bus.cpu.carry = m <= res;
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SHA => {
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SHX => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let hh = (addr >> 8).wrapping_add(1);
-                let ll = addr & 0xff;
-                let res = (bus.cpu.x as u16 & hh) as u8;
-                let addrs1 = ((bus.cpu.x as u16 & hh) << 8) | ll;
-                bus.data_write(ppu, addrs1, res);
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SHY => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let hh = (addr >> 8).wrapping_add(1);
-                let ll = addr & 0xff;
-                let res = (bus.cpu.y as u16 & hh) as u8;
-                let addrs1 = ((bus.cpu.y as u16 & hh) << 8) | ll;
-                bus.data_write(ppu, addrs1, res);
-                bus.cpu.pc += 1; // Next instruction
-            }
-            InstructionName::SLO => {
-                // ASL
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-                // Carry flag
-                bus.cpu.carry = m & 0b1000_0000 == 0b1000_0000;
-                let res = m << 1;
-                bus.data_write(ppu, addr, res);
-                // ORA
-                bus.cpu.a |= res as u8;
-                // Negative and zero flags
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-
-                bus.cpu.pc += 1; // Next instruction
             }
-            InstructionName::SRE => {
-                // LSR
-                let shift_accumulator =
-                    matches!(instr.addressing_mode, AddressingMode::Accumulator);
-                let mut addr = 0x00;
+            _ => {} // NOPs only do the read
+        }
+    }
 
-                let m = if shift_accumulator {
-                    bus.cpu.a
-                } else {
-                    addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr)
-                };
+    /// Determines the address and value a writing instruction stores. `SHA`, `SHX`, `SHY` and `TAS` AND the value with the high byte of the base address plus one, and when the index crossed a page that value also replaces the high byte of the address.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU and the state of the instruction.
+    /// * `instr` - The instruction that is executed.
+    ///
+    /// # Return
+    /// * `(u16, u8)` - The address and the value that is written to it.
+    fn write_operation(bus: &mut Bus, instr: &Instruction) -> (u16, u8) {
+        let address = bus.instruction.address;
+        let unstable_source = match instr.instruction_name {
+            InstructionName::STA => return (address, bus.cpu.a),
+            InstructionName::STX => return (address, bus.cpu.x),
+            InstructionName::STY => return (address, bus.cpu.y),
+            InstructionName::SAX => return (address, bus.cpu.a & bus.cpu.x),
+            InstructionName::SHX => bus.cpu.x,
+            InstructionName::SHY => bus.cpu.y,
+            InstructionName::SHA => bus.cpu.a & bus.cpu.x,
+            InstructionName::TAS => {
+                bus.cpu.sp = 0x100 | (bus.cpu.a & bus.cpu.x) as u16;
+                bus.cpu.a & bus.cpu.x
+            }
+            _ => unreachable!("not a writing instruction"),
+        };
+        let page_crossed = bus.instruction.page_crossed;
+        let base_high = ((address >> 8) as u8).wrapping_sub(page_crossed as u8);
+        let value = unstable_source & base_high.wrapping_add(1);
+        if page_crossed {
+            (u16::from_le_bytes([address as u8, value]), value)
+        } else {
+            (address, value)
+        }
+    }
 
-                // Carry flag
+    /// Executes a read-modify-write instruction on its operand. The combined unofficial instructions also do their second operation on the accumulator here.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `instr` - The instruction that is executed.
+    /// * `m` - The operand.
+    ///
+    /// # Return
+    /// * `u8` - The modified operand that is written back.
+    fn modify_operation(bus: &mut Bus, instr: &Instruction, m: u8) -> u8 {
+        let res = match instr.instruction_name {
+            InstructionName::ASL | InstructionName::SLO => {
+                bus.cpu.carry = m & 0b1000_0000 == 0b1000_0000;
+                m << 1
+            }
+            InstructionName::LSR | InstructionName::SRE => {
                 bus.cpu.carry = m & 0x1 == 0x1;
-
-                // operation
-                let res = m >> 1;
-
-                // Writeback
-                if shift_accumulator {
-                    bus.cpu.a = res;
-                } else {
-                    bus.data_write(ppu, addr, res);
-                }
-
-                // EOR
-                // operation
-                bus.cpu.a ^= res;
-
-                //flags
-                bus.cpu.negative = bus.cpu.a & 0b1000_0000 == 0b1000_0000;
-                bus.cpu.zero = bus.cpu.a == 0;
-                bus.cpu.pc += 1; // Next instruction
+                m >> 1
             }
-            InstructionName::TAS => {
-                bus.cpu.pc += 1; // Next instruction
+            InstructionName::ROL | InstructionName::RLA => {
+                let res = (m << 1) | bus.cpu.carry as u8;
+                bus.cpu.carry = m & 0b1000_0000 == 0b1000_0000;
+                res
             }
-            InstructionName::USBC => {
-                let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                let m = bus.data_read(ppu, addr);
-
-                let a_is_positive = bus.cpu.a < 0b1000_0000;
-                let m_is_positive = m < 0b1000_0000;
-                let a_positive_and_m_negative = a_is_positive && !m_is_positive;
-                let a_negative_and_m_positive = !a_is_positive && m_is_positive;
-
-                let (temp, carry1) = bus.cpu.a.overflowing_sub(m);
-                let (res, carry2) = temp.overflowing_sub(if bus.cpu.carry { 0 } else { 1 });
-                bus.cpu.carry = !(carry1 || carry2);
-                bus.cpu.zero = res == 0;
-                bus.cpu.negative = (res & 0b1000_0000) == 0b1000_0000;
-                bus.cpu.overflow = (a_positive_and_m_negative && res > 0b0111_1111)
-                    || (a_negative_and_m_positive && res < 0b1000_0000);
-                bus.cpu.a = res as u8;
+            InstructionName::ROR | InstructionName::RRA => {
+                let res = (m >> 1) | ((bus.cpu.carry as u8) << 7);
+                bus.cpu.carry = m & 0x1 == 0x1;
+                res
+            }
+            InstructionName::INC | InstructionName::ISC => m.wrapping_add(1),
+            InstructionName::DEC | InstructionName::DCP => m.wrapping_sub(1),
+            _ => unreachable!("not a read-modify-write instruction"),
+        };
 
-                bus.cpu.pc += 1; //Next instruction
+        match instr.instruction_name {
+            InstructionName::SLO => {
+                bus.cpu.a |= res;
+                bus.cpu.set_zero_negative(bus.cpu.a);
             }
-            InstructionName::NOPs => {
-                if !matches!(instr.addressing_mode, AddressingMode::Implied) {
-                    let addr = Self::get_data_address(bus, instr.addressing_mode, ppu);
-                    bus.data_read(ppu, addr);
-                }
-                bus.cpu.pc += 1; // Next instruction
+            InstructionName::RLA => {
+                bus.cpu.a &= res;
+                bus.cpu.set_zero_negative(bus.cpu.a);
             }
-            InstructionName::JAM => {
-                // To kill the program
-                bus.jam = true;
+            InstructionName::SRE => {
+                bus.cpu.a ^= res;
+                bus.cpu.set_zero_negative(bus.cpu.a);
             }
+            InstructionName::RRA => Self::add_with_carry(bus, res),
+            InstructionName::ISC => Self::add_with_carry(bus, !res),
+            InstructionName::DCP => Self::compare(bus, bus.cpu.a, res),
+            _ => bus.cpu.set_zero_negative(res),
         }
+        res
+    }
+
+    /// Adds the operand and the carry to the accumulator and sets the flags like `ADC`. Subtraction is addition of the inverted operand.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `m` - The operand.
+    ///
+    /// Nothing is returned.
+    fn add_with_carry(bus: &mut Bus, m: u8) {
+        let res = bus.cpu.a as u16 + m as u16 + bus.cpu.carry as u16;
+        bus.cpu.overflow = ((bus.cpu.a ^ (res as u8)) & (m ^ (res as u8)) & 0x80) == 0x80;
+        bus.cpu.carry = res > 0xff;
+        bus.cpu.a = res as u8;
+        bus.cpu.set_zero_negative(bus.cpu.a);
+    }
+
+    /// Compares a register with the operand and sets the flags like `CMP`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - Borrowed instance of Bus, which holds the CPU.
+    /// * `register` - Value of the compared register.
+    /// * `m` - The operand.
+    ///
+    /// Nothing is returned.
+    fn compare(bus: &mut Bus, register: u8, m: u8) {
+        bus.cpu.set_zero_negative(register.wrapping_sub(m));
+        bus.cpu.carry = register >= m;
     }
 }
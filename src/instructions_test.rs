@@ -1,29 +1,40 @@
 #[cfg(test)]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::unnecessary_cast,
+    clippy::unusual_byte_groupings
+)]
 mod instruction_tests {
     use crate::instructions::Instruction;
+    use crate::mapper::get_mapper;
     use crate::Bus;
     use tudelft_nes_ppu::{Mirroring, Ppu};
 
+    /// Index into the 16 kB PRG ROM of the default cartridge that is mapped to `addr`.
+    fn prg(addr: u16) -> usize {
+        (addr as usize - 0x8000) % 0x4000
+    }
+
     #[test]
     fn test_bcc() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x90; // BCC Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x90; // BCC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.carry = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x90; // BCC Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x90; // BCC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.carry = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x800a);
 
         test_cpu.cpu.pc = 0x8005;
-        test_cpu.cpu.mem[0x8005] = 0x90; // BCC Relative
-        test_cpu.cpu.mem[0x8006] = 0b1111_1101; // -3
+        test_cpu.cartridge.prg_rom_data[prg(0x8005)] = 0x90; // BCC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8006)] = 0b1111_1101; // -3
         test_cpu.cpu.carry = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8004); // 0x8005 - 3 + 2
@@ -32,22 +43,22 @@ mod instruction_tests {
     fn test_bcs() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xb0; // BCS Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb0; // BCS Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.carry = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xb0; // BCS Relative
-        test_cpu.cpu.mem[0x8001] = 0x18;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb0; // BCS Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x18;
         test_cpu.cpu.carry = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x801a);
 
         test_cpu.cpu.pc = 0x800a;
-        test_cpu.cpu.mem[0x800a] = 0xb0; // BCC Relative
-        test_cpu.cpu.mem[0x800b] = 0b1111_1011; // -5
+        test_cpu.cartridge.prg_rom_data[prg(0x800a)] = 0xb0; // BCC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x800b)] = 0b1111_1011; // -5
         test_cpu.cpu.carry = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8007); // 0x800a - 5 + 2
@@ -56,22 +67,22 @@ mod instruction_tests {
     fn test_beq() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xf0; // BEQ Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xf0; // BEQ Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.zero = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xf0; // BEQ Relative
-        test_cpu.cpu.mem[0x8001] = 0x7f;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xf0; // BEQ Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x7f;
         test_cpu.cpu.zero = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8081);
 
         test_cpu.cpu.pc = 0x9000;
-        test_cpu.cpu.mem[0x9000] = 0xf0; // BEQ Relative
-        test_cpu.cpu.mem[0x9001] = 0b1001_1100; // -100
+        test_cpu.cartridge.prg_rom_data[prg(0x9000)] = 0xf0; // BEQ Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0b1001_1100; // -100
         test_cpu.cpu.zero = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8f9e);
@@ -80,8 +91,8 @@ mod instruction_tests {
     fn test_bit() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x24; // BIT Zero Page
-        test_cpu.cpu.mem[0x8001] = 0xff;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x24; // BIT Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xff;
         test_cpu.cpu.mem[0x00ff] = 0b1100_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.negative, true);
@@ -89,8 +100,8 @@ mod instruction_tests {
 
         test_cpu.cpu.negative = false;
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x24; // BIT Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x24; // BIT Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0b1000_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.negative, true);
@@ -99,9 +110,9 @@ mod instruction_tests {
         test_cpu.cpu.negative = false;
         test_cpu.cpu.overflow = false;
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x2c; // BIT Absolute
-        test_cpu.cpu.mem[0x8001] = 0xff;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x2c; // BIT Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xff;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.mem[0x01ff] = 0b0100_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.negative, false);
@@ -111,22 +122,22 @@ mod instruction_tests {
     fn test_bmi() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x30; // BMI Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x30; // BMI Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.negative = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x30; // BMI Relative
-        test_cpu.cpu.mem[0x8001] = 0x7f;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x30; // BMI Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x7f;
         test_cpu.cpu.negative = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8081);
 
         test_cpu.cpu.pc = 0x9000;
-        test_cpu.cpu.mem[0x9000] = 0x30; // BMI Relative
-        test_cpu.cpu.mem[0x9001] = 0b1001_1100; // -100
+        test_cpu.cartridge.prg_rom_data[prg(0x9000)] = 0x30; // BMI Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0b1001_1100; // -100
         test_cpu.cpu.negative = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8f9e);
@@ -135,22 +146,22 @@ mod instruction_tests {
     fn test_bne() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xd0; // BNE Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.zero = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xd0; // BNE Relative
-        test_cpu.cpu.mem[0x8001] = 0x7f;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x7f;
         test_cpu.cpu.zero = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8081);
 
         test_cpu.cpu.pc = 0x9000;
-        test_cpu.cpu.mem[0x9000] = 0xd0; // BNE Relative
-        test_cpu.cpu.mem[0x9001] = 0b1001_1100; // -100
+        test_cpu.cartridge.prg_rom_data[prg(0x9000)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0b1001_1100; // -100
         test_cpu.cpu.zero = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8f9e);
@@ -159,22 +170,22 @@ mod instruction_tests {
     fn test_bpl() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x10; // BPL Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x10; // BPL Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.negative = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x10; // BPL Relative
-        test_cpu.cpu.mem[0x8001] = 0x7f;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x10; // BPL Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x7f;
         test_cpu.cpu.negative = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8081);
 
         test_cpu.cpu.pc = 0x9000;
-        test_cpu.cpu.mem[0x9000] = 0x10; // BPL Relative
-        test_cpu.cpu.mem[0x9001] = 0b1001_1100; // -100
+        test_cpu.cartridge.prg_rom_data[prg(0x9000)] = 0x10; // BPL Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0b1001_1100; // -100
         test_cpu.cpu.negative = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8f9e);
@@ -183,22 +194,22 @@ mod instruction_tests {
     fn test_bvc() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x50; // BVC Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x50; // BVC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.overflow = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x50; // BVC Relative
-        test_cpu.cpu.mem[0x8001] = 0x7f;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x50; // BVC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x7f;
         test_cpu.cpu.overflow = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8081);
 
         test_cpu.cpu.pc = 0x9000;
-        test_cpu.cpu.mem[0x9000] = 0x50; // BVC Relative
-        test_cpu.cpu.mem[0x9001] = 0b1001_1100; // -100
+        test_cpu.cartridge.prg_rom_data[prg(0x9000)] = 0x50; // BVC Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0b1001_1100; // -100
         test_cpu.cpu.overflow = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8f9e);
@@ -207,22 +218,22 @@ mod instruction_tests {
     fn test_bvs() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x70; // BVS Relative
-        test_cpu.cpu.mem[0x8001] = 0x08;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x70; // BVS Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x08;
         test_cpu.cpu.overflow = false;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8002); // No branch
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x70; // BVS Relative
-        test_cpu.cpu.mem[0x8001] = 0x7f;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x70; // BVS Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x7f;
         test_cpu.cpu.overflow = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8081);
 
         test_cpu.cpu.pc = 0x9000;
-        test_cpu.cpu.mem[0x9000] = 0x70; // BVS Relative
-        test_cpu.cpu.mem[0x9001] = 0b1001_1100; // -100
+        test_cpu.cartridge.prg_rom_data[prg(0x9000)] = 0x70; // BVS Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0b1001_1100; // -100
         test_cpu.cpu.overflow = true;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8f9e);
@@ -232,7 +243,7 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.x = 5;
-        test_cpu.cpu.mem[0x8000] = 0xe8; // INX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe8; // INX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 6);
         assert!(!test_cpu.cpu.negative);
@@ -240,7 +251,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0xff;
-        test_cpu.cpu.mem[0x8000] = 0xe8; // INX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe8; // INX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0);
         assert!(test_cpu.cpu.zero);
@@ -248,7 +259,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0b1001_1100;
-        test_cpu.cpu.mem[0x8000] = 0xe8; // INX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe8; // INX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0b1001_1101);
         assert!(!test_cpu.cpu.zero);
@@ -259,7 +270,7 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.y = 6;
-        test_cpu.cpu.mem[0x8000] = 0xc8; // INY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc8; // INY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 7);
         assert!(!test_cpu.cpu.negative);
@@ -267,7 +278,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0xff;
-        test_cpu.cpu.mem[0x8000] = 0xc8; // INY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc8; // INY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0);
         assert!(!test_cpu.cpu.negative);
@@ -275,7 +286,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0b1011_1111;
-        test_cpu.cpu.mem[0x8000] = 0xc8; // INY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc8; // INY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0b1100_0000);
         assert!(test_cpu.cpu.negative);
@@ -286,18 +297,18 @@ mod instruction_tests {
     fn test_jmp() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x4c; // JMP Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x80;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x4c; // JMP Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x80;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8001);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0200] = 0x86;
         test_cpu.cpu.mem[0x0201] = 0x67;
-        test_cpu.cpu.mem[0x8000] = 0x6c; // JMP Indirect
-        test_cpu.cpu.mem[0x8001] = 0x00;
-        test_cpu.cpu.mem[0x8002] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x6c; // JMP Indirect
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x00;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x02;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x6786);
     }
@@ -305,17 +316,17 @@ mod instruction_tests {
     fn test_lda() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xa9; // LDY Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa9; // LDY Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x42);
         assert!(!test_cpu.cpu.negative);
         assert!(!test_cpu.cpu.zero);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xa5; // LDA Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa5; // LDA Zero Page
         test_cpu.cpu.mem[0x0001] = 0x00;
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x00);
         assert!(!test_cpu.cpu.negative);
@@ -323,44 +334,44 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0xb5; // LDA Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb5; // LDA Zero Page X
         test_cpu.cpu.mem[0x0003] = 0xff;
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0xff);
         assert!(test_cpu.cpu.negative);
         assert!(!test_cpu.cpu.zero);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xad; // LDA Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xad; // LDA Absolute
         test_cpu.cpu.mem[0x0101] = 0x45;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x45);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xbd; // LDA Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xbd; // LDA Absolute X
         test_cpu.cpu.mem[0x0105] = 0x46;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x46);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xb9; // LDA Absolute Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb9; // LDA Absolute Y
         test_cpu.cpu.mem[0x0105] = 0x47; //LDA address:0102; y:03
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x47);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xa1; // LDA Indirect X
-        test_cpu.cpu.mem[0x8001] = 0x01; // address
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa1; // LDA Indirect X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; // address
         test_cpu.cpu.mem[0x04] = 0x11; //address ll = address + x
         test_cpu.cpu.mem[0x05] = 0x07; //address hh = address + x + 1
         test_cpu.cpu.mem[0x0711] = 0x48; //x:0x03
@@ -369,8 +380,8 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x01;
-        test_cpu.cpu.mem[0x8000] = 0xa1; // LDA Indirect X Overflow
-        test_cpu.cpu.mem[0x8001] = 0xfe; // address
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa1; // LDA Indirect X Overflow
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xfe; // address
         test_cpu.cpu.mem[0xff] = 0x11; //address ll = address + x
         test_cpu.cpu.mem[0x00] = 0x06; //address hh = address + x + 1
         test_cpu.cpu.mem[0x0611] = 0x48;
@@ -379,8 +390,8 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xb1; // LDA Indirect Y
-        test_cpu.cpu.mem[0x8001] = 0x01; //address;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb1; // LDA Indirect Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; //address;
         test_cpu.cpu.mem[0x0001] = 0x11; //address ll;
         test_cpu.cpu.mem[0x0002] = 0x05; //address hh;
         test_cpu.cpu.mem[0x0514] = 0x49;
@@ -391,8 +402,8 @@ mod instruction_tests {
     fn test_ldx() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xa2; // LDX Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa2; // LDX Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x42);
         assert!(!test_cpu.cpu.negative);
@@ -400,8 +411,8 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0001] = 0xff; // -1
-        test_cpu.cpu.mem[0x8000] = 0xa6; // LDX Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa6; // LDX Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0xff);
         assert!(test_cpu.cpu.negative);
@@ -410,8 +421,8 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x2;
         test_cpu.cpu.mem[0x0003] = 0x00;
-        test_cpu.cpu.mem[0x8000] = 0xb6; // LDX Zero Page Y
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb6; // LDX Zero Page Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x00);
         assert!(!test_cpu.cpu.negative);
@@ -419,18 +430,18 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0101] = 0x45;
-        test_cpu.cpu.mem[0x8000] = 0xae; // LDX Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xae; // LDX Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x45);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xbe; // LDX Absolute Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xbe; // LDX Absolute Y
         test_cpu.cpu.mem[0x0105] = 0x46;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x46);
     }
@@ -438,8 +449,8 @@ mod instruction_tests {
     fn test_ldy() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xa0; // LDY Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa0; // LDY Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0x42);
         assert!(!test_cpu.cpu.negative);
@@ -447,8 +458,8 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0001] = 0xfe;
-        test_cpu.cpu.mem[0x8000] = 0xa4; // LDY Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa4; // LDY Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0xfe);
         assert!(test_cpu.cpu.negative);
@@ -457,8 +468,8 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x2;
         test_cpu.cpu.mem[0x0003] = 0x00;
-        test_cpu.cpu.mem[0x8000] = 0xb4; // LDY Zero Page X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb4; // LDY Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0x00);
         assert!(!test_cpu.cpu.negative);
@@ -466,18 +477,18 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0101] = 45;
-        test_cpu.cpu.mem[0x8000] = 0xac; // LDY Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xac; // LDY Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 45);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xbc; // LDY Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xbc; // LDY Absolute X
         test_cpu.cpu.mem[0x0105] = 0x46;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0x46);
     }
@@ -485,12 +496,12 @@ mod instruction_tests {
     fn test_rti_brk() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x00; // BRK Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x00; // BRK Implied
         test_cpu.cpu.zero = true; // Set a status flag
-        test_cpu.cpu.mem[test_cpu.mapper.get_mapper_address(0xfffe) as usize] = 0x66; // Set IRQ vector
-        test_cpu.cpu.mem[test_cpu.mapper.get_mapper_address(0xffff) as usize] = 0x07; // Set IRQ vector
+        test_cpu.cartridge.prg_rom_data[prg(0xfffe)] = 0x66; // Set IRQ vector
+        test_cpu.cartridge.prg_rom_data[prg(0xffff)] = 0x07; // Set IRQ vector
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.pc, test_cpu.mapper.get_mapper_address(0x0766),);
+        assert_eq!(test_cpu.cpu.pc, 0x0766);
         assert_eq!(test_cpu.cpu.mem[test_cpu.cpu.sp as usize + 3], 0x80);
         assert_eq!(test_cpu.cpu.mem[test_cpu.cpu.sp as usize + 2], 0x02);
         assert_eq!(test_cpu.cpu.mem[test_cpu.cpu.sp as usize + 1], 0x32); // NOTE b flag is alwasy set to 0b11
@@ -505,15 +516,15 @@ mod instruction_tests {
     fn test_rts_jsr() {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0x20; // JSR Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x90;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x20; // JSR Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x90;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x9001);
         assert_eq!(test_cpu.cpu.mem[(test_cpu.cpu.sp + 2) as usize], 0x80);
         assert_eq!(test_cpu.cpu.mem[(test_cpu.cpu.sp + 1) as usize], 0x02);
 
-        test_cpu.cpu.mem[0x9001] = 0x60; // RTS Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x9001)] = 0x60; // RTS Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8003);
     }
@@ -522,7 +533,7 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xea; // NOP Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xea; // NOP Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.pc, 0x8001);
     }
@@ -532,50 +543,50 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x43;
-        test_cpu.cpu.mem[0x8000] = 0x85; // STA Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x85; // STA Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0001], 0x43);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x02;
         test_cpu.cpu.a = 0x44;
-        test_cpu.cpu.mem[0x8000] = 0x95; // STA Zero Page X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x95; // STA Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0003], 0x44);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x45;
-        test_cpu.cpu.mem[0x8000] = 0x8d; // STA Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x8d; // STA Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0101], 0x45);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
         test_cpu.cpu.a = 0x46;
-        test_cpu.cpu.mem[0x8000] = 0x9d; // STA Absolute X
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x9d; // STA Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0105], 0x46);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
         test_cpu.cpu.a = 0x47;
-        test_cpu.cpu.mem[0x8000] = 0x99; // STA Absolute Y
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x99; // STA Absolute Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0105], 0x47);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
         test_cpu.cpu.a = 0x48;
-        test_cpu.cpu.mem[0x8000] = 0x81; // STA Indirect X
-        test_cpu.cpu.mem[0x8001] = 0x01; //addr of m
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x81; // STA Indirect X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; //addr of m
         test_cpu.cpu.mem[0x04] = 0x11; //address ll = address + x
         test_cpu.cpu.mem[0x05] = 0x06; //address hh = address + x + 1
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -584,8 +595,8 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x01;
         test_cpu.cpu.a = 0x50;
-        test_cpu.cpu.mem[0x8000] = 0x81; // STA Indirect X Overflow
-        test_cpu.cpu.mem[0x8001] = 0xfe; // address
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x81; // STA Indirect X Overflow
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xfe; // address
         test_cpu.cpu.mem[0xff] = 0x11; //address ll = address + x
         test_cpu.cpu.mem[0x00] = 0x01; //address hh = address + x + 1
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -594,8 +605,8 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
         test_cpu.cpu.a = 0x49;
-        test_cpu.cpu.mem[0x8000] = 0x91; // STA Indirect Y
-        test_cpu.cpu.mem[0x8001] = 0x01; //address;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x91; // STA Indirect Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; //address;
         test_cpu.cpu.mem[0x0001] = 0x11; //address ll;
         test_cpu.cpu.mem[0x0002] = 0x04; //address hh;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -607,24 +618,24 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x43;
-        test_cpu.cpu.mem[0x8000] = 0x86; // STX Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x86; // STX Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0001], 0x43);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x02;
         test_cpu.cpu.x = 0x44;
-        test_cpu.cpu.mem[0x8000] = 0x96; // STX Zero Page Y
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x96; // STX Zero Page Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0003], 0x44);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x45;
-        test_cpu.cpu.mem[0x8000] = 0x8e; // STX Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x8e; // STX Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0101], 0x45);
     }
@@ -634,24 +645,24 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x43;
-        test_cpu.cpu.mem[0x8000] = 0x84; // STY Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x84; // STY Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0001], 0x43);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x44;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0x94; // STY Zero Page X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x94; // STY Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0003], 0x44);
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x45;
-        test_cpu.cpu.mem[0x8000] = 0x8c; // STY Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x8c; // STY Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0101], 0x45);
     }
@@ -662,8 +673,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0001] = 0x42;
-        test_cpu.cpu.mem[0x8000] = 0xe6; // INC Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe6; // INC Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0001], 0x43);
         assert!(!test_cpu.cpu.zero);
@@ -672,8 +683,8 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0003] = 0xff;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0xf6; // INC Zero Page X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xf6; // INC Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0003], 0x00);
         assert!(test_cpu.cpu.zero);
@@ -681,9 +692,9 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0101] = 0xfe;
-        test_cpu.cpu.mem[0x8000] = 0xee; // INC Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xee; // INC Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0101], 0xff);
         assert!(!test_cpu.cpu.zero);
@@ -692,9 +703,9 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
         test_cpu.cpu.mem[0x0105] = 0x45;
-        test_cpu.cpu.mem[0x8000] = 0xfe; // INC Absolute X
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xfe; // INC Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0105], 0x46);
         assert!(!test_cpu.cpu.zero);
@@ -706,8 +717,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0001] = 0x44;
-        test_cpu.cpu.mem[0x8000] = 0xc6; // DEC Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc6; // DEC Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0001], 0x43);
         assert!(!test_cpu.cpu.zero);
@@ -716,8 +727,8 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0003] = 0x45;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0xd6; // DEC Zero Page X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd6; // DEC Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0003], 0x44);
         assert!(!test_cpu.cpu.zero);
@@ -725,9 +736,9 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.mem[0x0101] = 0x01;
-        test_cpu.cpu.mem[0x8000] = 0xce; // DEC Absolute
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xce; // DEC Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0101], 0x00);
         assert!(test_cpu.cpu.zero);
@@ -736,9 +747,9 @@ mod instruction_tests {
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
         test_cpu.cpu.mem[0x0105] = 0xff;
-        test_cpu.cpu.mem[0x8000] = 0xde; // DEC Absolute X
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xde; // DEC Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0105], 0xfe);
         assert!(!test_cpu.cpu.zero);
@@ -750,7 +761,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x44;
-        test_cpu.cpu.mem[0x8000] = 0xca; // DEX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xca; // DEX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x43);
         assert!(!test_cpu.cpu.zero);
@@ -758,7 +769,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x01;
-        test_cpu.cpu.mem[0x8000] = 0xca; // DEX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xca; // DEX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x00);
         assert!(test_cpu.cpu.zero);
@@ -766,7 +777,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0xfe;
-        test_cpu.cpu.mem[0x8000] = 0xca; // DEX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xca; // DEX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0xfd);
         assert!(!test_cpu.cpu.zero);
@@ -778,7 +789,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x44;
-        test_cpu.cpu.mem[0x8000] = 0x88; // DEY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x88; // DEY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0x43);
         assert!(!test_cpu.cpu.zero);
@@ -786,7 +797,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x01;
-        test_cpu.cpu.mem[0x8000] = 0x88; // DEY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x88; // DEY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0x00);
         assert!(test_cpu.cpu.zero);
@@ -794,7 +805,7 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0xf1;
-        test_cpu.cpu.mem[0x8000] = 0x88; // DEY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x88; // DEY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0xf0);
         assert!(!test_cpu.cpu.zero);
@@ -806,7 +817,7 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x38; // SEC Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x38; // SEC Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.carry, true);
     }
@@ -815,7 +826,7 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xf8; // SED Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xf8; // SED Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.dec, true);
     }
@@ -824,7 +835,7 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x78; // SEI Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x78; // SEI Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.irq_dis, true);
     }
@@ -834,7 +845,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0x18; // CLC Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x18; // CLC Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.carry, false);
     }
@@ -844,7 +855,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.dec = true;
-        test_cpu.cpu.mem[0x8000] = 0xd8; // CLD Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd8; // CLD Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.dec, false);
     }
@@ -854,7 +865,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.irq_dis = true;
-        test_cpu.cpu.mem[0x8000] = 0x58; // CLI Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x58; // CLI Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.irq_dis, false);
     }
@@ -864,7 +875,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.overflow = true;
-        test_cpu.cpu.mem[0x8000] = 0xb8; // CLV Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb8; // CLV Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.overflow, false);
     }
@@ -875,8 +886,8 @@ mod instruction_tests {
         //a > mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xc9; // CMP Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc9; // CMP Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.a = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -885,8 +896,8 @@ mod instruction_tests {
         //a = mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xc9; // CMP Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc9; // CMP Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.a = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -895,8 +906,8 @@ mod instruction_tests {
         //a < mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xc9; // CMP Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc9; // CMP Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.a = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -908,8 +919,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xc5; // CMP Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc5; // CMP Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.a = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -920,8 +931,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xc5; // CMP Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc5; // CMP Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.a = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -932,8 +943,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xc5; // CMP Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc5; // CMP Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.a = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -947,9 +958,9 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0xd5; // CMP Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd5; // CMP Zero Page X
         test_cpu.cpu.mem[0x0003] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.a = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -960,9 +971,9 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0xd5; // CMP Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd5; // CMP Zero Page X
         test_cpu.cpu.mem[0x0003] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.a = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -973,9 +984,9 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x02;
-        test_cpu.cpu.mem[0x8000] = 0xd5; // CMP Zero Page X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd5; // CMP Zero Page X
         test_cpu.cpu.mem[0x0003] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.a = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -987,10 +998,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xcd; // CMP Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xcd; // CMP Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1000,10 +1011,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xcd; // CMP Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xcd; // CMP Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1013,10 +1024,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xcd; // CMP Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xcd; // CMP Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1029,10 +1040,10 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xdd; // CMP Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xdd; // CMP Absolute X
         test_cpu.cpu.mem[0x0105] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1043,10 +1054,10 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xdd; // CMP Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xdd; // CMP Absolute X
         test_cpu.cpu.mem[0x0105] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1057,10 +1068,10 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xdd; // CMP Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xdd; // CMP Absolute X
         test_cpu.cpu.mem[0x0105] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1073,10 +1084,10 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xd9; // CMP Absolute Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd9; // CMP Absolute Y
         test_cpu.cpu.mem[0x0105] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1087,10 +1098,10 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xd9; // CMP Absolute Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd9; // CMP Absolute Y
         test_cpu.cpu.mem[0x0105] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1101,10 +1112,10 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xd9; // CMP Absolute Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd9; // CMP Absolute Y
         test_cpu.cpu.mem[0x0105] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x02;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x02;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.a = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1116,8 +1127,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x03;
-        test_cpu.cpu.mem[0x8000] = 0xc1; // CMP Indirect X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc1; // CMP Indirect X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x04] = 0x11; //address ll = address + x
         test_cpu.cpu.mem[0x05] = 0x05; //address hh = address + x + 1
         test_cpu.cpu.mem[0x0511] = 0x43; // value of m
@@ -1131,8 +1142,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x03;
-        test_cpu.cpu.mem[0x8000] = 0xc1; // CMP Indirect X
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc1; // CMP Indirect X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x04] = 0x11; //address ll = address + x
         test_cpu.cpu.mem[0x05] = 0x04; //address hh = address + x + 1
         test_cpu.cpu.mem[0x0411] = 0x44; // value of m
@@ -1148,8 +1159,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xd1; // CMP Indirect Y
-        test_cpu.cpu.mem[0x8001] = 0x01; //address;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd1; // CMP Indirect Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; //address;
         test_cpu.cpu.mem[0x0001] = 0x11; //address ll;
         test_cpu.cpu.mem[0x0002] = 0x02; //address hh;
         test_cpu.cpu.mem[0x0214] = 0x42;
@@ -1163,8 +1174,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xd1; // CMP Indirect Y
-        test_cpu.cpu.mem[0x8001] = 0x01; //address;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd1; // CMP Indirect Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; //address;
         test_cpu.cpu.mem[0x0001] = 0x11; //address ll;
         test_cpu.cpu.mem[0x0002] = 0x03; //address hh;
         test_cpu.cpu.mem[0x0314] = 0x42;
@@ -1178,8 +1189,8 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x3;
-        test_cpu.cpu.mem[0x8000] = 0xd1; // CMP Indirect Y
-        test_cpu.cpu.mem[0x8001] = 0x01; //address;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd1; // CMP Indirect Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01; //address;
         test_cpu.cpu.mem[0x0001] = 0x11; //address ll;
         test_cpu.cpu.mem[0x0002] = 0x04; //address hh;
         test_cpu.cpu.mem[0x0414] = 0x42;
@@ -1195,8 +1206,8 @@ mod instruction_tests {
         //a > mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xe0; // CPX Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe0; // CPX Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.x = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1205,8 +1216,8 @@ mod instruction_tests {
         //a = mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xe0; // CPX Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe0; // CPX Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.x = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1215,8 +1226,8 @@ mod instruction_tests {
         //a < mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xe0; // CPX Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe0; // CPX Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.x = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1228,8 +1239,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xe4; // CPX Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe4; // CPX Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.x = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1240,8 +1251,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xe4; // CPX Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe4; // CPX Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.x = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1252,8 +1263,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xe4; // CPX Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xe4; // CPX Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.x = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1266,10 +1277,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xec; // CPX Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xec; // CPX Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.x = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1279,10 +1290,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xec; // CPX Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xec; // CPX Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.x = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1292,10 +1303,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xec; // CPX Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xec; // CPX Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.x = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1308,8 +1319,8 @@ mod instruction_tests {
         //a > mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xc0; // CPY Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc0; // CPY Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.y = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1318,8 +1329,8 @@ mod instruction_tests {
         //a = mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xc0; // CPY Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc0; // CPY Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.y = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1328,8 +1339,8 @@ mod instruction_tests {
         //a < mem
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
-        test_cpu.cpu.mem[0x8000] = 0xc0; // CPY Immediate
-        test_cpu.cpu.mem[0x8001] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc0; // CPY Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x42;
         test_cpu.cpu.y = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1341,8 +1352,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xc4; // CPY Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc4; // CPY Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.y = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1353,8 +1364,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xc4; // CPY Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc4; // CPY Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.y = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1365,8 +1376,8 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xc4; // CPY Zero Page
-        test_cpu.cpu.mem[0x8001] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xc4; // CPY Zero Page
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
         test_cpu.cpu.mem[0x0001] = 0x42;
         test_cpu.cpu.y = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1379,10 +1390,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xcc; // CPY Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xcc; // CPY Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.y = 0x43;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1392,10 +1403,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xcc; // CPY Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xcc; // CPY Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.y = 0x42;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1405,10 +1416,10 @@ mod instruction_tests {
         let mut test_cpu = Bus::default();
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0xcc; // CPY Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xcc; // CPY Absolute
         test_cpu.cpu.mem[0x0101] = 0x42;
-        test_cpu.cpu.mem[0x8001] = 0x01;
-        test_cpu.cpu.mem[0x8002] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x01;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.y = 0x41;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, false);
@@ -1423,7 +1434,7 @@ mod instruction_tests {
         test_cpu.cpu.sp = 0x01dd;
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x44;
-        test_cpu.cpu.mem[0x8000] = 0x48; // PHA Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x48; // PHA Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(
             test_cpu.cpu.mem[(test_cpu.cpu.sp + 1 as u16) as usize],
@@ -1443,7 +1454,7 @@ mod instruction_tests {
         test_cpu.cpu.b = true;
         test_cpu.cpu.overflow = true;
         test_cpu.cpu.negative = true;
-        test_cpu.cpu.mem[0x8000] = 0x08; // PHP Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x08; // PHP Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(
             test_cpu.cpu.mem[(test_cpu.cpu.sp + 1 as u16) as usize],
@@ -1457,7 +1468,7 @@ mod instruction_tests {
         test_cpu.cpu.sp = 0x01dd;
         test_cpu.cpu.mem[(test_cpu.cpu.sp + 1 as u16) as usize] = 0x44;
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x68; // PLA Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x68; // PLA Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x44);
     }
@@ -1468,7 +1479,7 @@ mod instruction_tests {
         test_cpu.cpu.sp = 0x01dd;
         test_cpu.cpu.mem[(test_cpu.cpu.sp + 1 as u16) as usize] = 0xFF;
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x28; // PLP Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x28; // PLP Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.carry, true);
         assert_eq!(test_cpu.cpu.zero, true);
@@ -1487,7 +1498,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x80;
-        test_cpu.cpu.mem[0x8000] = 0xaa; // TAX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xaa; // TAX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x80); //test transfer
         assert_eq!(test_cpu.cpu.negative, true); //test negative flag
@@ -1496,7 +1507,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x00;
-        test_cpu.cpu.mem[0x8000] = 0xaa; // TAX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xaa; // TAX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true); //test zero flag
     }
@@ -1506,7 +1517,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x80;
-        test_cpu.cpu.mem[0x8000] = 0xa8; // TAY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa8; // TAY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.y, 0x80); //test transfer
         assert_eq!(test_cpu.cpu.negative, true); //test negative flag
@@ -1515,7 +1526,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.a = 0x00;
-        test_cpu.cpu.mem[0x8000] = 0xa8; // TAY Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xa8; // TAY Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true); //test zero flag
     }
@@ -1525,7 +1536,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.sp = 0x180;
-        test_cpu.cpu.mem[0x8000] = 0xba; // TSX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xba; // TSX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x80); //test transfer
         assert_eq!(test_cpu.cpu.negative, true); //test negative flag
@@ -1535,7 +1546,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.sp = 0x100;
-        test_cpu.cpu.mem[0x8000] = 0xba; // TSX Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xba; // TSX Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.x, 0x00); //test transfer
         assert_eq!(test_cpu.cpu.negative, false); //test negative flag
@@ -1547,7 +1558,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x80;
-        test_cpu.cpu.mem[0x8000] = 0x8a; // TXA Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x8a; // TXA Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x80); //test transfer
         assert_eq!(test_cpu.cpu.negative, true); //test negative flag
@@ -1556,7 +1567,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x00;
-        test_cpu.cpu.mem[0x8000] = 0x8a; // TXA Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x8a; // TXA Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true); //test zero flag
     }
@@ -1566,7 +1577,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.x = 0x80;
-        test_cpu.cpu.mem[0x8000] = 0x9a; // TXS Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x9a; // TXS Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.sp, 0x180);
     }
@@ -1576,7 +1587,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x80;
-        test_cpu.cpu.mem[0x8000] = 0x98; // TYA Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x98; // TYA Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x80); //test transfer
         assert_eq!(test_cpu.cpu.negative, true); //test negative flag
@@ -1585,7 +1596,7 @@ mod instruction_tests {
         let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.y = 0x00;
-        test_cpu.cpu.mem[0x8000] = 0x98; // TYA Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x98; // TYA Implied
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.zero, true); //test zero flag
     }
@@ -1596,8 +1607,8 @@ mod instruction_tests {
 
         // signed:   5 + 5 = 10 | V -> 0
         // unsigned: 5 + 5 = 10 | C -> 0
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 0x05;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x05;
         test_cpu.cpu.carry = false;
         test_cpu.cpu.a = 0x05;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1611,8 +1622,8 @@ mod instruction_tests {
         // unsigned: 244 + 14 = (1)_0000_0010 | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100; // -12
         test_cpu.cpu.a = 14;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 2);
@@ -1625,8 +1636,8 @@ mod instruction_tests {
         // unsigned: 244 + 12 = (1)_0000_0000 | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100; // -12
         test_cpu.cpu.a = 12;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -1639,8 +1650,8 @@ mod instruction_tests {
         // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100; // -12
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1110_1000);
@@ -1653,8 +1664,8 @@ mod instruction_tests {
         // unsigned: 100 + 50 = 150   | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 50;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 50;
         test_cpu.cpu.a = 100;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 150);
@@ -1667,8 +1678,8 @@ mod instruction_tests {
         // unsigned: 255 + 255 = (1)_1111_1110  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111; // -1
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111; // -1
         test_cpu.cpu.a = 0b1111_1111; // -1
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_1110);
@@ -1681,8 +1692,8 @@ mod instruction_tests {
         // unsigned: 0 + 0 = 0  | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x69; // ADC Immediate
-        test_cpu.cpu.mem[0x8001] = 0; // -1
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x69; // ADC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0; // -1
         test_cpu.cpu.a = 0; // -1
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -1697,9 +1708,9 @@ mod instruction_tests {
         // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x65; // ADC Zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x65; // ADC Zeropage
         test_cpu.cpu.mem[0x0091] = 0b1111_0100; // -12
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1110_1000);
@@ -1712,9 +1723,9 @@ mod instruction_tests {
         // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x75; // ADC ZeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x75; // ADC ZeropageX
         test_cpu.cpu.mem[0x0096] = 0b1111_0100; // -12
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.x = 5;
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1728,10 +1739,10 @@ mod instruction_tests {
         // // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x6D; // ADC Absolute
-        test_cpu.cpu.mem[0x9096] = 0b1111_0100; // -12
-        test_cpu.cpu.mem[0x8001] = 0x96; // LL
-        test_cpu.cpu.mem[0x8002] = 0x90; // HH
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x6D; // ADC Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x9096)] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x96; // LL
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x90; // HH
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1110_1000);
@@ -1744,10 +1755,10 @@ mod instruction_tests {
         // // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x7D; // ADC AbsoluteX
-        test_cpu.cpu.mem[0x5093] = 0b1111_0100; // -12
-        test_cpu.cpu.mem[0x8001] = 0x91; // LL
-        test_cpu.cpu.mem[0x8002] = 0x50; // HH
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x7D; // ADC AbsoluteX
+        test_cpu.cpu.mem[0x0093] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // LL
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x00; // HH
         test_cpu.cpu.x = 2;
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1761,10 +1772,10 @@ mod instruction_tests {
         // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x79; // ADC AbsoluteY
-        test_cpu.cpu.mem[0x5151] = 0b1111_0100; // -12
-        test_cpu.cpu.mem[0x8001] = 0x41;
-        test_cpu.cpu.mem[0x8002] = 0x51;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x79; // ADC AbsoluteY
+        test_cpu.cpu.mem[0x0151] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x41;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
         test_cpu.cpu.y = 0x10;
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1778,14 +1789,14 @@ mod instruction_tests {
         // unsigned: 5 + 12 + C = 18  | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0x61; // ADC IndirectX
-        test_cpu.cpu.mem[0x8001] = 0x04;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x61; // ADC IndirectX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x04;
         test_cpu.cpu.x = 0x03;
 
         test_cpu.cpu.mem[0x0007] = 0x13; // Address LL of m
-        test_cpu.cpu.mem[0x0008] = 0x51; // Address HH of m
+        test_cpu.cpu.mem[0x0008] = 0x01; // Address HH of m
 
-        test_cpu.cpu.mem[0x5113] = 0x12; // m
+        test_cpu.cpu.mem[0x0113] = 0x12; // m
         test_cpu.cpu.a = 5;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0x18);
@@ -1798,12 +1809,12 @@ mod instruction_tests {
         // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x71; // ADC IndirectY
-        test_cpu.cpu.mem[0x8001] = 0x33;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x71; // ADC IndirectY
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x33;
         test_cpu.cpu.mem[0x0033] = 0x73; // Address LL of m
-        test_cpu.cpu.mem[0x0034] = 0x51; // Address HH of m
+        test_cpu.cpu.mem[0x0034] = 0x01; // Address HH of m
         test_cpu.cpu.y = 0x4;
-        test_cpu.cpu.mem[0x5177] = 0b1111_0100; // m = -12
+        test_cpu.cpu.mem[0x0177] = 0b1111_0100; // m = -12
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1110_1000);
@@ -1820,8 +1831,8 @@ mod instruction_tests {
 
         // signed:   5 - 5 = 0  | V -> 0
         // unsigned: 5 - 5 = 0 | C -> 0
-        test_cpu.cpu.mem[0x8000] = 0xE9; // SBC Immediate
-        test_cpu.cpu.mem[0x8001] = 0x05;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE9; // SBC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x05;
         test_cpu.cpu.carry = true;
         test_cpu.cpu.a = 0x05;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1835,8 +1846,8 @@ mod instruction_tests {
         // unsigned: 244 - 14 - C = 229  | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xE9; // SBC Immediate
-        test_cpu.cpu.mem[0x8001] = 14;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE9; // SBC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 14;
         test_cpu.cpu.a = 0b1111_0100; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1110_0101); // -27 -> 0b1110_0110
@@ -1849,8 +1860,8 @@ mod instruction_tests {
         // unsigned: 12 - 12 - 1 = -1  | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xE9; // SBC Immediate
-        test_cpu.cpu.mem[0x8001] = 12;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE9; // SBC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 12;
         test_cpu.cpu.a = 12;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 255);
@@ -1863,8 +1874,8 @@ mod instruction_tests {
         // unsigned: 4 - 244 -> 260 - 244 = 16  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xE9; // SBC Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100; // -12
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE9; // SBC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100; // -12
         test_cpu.cpu.a = 4;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 15);
@@ -1877,8 +1888,8 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xE9; // SBC Immediate
-        test_cpu.cpu.mem[0x8001] = 100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE9; // SBC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 100;
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 55);
@@ -1891,8 +1902,8 @@ mod instruction_tests {
         // unsigned: 0 - 0 = 0  | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0xE9; // SBC Immediate
-        test_cpu.cpu.mem[0x8001] = 0; // -1
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE9; // SBC Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0; // -1
         test_cpu.cpu.a = 0; // -1
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -1907,9 +1918,9 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xE5; // SBC Zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE5; // SBC Zeropage
         test_cpu.cpu.mem[0x0091] = 100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 55);
@@ -1922,9 +1933,9 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xF5; // SBC ZeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xF5; // SBC ZeropageX
         test_cpu.cpu.mem[0x0096] = 100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.x = 5;
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1938,10 +1949,10 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xED; // SBC Absolute
-        test_cpu.cpu.mem[0x8896] = 100;
-        test_cpu.cpu.mem[0x8001] = 0x96;
-        test_cpu.cpu.mem[0x8002] = 0x88;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xED; // SBC Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8896)] = 100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x96;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x88;
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 55);
@@ -1954,10 +1965,10 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xFD; // SBC AbsoluteX
-        test_cpu.cpu.mem[0x8E93] = 100;
-        test_cpu.cpu.mem[0x8001] = 0x91;
-        test_cpu.cpu.mem[0x8002] = 0x8E;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xFD; // SBC AbsoluteX
+        test_cpu.cartridge.prg_rom_data[prg(0x8E93)] = 100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x8E;
         test_cpu.cpu.x = 2;
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1971,10 +1982,10 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0xF9; // SBC AbsoluteY
-        test_cpu.cpu.mem[0xA051] = 100;
-        test_cpu.cpu.mem[0x8001] = 0x41;
-        test_cpu.cpu.mem[0x8002] = 0xA0;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xF9; // SBC AbsoluteY
+        test_cpu.cartridge.prg_rom_data[prg(0xA051)] = 100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x41;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0xA0;
         test_cpu.cpu.y = 0x10;
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -1988,14 +1999,14 @@ mod instruction_tests {
         // unsigned: 156 - 100 = 56                         | C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0xE1; // SBC IndirectX
-        test_cpu.cpu.mem[0x8001] = 0x04;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xE1; // SBC IndirectX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x04;
         test_cpu.cpu.x = 0x03;
 
         test_cpu.cpu.mem[0x0007] = 0x13; // Address LL of m
-        test_cpu.cpu.mem[0x0008] = 0x51; // Address HH of m
+        test_cpu.cpu.mem[0x0008] = 0x01; // Address HH of m
 
-        test_cpu.cpu.mem[0x5113] = 100; // m
+        test_cpu.cpu.mem[0x0113] = 100; // m
         test_cpu.cpu.a = 0b1001_1100;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 56);
@@ -2008,13 +2019,13 @@ mod instruction_tests {
         // unsigned: 244 + 244 = (1)_1110_1000  | C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0xF1; // SBC IndirectY
-        test_cpu.cpu.mem[0x8001] = 0x33;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xF1; // SBC IndirectY
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x33;
         test_cpu.cpu.mem[0x0033] = 0xF3; // Address LL of m
-        test_cpu.cpu.mem[0x0034] = 0x51; // Address HH of m
+        test_cpu.cpu.mem[0x0034] = 0x01; // Address HH of m
 
         test_cpu.cpu.y = 0x14;
-        test_cpu.cpu.mem[0x5207] = 100; // m = 100
+        test_cpu.cpu.mem[0x0207] = 100; // m = 100
         test_cpu.cpu.a = 0b1001_1100; // -100
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 56);
@@ -2034,8 +2045,8 @@ mod instruction_tests {
         // = 0b0011_0000 -> N -> 0, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x29; // AND Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x29; // AND Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100;
         test_cpu.cpu.a = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0011_0000);
@@ -2047,8 +2058,8 @@ mod instruction_tests {
         // = 0b0000_0000 -> N -> 0, Z -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x29; // AND Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x29; // AND Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111;
         test_cpu.cpu.a = 0;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -2060,8 +2071,8 @@ mod instruction_tests {
         // = 0b1111_1111 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x29; // AND Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x29; // AND Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111;
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_1111);
@@ -2073,8 +2084,8 @@ mod instruction_tests {
         // = 0b1000_0011 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x29; // AND Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1100_1011;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x29; // AND Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1100_1011;
         test_cpu.cpu.a = 0b1001_0111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1000_0011);
@@ -2085,9 +2096,9 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x25; // AND Zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x25; // AND Zeropage
         test_cpu.cpu.mem[0x0091] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1001_0100);
@@ -2096,9 +2107,9 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x35; // AND ZeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x35; // AND ZeropageX
         test_cpu.cpu.mem[0x0096] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.x = 5;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2110,10 +2121,10 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x2D; // AND Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x2D; // AND Absolute
         test_cpu.cpu.mem[0x0096] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x96;
-        test_cpu.cpu.mem[0x8002] = 0x00;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x96;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x00;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1001_0100);
@@ -2122,10 +2133,10 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x3D; // AND AbsoluteX
-        test_cpu.cpu.mem[0x6693] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91;
-        test_cpu.cpu.mem[0x8002] = 0x66;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x3D; // AND AbsoluteX
+        test_cpu.cartridge.prg_ram[0x6693 - 0x6000] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x66;
         test_cpu.cpu.x = 2;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2135,10 +2146,10 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x39; // AND AbsoluteY
-        test_cpu.cpu.mem[0x4251] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x41;
-        test_cpu.cpu.mem[0x8002] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x39; // AND AbsoluteY
+        test_cpu.cpu.mem[0x0251] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x41;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x02;
         test_cpu.cpu.y = 0x10;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2147,14 +2158,14 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x21; // AND IndirectX
-        test_cpu.cpu.mem[0x8001] = 0x04;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x21; // AND IndirectX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x04;
         test_cpu.cpu.x = 0x03;
 
         test_cpu.cpu.mem[0x0007] = 0x13; // Address LL of m
-        test_cpu.cpu.mem[0x0008] = 0x53; // Address HH of m
+        test_cpu.cpu.mem[0x0008] = 0x03; // Address HH of m
 
-        test_cpu.cpu.mem[0x5313] = 0b1111_0100; // m
+        test_cpu.cpu.mem[0x0313] = 0b1111_0100; // m
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1001_0100);
@@ -2163,12 +2174,12 @@ mod instruction_tests {
 
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x31; // AND IndirectY
-        test_cpu.cpu.mem[0x8001] = 0x33;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x31; // AND IndirectY
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x33;
         test_cpu.cpu.mem[0x0033] = 0x73; // LL of m
         test_cpu.cpu.mem[0x0034] = 0x88; // HH of m
         test_cpu.cpu.y = 0x4;
-        test_cpu.cpu.mem[0x8877] = 0b1111_0100; // m
+        test_cpu.cartridge.prg_rom_data[prg(0x8877)] = 0b1111_0100; // m
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1001_0100);
@@ -2186,8 +2197,8 @@ mod instruction_tests {
         // = 0b1100_1110 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x49; // EOR Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x49; // EOR Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100;
         test_cpu.cpu.a = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1100_1110);
@@ -2199,8 +2210,8 @@ mod instruction_tests {
         // = 0b1111_1111 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x49; // EOR Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x49; // EOR Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111;
         test_cpu.cpu.a = 0;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 255);
@@ -2212,8 +2223,8 @@ mod instruction_tests {
         // = 0b0000_0000 -> N -> 0, Z -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x49; // EOR Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x49; // EOR Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111;
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -2225,8 +2236,8 @@ mod instruction_tests {
         // = 0b0101_1100 -> N -> 0, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x49; // EOR Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1100_1011;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x49; // EOR Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1100_1011;
         test_cpu.cpu.a = 0b1001_0111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0101_1100);
@@ -2236,9 +2247,9 @@ mod instruction_tests {
         /* Other addressing modes */
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x45; // EOR Zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x45; // EOR Zeropage
         test_cpu.cpu.mem[0x0091] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0110_0010);
@@ -2246,9 +2257,9 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x55; // EOR ZeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x55; // EOR ZeropageX
         test_cpu.cpu.mem[0x0096] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.x = 5;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2257,10 +2268,10 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x4D; // EOR Absolute
-        test_cpu.cpu.mem[0x4296] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x96;
-        test_cpu.cpu.mem[0x8002] = 0x42;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x4D; // EOR Absolute
+        test_cpu.cpu.mem[0x0296] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x96;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x02;
         test_cpu.cpu.a = 0b1001_0110; // -12
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0110_0010);
@@ -2268,10 +2279,10 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x5D; // EOR AbsoluteX
-        test_cpu.cpu.mem[0x4393] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91;
-        test_cpu.cpu.mem[0x8002] = 0x43;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x5D; // EOR AbsoluteX
+        test_cpu.cpu.mem[0x0393] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x03;
         test_cpu.cpu.x = 2;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2280,10 +2291,10 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x59; // EOR AbsoluteY
-        test_cpu.cpu.mem[0x4451] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x41;
-        test_cpu.cpu.mem[0x8002] = 0x44;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x59; // EOR AbsoluteY
+        test_cpu.cpu.mem[0x0451] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x41;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x04;
         test_cpu.cpu.y = 0x10;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2292,14 +2303,14 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x41; // EOR IndirectX
-        test_cpu.cpu.mem[0x8001] = 0xF4;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x41; // EOR IndirectX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xF4;
         test_cpu.cpu.x = 0x13;
 
         test_cpu.cpu.mem[0x0007] = 0x12; // Address LL of m -> wrap around!!
-        test_cpu.cpu.mem[0x0008] = 0x52; // Address HH of m
+        test_cpu.cpu.mem[0x0008] = 0x02; // Address HH of m
 
-        test_cpu.cpu.mem[0x5212] = 0b1111_0100; // m
+        test_cpu.cpu.mem[0x0212] = 0b1111_0100; // m
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0110_0010);
@@ -2307,12 +2318,12 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x51; // EOR IndirectY
-        test_cpu.cpu.mem[0x8001] = 0x33;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x51; // EOR IndirectY
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x33;
         test_cpu.cpu.mem[0x0033] = 0x73; // LL
-        test_cpu.cpu.mem[0x0034] = 0x53; // HH
+        test_cpu.cpu.mem[0x0034] = 0x03; // HH
         test_cpu.cpu.y = 0x4;
-        test_cpu.cpu.mem[0x5377] = 0b1111_0100; // m
+        test_cpu.cpu.mem[0x0377] = 0b1111_0100; // m
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0110_0010);
@@ -2330,8 +2341,8 @@ mod instruction_tests {
         // = 0b1111_1110 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x09; // ORA Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x09; // ORA Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_0100;
         test_cpu.cpu.a = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_1110);
@@ -2343,8 +2354,8 @@ mod instruction_tests {
         // = 0b1111_1111 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x09; // ORA Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x09; // ORA Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111;
         test_cpu.cpu.a = 0;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 255);
@@ -2356,8 +2367,8 @@ mod instruction_tests {
         // = 0b1111_1111 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x09; // ORA Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1111_1111;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x09; // ORA Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1111_1111;
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 255);
@@ -2369,8 +2380,8 @@ mod instruction_tests {
         // = 0b1101_1111 -> N -> 1, Z -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x09; // ORA Immediate
-        test_cpu.cpu.mem[0x8001] = 0b1100_1011;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x09; // ORA Immediate
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0b1100_1011;
         test_cpu.cpu.a = 0b1001_0111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1101_1111);
@@ -2380,9 +2391,9 @@ mod instruction_tests {
         /* Other addressing modes */
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x05; // ORA Zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x05; // ORA Zeropage
         test_cpu.cpu.mem[0x0091] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_0110);
@@ -2390,9 +2401,9 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x15; // ORA ZeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x15; // ORA ZeropageX
         test_cpu.cpu.mem[0x0096] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91; // Address on zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91; // Address on zeropage
         test_cpu.cpu.x = 5;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2401,10 +2412,10 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x0D; // ORA Absolute
-        test_cpu.cpu.mem[0x6496] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x96;
-        test_cpu.cpu.mem[0x8002] = 0x64;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x0D; // ORA Absolute
+        test_cpu.cartridge.prg_ram[0x6496 - 0x6000] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x96;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x64;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_0110);
@@ -2412,10 +2423,10 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x1D; // ORA AbsoluteX
-        test_cpu.cpu.mem[0x6593] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x91;
-        test_cpu.cpu.mem[0x8002] = 0x65;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x1D; // ORA AbsoluteX
+        test_cpu.cartridge.prg_ram[0x6593 - 0x6000] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x91;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x65;
         test_cpu.cpu.x = 2;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2424,10 +2435,10 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x19; // ORA AbsoluteY
-        test_cpu.cpu.mem[0x4451] = 0b1111_0100;
-        test_cpu.cpu.mem[0x8001] = 0x41;
-        test_cpu.cpu.mem[0x8002] = 0x44;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x19; // ORA AbsoluteY
+        test_cpu.cpu.mem[0x0451] = 0b1111_0100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x41;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x04;
         test_cpu.cpu.y = 0x10;
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2436,14 +2447,14 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x01; // ORA IndirectX
-        test_cpu.cpu.mem[0x8001] = 0x04;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x01; // ORA IndirectX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x04;
         test_cpu.cpu.x = 0x03;
 
         test_cpu.cpu.mem[0x0007] = 0x13; // Address of m
         test_cpu.cpu.mem[0x0008] = 0x99; // Address of m
 
-        test_cpu.cpu.mem[0x9913] = 0b1111_0100; // m
+        test_cpu.cartridge.prg_rom_data[prg(0x9913)] = 0b1111_0100; // m
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_0110);
@@ -2451,12 +2462,12 @@ mod instruction_tests {
         assert_eq!(test_cpu.cpu.zero, false);
 
         test_cpu.cpu.pc = 0x8000;
-        test_cpu.cpu.mem[0x8000] = 0x11; // ORA IndirectY
-        test_cpu.cpu.mem[0x8001] = 0x33;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x11; // ORA IndirectY
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x33;
         test_cpu.cpu.mem[0x0033] = 0x73;
         test_cpu.cpu.mem[0x0034] = 0x77;
         test_cpu.cpu.y = 0x4;
-        test_cpu.cpu.mem[0x7777] = 0b1111_0100; // m
+        test_cpu.cartridge.prg_ram[0x7777 - 0x6000] = 0b1111_0100; // m
         test_cpu.cpu.a = 0b1001_0110;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_0110);
@@ -2473,7 +2484,7 @@ mod instruction_tests {
         // = 0b01110100 -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x0A; // ASL accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x0A; // ASL accumulator
         test_cpu.cpu.a = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0111_0100);
@@ -2485,7 +2496,7 @@ mod instruction_tests {
         // = 0b(1)11111110 -> N -> 1, Z -> 0, C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x0A; // ASL Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x0A; // ASL Accumulator
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_1110);
@@ -2497,7 +2508,7 @@ mod instruction_tests {
         // = 0b0000_0000 -> N -> 0, Z -> 1, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x0A; // ALS Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x0A; // ALS Accumulator
         test_cpu.cpu.a = 0b0000_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -2511,8 +2522,8 @@ mod instruction_tests {
         // = 0b1111_010(0) -> N -> 1, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x06; // ASL zeropage
-        test_cpu.cpu.mem[0x8001] = 0x0076;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x06; // ASL zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0076;
         test_cpu.cpu.mem[0x0076] = 0b0111_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0076], 0b1111_0100);
@@ -2524,8 +2535,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x16; // ASL zeropageX
-        test_cpu.cpu.mem[0x8001] = 0x0066;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x16; // ASL zeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0066;
         test_cpu.cpu.x = 3;
         test_cpu.cpu.mem[0x0069] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2538,30 +2549,30 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x0E; // ASL absolute
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x54;
-        test_cpu.cpu.mem[0x5460] = 0b0011_1010;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x0E; // ASL absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x04;
+        test_cpu.cpu.mem[0x0460] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x5460], 0b0111_0100);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cpu.mem[0x0460], 0b0111_0100);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
 
         //  0b00111010
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x1E; // ASL absoluteX
-        test_cpu.cpu.mem[0x8001] = 0x0060;
-        test_cpu.cpu.mem[0x8002] = 0x54;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x1E; // ASL absoluteX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0060;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x04;
         test_cpu.cpu.x = 3;
-        test_cpu.cpu.mem[0x5463] = 0b0011_1010;
+        test_cpu.cpu.mem[0x0463] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x5463], 0b0111_0100);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cpu.mem[0x0463], 0b0111_0100);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
     }
     #[test]
     fn test_lsr() {
@@ -2572,7 +2583,7 @@ mod instruction_tests {
         // = 0b00011101 -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x4A; // LSR accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x4A; // LSR accumulator
         test_cpu.cpu.a = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b000_11101);
@@ -2584,7 +2595,7 @@ mod instruction_tests {
         // = 0b01111111(1) -> N -> 0, Z -> 0, C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x4A; // LSR Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x4A; // LSR Accumulator
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0111_1111);
@@ -2596,7 +2607,7 @@ mod instruction_tests {
         // = 0b0000_0000 -> N -> 0, Z -> 1, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x4A; // LSR Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x4A; // LSR Accumulator
         test_cpu.cpu.a = 0b0000_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -2610,8 +2621,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x46; // LSR zeropage
-        test_cpu.cpu.mem[0x8001] = 0x0076;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x46; // LSR zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0076;
         test_cpu.cpu.mem[0x0076] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0076], 0b0001_1101);
@@ -2623,8 +2634,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x56; // LSR zeropageX
-        test_cpu.cpu.mem[0x8001] = 0x0066;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x56; // LSR zeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0066;
         test_cpu.cpu.x = 3;
         test_cpu.cpu.mem[0x0069] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2637,30 +2648,30 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x4E; // LSR absolute
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x60;
-        test_cpu.cpu.mem[0x6060] = 0b0011_1010;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x4E; // LSR absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x60;
+        test_cpu.cartridge.prg_ram[0x6060 - 0x6000] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x6060], 0b0001_1101);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cartridge.prg_ram[0x6060 - 0x6000], 0b0001_1101);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
 
         //  0b00111010
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x5E; // LSR absoluteX
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x55;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x5E; // LSR absoluteX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x05;
         test_cpu.cpu.x = 3;
-        test_cpu.cpu.mem[0x5563] = 0b0011_1010;
+        test_cpu.cpu.mem[0x0563] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x5563], 0b0001_1101);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cpu.mem[0x0563], 0b0001_1101);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
     }
 
     #[test]
@@ -2672,7 +2683,7 @@ mod instruction_tests {
         // = 0b(1)000_1110 -> N -> 1, Z -> 0, C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x6A; // ROR accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x6A; // ROR accumulator
         test_cpu.cpu.a = 0b0001_1101;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0000_1110);
@@ -2684,7 +2695,7 @@ mod instruction_tests {
         // = 0b(1)1111111 -> N -> 1, Z -> 0, C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x6A; // ROR Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x6A; // ROR Accumulator
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0111_1111);
@@ -2696,7 +2707,7 @@ mod instruction_tests {
         // = 0b(0)0000000 -> N -> 0, Z -> 1, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x6A; // LSR Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x6A; // LSR Accumulator
         test_cpu.cpu.a = 0b0000_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -2710,8 +2721,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x66; // ROR zeropage
-        test_cpu.cpu.mem[0x8001] = 0x0076;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x66; // ROR zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0076;
         test_cpu.cpu.mem[0x0076] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0076], 0b0001_1101);
@@ -2723,8 +2734,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0x76; // ROR zeropageX
-        test_cpu.cpu.mem[0x8001] = 0x0066;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x76; // ROR zeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0066;
         test_cpu.cpu.x = 3;
         test_cpu.cpu.mem[0x0069] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2737,30 +2748,30 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0x6E; // ROR absolute
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x52;
-        test_cpu.cpu.mem[0x5260] = 0b1011_1010;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x6E; // ROR absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x02;
+        test_cpu.cpu.mem[0x0260] = 0b1011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x5260], 0b1101_1101);
-        assert_eq!(test_cpu.cpu.negative, true);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cpu.mem[0x0260], 0b1101_1101);
+        assert!(test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
 
         //  0b00111010
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x7E; // ROR absoluteX
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x66;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x7E; // ROR absoluteX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x66;
         test_cpu.cpu.x = 3;
-        test_cpu.cpu.mem[0x6663] = 0b0011_1010;
+        test_cpu.cartridge.prg_ram[0x6663 - 0x6000] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x6663], 0b0001_1101);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cartridge.prg_ram[0x6663 - 0x6000], 0b0001_1101);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
     }
 
     #[test]
@@ -2772,7 +2783,7 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x2A; // ROL accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x2A; // ROL accumulator
         test_cpu.cpu.a = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b0111_0100);
@@ -2784,7 +2795,7 @@ mod instruction_tests {
         // = 0b11111111(1) -> N -> 1, Z -> 0, C -> 1
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x2A; // ROL Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x2A; // ROL Accumulator
         test_cpu.cpu.a = 0b1111_1111;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0b1111_1110);
@@ -2796,7 +2807,7 @@ mod instruction_tests {
         // = 0b0000_000(0) -> N -> 0, Z -> 1, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x2A; // ROL Accumulator
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x2A; // ROL Accumulator
         test_cpu.cpu.a = 0b0000_0000;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.a, 0);
@@ -2810,8 +2821,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = true;
-        test_cpu.cpu.mem[0x8000] = 0x26; // ROL zeropage
-        test_cpu.cpu.mem[0x8001] = 0x0076;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x26; // ROL zeropage
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0076;
         test_cpu.cpu.mem[0x0076] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
         assert_eq!(test_cpu.cpu.mem[0x0076], 0b0111_0101);
@@ -2823,8 +2834,8 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x36; // ROL zeropageX
-        test_cpu.cpu.mem[0x8001] = 0x0066;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x36; // ROL zeropageX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x0066;
         test_cpu.cpu.x = 3;
         test_cpu.cpu.mem[0x0069] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
@@ -2837,29 +2848,256 @@ mod instruction_tests {
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x2E; // ROL absolute
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x77;
-        test_cpu.cpu.mem[0x7760] = 0b0011_1010;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x2E; // ROL absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x77;
+        test_cpu.cartridge.prg_ram[0x7760 - 0x6000] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x7760], 0b0111_0100);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cartridge.prg_ram[0x7760 - 0x6000], 0b0111_0100);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
 
         //  0b00111010
         // = 0b0111_010(0) -> N -> 0, Z -> 0, C -> 0
         test_cpu.cpu.pc = 0x8000;
         test_cpu.cpu.carry = false;
-        test_cpu.cpu.mem[0x8000] = 0x3E; // ROL absoluteX
-        test_cpu.cpu.mem[0x8001] = 0x60;
-        test_cpu.cpu.mem[0x8002] = 0x7F;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x3E; // ROL absoluteX
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x60;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x7F;
         test_cpu.cpu.x = 3;
-        test_cpu.cpu.mem[0x7F63] = 0b0011_1010;
+        test_cpu.cartridge.prg_ram[0x7F63 - 0x6000] = 0b0011_1010;
         Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
-        assert_eq!(test_cpu.cpu.mem[0x7F63], 0b0111_0100);
-        assert_eq!(test_cpu.cpu.negative, false);
-        assert_eq!(test_cpu.cpu.zero, false);
-        assert_eq!(test_cpu.cpu.carry, false);
+        assert_eq!(test_cpu.cartridge.prg_ram[0x7F63 - 0x6000], 0b0111_0100);
+        assert!(!test_cpu.cpu.negative);
+        assert!(!test_cpu.cpu.zero);
+        assert!(!test_cpu.cpu.carry);
+    }
+    #[test]
+    fn test_page_cross_cycles() {
+        let mut test_cpu = Bus::default();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xbd; // LDA Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xfe;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x01;
+        test_cpu.cpu.x = 0x01;
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(cycles, 4); // $01FF is on the same page
+
+        test_cpu.cpu.pc = 0x8000;
+        test_cpu.cpu.x = 0x02;
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(cycles, 5); // $0200 is on the next page
+
+        test_cpu.cpu.pc = 0x8000;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x9d; // STA Absolute X
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(cycles, 5); // Writes always take the extra cycle
+
+        test_cpu.cpu.pc = 0x8000;
+        test_cpu.cpu.y = 0x10;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xb1; // LDA Indirect Y
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x10;
+        test_cpu.cpu.mem[0x0010] = 0xf8;
+        test_cpu.cpu.mem[0x0011] = 0x02;
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(cycles, 6);
+    }
+    #[test]
+    fn test_branch_cycles() {
+        let mut test_cpu = Bus::default();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x10;
+        test_cpu.cpu.zero = true;
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(cycles, 2); // Not taken
+
+        test_cpu.cpu.pc = 0x8000;
+        test_cpu.cpu.zero = false;
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x8012);
+        assert_eq!(cycles, 3); // Taken
+
+        test_cpu.cpu.pc = 0x80f0;
+        test_cpu.cartridge.prg_rom_data[prg(0x80f0)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x80f1)] = 0x10;
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x8102);
+        assert_eq!(cycles, 4); // Taken to the next page
+
+        test_cpu.cpu.pc = 0x8100;
+        test_cpu.cartridge.prg_rom_data[prg(0x8100)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8101)] = 0xfa; // -6
+        let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x80fc);
+        assert_eq!(cycles, 4); // Taken to the previous page
+    }
+    /// Cycles of every opcode without crossed pages or taken branches, 0 for the opcodes that jam the CPU.
+    #[rustfmt::skip]
+    const CYCLES: [u8; 256] = [
+        7, 6, 0, 8, 3, 3, 5, 5, 3, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        6, 6, 0, 8, 3, 3, 5, 5, 4, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        6, 6, 0, 8, 3, 3, 5, 5, 3, 2, 2, 2, 3, 4, 6, 6,
+        2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        6, 6, 0, 8, 3, 3, 5, 5, 4, 2, 2, 2, 5, 4, 6, 6,
+        2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4,
+        2, 6, 0, 6, 4, 4, 4, 4, 2, 5, 2, 5, 5, 5, 5, 5,
+        2, 6, 2, 6, 3, 3, 3, 3, 2, 2, 2, 2, 4, 4, 4, 4,
+        2, 5, 0, 5, 4, 4, 4, 4, 2, 4, 2, 4, 4, 4, 4, 4,
+        2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+        2, 6, 2, 8, 3, 3, 5, 5, 2, 2, 2, 2, 4, 4, 6, 6,
+        2, 5, 0, 8, 4, 4, 6, 6, 2, 4, 2, 7, 4, 4, 7, 7,
+    ];
+
+    #[test]
+    fn test_opcode_cycles() {
+        for opcode in 0..=255u8 {
+            if CYCLES[opcode as usize] == 0 {
+                continue;
+            }
+            let mut test_cpu = Bus::default();
+            let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+            // Flags that make every branch fall through
+            test_cpu.cpu.carry = opcode != 0xb0;
+            test_cpu.cpu.zero = opcode != 0xf0;
+            test_cpu.cpu.negative = opcode != 0x30;
+            test_cpu.cpu.overflow = opcode != 0x70;
+            test_cpu.cartridge.prg_rom_data[prg(0x8000)] = opcode;
+            let cycles = Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+            assert_eq!(cycles, CYCLES[opcode as usize], "opcode {opcode:#04x}");
+        }
+    }
+    #[test]
+    fn test_dummy_accesses() {
+        let mut test_cpu = Bus::default();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xbd; // LDA Absolute X
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xff;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x20;
+        test_cpu.cpu.x = 0x08;
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        // The dummy read from $2007 and the read from its mirror $2107 both increment the VRAM address
+        assert_eq!(test_cpu.ppu_addr, 2);
+
+        test_cpu.cpu.pc = 0x8000;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xee; // INC Absolute
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x06;
+        test_cpu.cartridge.prg_rom_data[prg(0x8002)] = 0x20;
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        // The dummy write and the write both go to PPUADDR
+        assert!(!test_cpu.ppu_addr_toggle);
+    }
+
+    /// Builds a bus whose mapper holds the IRQ line low, with the IRQ handler at $9000 and the NMI handler at $9100.
+    fn irq_bus() -> Bus {
+        let mut test_cpu = Bus::default();
+        // MMC3 with its IRQ flag raised, the last byte of its state
+        test_cpu.cartridge.mapper_number = 4;
+        let mut mapper = get_mapper(&test_cpu.cartridge).unwrap();
+        let mut state = mapper.save_state();
+        *state.last_mut().unwrap() = 1;
+        mapper.load_state(&test_cpu.cartridge, &state).unwrap();
+        test_cpu.mapper = mapper;
+        test_cpu.cartridge.prg_rom_data[prg(0xfffa)] = 0x00;
+        test_cpu.cartridge.prg_rom_data[prg(0xfffb)] = 0x91;
+        test_cpu.cartridge.prg_rom_data[prg(0xfffe)] = 0x00;
+        test_cpu.cartridge.prg_rom_data[prg(0xffff)] = 0x90;
+        test_cpu
+    }
+    #[test]
+    fn test_cli_delay() {
+        let mut test_cpu = irq_bus();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cpu.irq_dis = true;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x58; // CLI Implied
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0xea; // NOP Implied
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x8002); // The instruction after CLI runs first
+
+        assert_eq!(
+            Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu),
+            7
+        );
+        assert_eq!(test_cpu.cpu.pc, 0x9000);
+        assert!(test_cpu.cpu.irq_dis);
+        assert_eq!(test_cpu.cpu.mem[0x01ff], 0x80);
+        assert_eq!(test_cpu.cpu.mem[0x01fe], 0x02);
+        assert_eq!(test_cpu.cpu.mem[0x01fd] & 0b0011_0100, 0b0010_0000); // B and I clear
+    }
+    #[test]
+    fn test_sei_delay() {
+        let mut test_cpu = irq_bus();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x78; // SEI Implied
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        // The IRQ was polled before SEI set the flag, the pushed status has it set
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x9000);
+        assert_eq!(test_cpu.cpu.mem[0x01fd] & 0b0011_0100, 0b0010_0100);
+    }
+    #[test]
+    fn test_branch_delays_irq() {
+        let mut test_cpu = irq_bus();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cpu.irq_dis = true;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xd0; // BNE Relative
+        test_cpu.cartridge.prg_rom_data[prg(0x8001)] = 0x10;
+        test_cpu.cartridge.prg_rom_data[prg(0x8012)] = 0xea; // NOP Implied
+        Instruction::execute_cycle(&mut test_cpu, &mut dummy_ppu);
+        test_cpu.cpu.irq_dis = false; // The IRQ shows up during the operand fetch
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x8013);
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x9000);
+    }
+    #[test]
+    fn test_nmi() {
+        let mut test_cpu = Bus::default();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cartridge.prg_rom_data[prg(0xfffa)] = 0x00;
+        test_cpu.cartridge.prg_rom_data[prg(0xfffb)] = 0x91;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0xea; // NOP Implied
+        test_cpu.cpu.b = true;
+        test_cpu.nmi_pending = true;
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(
+            Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu),
+            7
+        );
+        assert_eq!(test_cpu.cpu.pc, 0x9100);
+        assert_eq!(test_cpu.cpu.mem[0x01fd] & 0b0011_0000, 0b0010_0000); // B flag clear
+        assert!(!test_cpu.nmi_pending);
+
+        // The first instruction of the handler runs before anything else
+        test_cpu.cartridge.prg_rom_data[prg(0x9100)] = 0xea; // NOP Implied
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x9101);
+    }
+    #[test]
+    fn test_nmi_hijacks_brk() {
+        let mut test_cpu = irq_bus();
+        let mut dummy_ppu = Ppu::new(Mirroring::Horizontal);
+        test_cpu.cpu.irq_dis = true;
+        test_cpu.cartridge.prg_rom_data[prg(0x8000)] = 0x00; // BRK Implied
+        for _ in 0..3 {
+            Instruction::execute_cycle(&mut test_cpu, &mut dummy_ppu);
+        }
+        test_cpu.nmi_pending = true;
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x9100);
+        assert_eq!(test_cpu.cpu.mem[0x01fd] & 0b0011_0000, 0b0011_0000); // Still pushed by BRK
+        assert!(!test_cpu.nmi_pending);
+
+        // Without the NMI, BRK uses the IRQ vector
+        test_cpu.cpu.pc = 0x8000;
+        Instruction::do_instruction(&mut test_cpu, &mut dummy_ppu);
+        assert_eq!(test_cpu.cpu.pc, 0x9000);
     }
 }
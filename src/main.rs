@@ -1,32 +1,172 @@
+mod apu;
 mod bus;
 mod cartridge;
+mod cli;
 mod controller;
 mod cpu;
 mod instructions;
 mod instructions_test;
 mod mapper;
+mod save;
+mod wav;
 
-use crate::cartridge::Cartridge;
+use crate::cartridge::{Cartridge, Timing};
 use crate::cpu::Cpu6502;
 use instructions::Instruction;
-use mapper::MapperType;
 
-use crate::bus::Bus;
-use log::LevelFilter;
-use tudelft_nes_ppu::{run_cpu, Mirroring};
+use crate::apu::{CPU_CLOCK, PAL_CPU_CLOCK};
+use crate::bus::{clock_seed, Bus};
+use crate::cli::{CliError, Region, USAGE};
+use crate::save::SaveFile;
+use crate::wav::WavWriter;
+use std::path::Path;
+use std::sync::mpsc::channel;
+use std::thread::{self, JoinHandle};
+use std::{env, fs, process};
+use tudelft_nes_ppu::{run_cpu, run_cpu_headless_for, Mirroring};
 use tudelft_nes_test::TestableCpu;
 
+/// Number of CPU cycles in an NTSC frame, 341 * 262 PPU dots at three dots per CPU cycle.
+const CYCLES_PER_FRAME: usize = 29781;
+
+/// Sends the audio of the APU to a WAV file, which is written on its own thread.
+///
+/// # Arguments
+///
+/// * `cpu` - The bus whose APU is recorded.
+/// * `path` - Path of the WAV file.
+/// * `clock_rate` - CPU clock of the console in Hz, which the APU output changes at.
+/// * `sample_rate` - Sample rate of the recording in Hz.
+///
+/// # Return
+/// * `JoinHandle<()>` - The writing thread, which ends when the bus is dropped.
+fn record_audio(cpu: &mut Bus, path: &Path, clock_rate: u32, sample_rate: u32) -> JoinHandle<()> {
+    let mut wav = match WavWriter::create(path, sample_rate) {
+        Ok(wav) => wav,
+        Err(e) => {
+            eprintln!("error: could not create WAV file '{}': {e}", path.display());
+            process::exit(1);
+        }
+    };
+    let (sender, receiver) = channel::<Vec<f32>>();
+    cpu.apu.set_sample_rate(clock_rate, sample_rate);
+    // The file takes the samples as fast as they come, so the recording keeps the nominal rate
+    cpu.apu.resampler.rate_control.set_buffer_fill(0.5);
+    cpu.apu.output = Some(sender);
+
+    let path = path.to_path_buf();
+    thread::spawn(move || {
+        for samples in receiver {
+            if let Err(e) = wav.write_samples(&samples) {
+                log::error!(
+                    "could not write '{}', recording stopped: {e}",
+                    path.display()
+                );
+                return;
+            }
+        }
+    })
+}
+
 fn main() {
-    env_logger::builder().filter_level(LevelFilter::Info).init();
+    let options = match cli::parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(CliError::Help) => {
+            println!("{USAGE}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    env_logger::builder().filter_level(options.log_level).init();
+
+    let rom = match fs::read(&options.rom_path) {
+        Ok(rom) => rom,
+        Err(e) => {
+            eprintln!(
+                "error: could not read ROM file '{}': {e}",
+                options.rom_path.display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let mut cpu = match Bus::get_cpu(&rom) {
+        Ok(cpu) => cpu,
+        Err(e) => {
+            eprintln!(
+                "error: could not load ROM file '{}': {e}",
+                options.rom_path.display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let save_file = SaveFile::for_rom(&options.rom_path);
+    let save_path = save_file.path.clone();
+    if let Err(e) = cpu.attach_save_file(save_file) {
+        log::warn!(
+            "could not read save file '{}', the game will not be saved: {e}",
+            save_path.display()
+        );
+    }
+
+    let region = options.region.unwrap_or(match cpu.cartridge.timing {
+        Timing::Pal | Timing::Dendy => Region::Pal,
+        Timing::Ntsc | Timing::MultiRegion => Region::Ntsc,
+    });
+    if region == Region::Pal {
+        log::warn!("PAL timing is not supported by the PPU, running at NTSC speed");
+    }
+
+    cpu.random_ram = options.random_ram;
+    if options.random_ram {
+        cpu.randomize_ram(clock_seed());
+    }
+
+    if let Some(mirroring) = options.mirroring {
+        cpu.override_mirroring(mirroring);
+    }
+    log::info!("Starting with {:?} mirroring", cpu.mirroring());
 
-    pub const FILE: &[u8] = include_bytes!("../test_roms/nestest.nes");
+    let clock_rate = match region {
+        Region::Ntsc => CPU_CLOCK,
+        Region::Pal => PAL_CPU_CLOCK,
+    };
+    let recording = options
+        .wav_path
+        .as_deref()
+        .map(|path| record_audio(&mut cpu, path, clock_rate, options.sample_rate));
 
-    let cpu = Bus::get_cpu(FILE).expect("In main error");
+    if let Some(frames) = options.frames {
+        // The PPU can only be stepped by the runners of tudelft-nes-ppu, which all sleep to keep the speed of the console.
+        // A headless run therefore takes as long as the frames would on a console, there is no unthrottled runner to use.
+        if let Err(e) = run_cpu_headless_for(
+            &mut cpu,
+            Mirroring::FourScreen,
+            frames as usize * CYCLES_PER_FRAME,
+        ) {
+            eprintln!("error: {e}");
+            process::exit(1);
+        }
+        // Dropping the bus writes the save file and the last samples, which ends the recording
+        drop(cpu);
+        if let Some(recording) = recording {
+            recording.join().expect("WAV writer panicked");
+        }
+        return;
+    }
 
-    run_cpu(cpu, Mirroring::Horizontal);
+    // The bus arranges the nametables itself, so that mappers can change the mirroring.
+    // Closing the window ends the process without returning, the bus flushes the save file every second for that reason.
+    run_cpu(cpu, Mirroring::FourScreen);
 }
 
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod tests {
     use crate::Bus;
     use log::LevelFilter;
@@ -1,832 +1,522 @@
-//! This module handles the mapping of logical addresses used by the CPU and the physical memory location in the cartridge
-
+//! This module handles the mapping of logical addresses used by the CPU and the PPU to the physical memory locations in the cartridge.
+//!
+//! Every board is implemented in its own submodule by a struct implementing `Mapper`. To add a board, create a new file in `src/mapper/` and add it to `MAPPERS`.
+
+mod axrom;
+mod cnrom;
+mod mmc1;
+mod mmc2;
+mod mmc3;
+mod nrom;
+mod uxrom;
+
+use crate::cartridge::RomError;
 use crate::Cartridge;
-
-/// A struct translating the memory address used by the CPU into the physical memory location depending on the mapper that is used
-#[derive(Debug, PartialEq, Eq)]
-pub enum MapperType {
-    /// Simple mapper which maps the logical address to the same physical address.
-    Nrom { prg_rom_size_in_16kb: u8 },
-    /// Nintendo MMC1 mapper.
-    MMC1 {
-        // Settings
-        /// Indicates whether mirroring is used by PPU.
-        mirroring: u8,
-        /// Specifies the PRGM ROM bank mode.
-        prg_rom_bank_mode: u8,
-        /// Specifies the CHR ROM bank mode.
-        chr_rom_bank_mode: bool,
-        /// Specifies which CHR are used by the PPU.
-        chr_bank0: u8,
-        /// Specifies which CHR are used by the PPU.
-        chr_bank1: u8,
-        /// Location of the switchable bank.
-        prg_bank: u8,
-        /// Indicates whether in MMC1B mode is used.
-        mmc1b: bool,
-
-        /// Register used by load-register.
-        shift_register: u8,
-        /// Data bits in the shift register.
-        amount_shifted: u8,
-    },
+use axrom::Axrom;
+use cnrom::Cnrom;
+use mmc1::Mmc1;
+use mmc2::Mmc2;
+use mmc3::Mmc3;
+use nrom::Nrom;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use tudelft_nes_ppu::Mirroring;
+use uxrom::Uxrom;
+
+/// A serialized mapper state could not be restored.
+#[derive(PartialEq, Eq, Debug)]
+pub struct StateError {
+    /// Number of bytes the mapper stores in its state.
+    pub expected: usize,
+    /// Number of bytes that were given.
+    pub found: usize,
 }
-impl Default for MapperType {
-    /// Implements the trait `Default` for MapperType which returns a default instance which uses the NROM mapper with the ROM size of 16 kB.
-    ///
-    /// # Return
-    /// * `Self` - Instance of the struct in the default state.
-    fn default() -> Self {
-        MapperType::Nrom {
-            prg_rom_size_in_16kb: 1,
-        }
+
+impl Display for StateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mapper state has {} bytes, expected {}",
+            self.found, self.expected
+        )
     }
 }
-impl MapperType {
-    /// Return a mapper instance based on the provided cartridge.
+
+impl Error for StateError {}
+
+/// Splits a serialized mapper state into its registers.
+///
+/// # Arguments
+///
+/// * `state` - Serialized registers.
+///
+/// # Return
+/// * `Result<[u8; N], StateError>` - The registers, or an error if `state` does not have `N` bytes.
+pub fn state_registers<const N: usize>(state: &[u8]) -> Result<[u8; N], StateError> {
+    state.try_into().map_err(|_| StateError {
+        expected: N,
+        found: state.len(),
+    })
+}
+
+/// A board translating the memory addresses used by the CPU and the PPU into the physical memory locations of the cartridge.
+///
+/// The ROM and RAM chips belong to the `Cartridge`, which is passed to every method, the mapper only holds the state of its registers.
+pub trait Mapper: Debug + Send {
+    /// Reads a byte from the cartridge space of the CPU.
     ///
     /// # Arguments
     ///
-    /// * `mapper_number` - ID of the mapper.
-    /// * `cartridge` - An instance of `Cartridge` used to determine the prg rom size.
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - CPU address in $6000-$FFFF.
     ///
     /// # Return
-    /// * `MapperType` - Enum of mapper. If mapper ID is not known, the function panics.
-    pub fn get_mapper(mapper_number: u8, cartridge: Cartridge) -> MapperType {
-        match mapper_number {
-            0 => MapperType::Nrom {
-                prg_rom_size_in_16kb: cartridge.prg_rom_size_in_16kb,
-            },
-            1 => MapperType::MMC1 {
-                mirroring: 0,
-                prg_rom_bank_mode: 0,
-                chr_rom_bank_mode: false,
-                chr_bank0: 0,
-                chr_bank1: 0,
-                prg_bank: 0,
-                mmc1b: false,
-                // Stored values
-                shift_register: 0,
-                amount_shifted: 0,
-            },
-            _ => panic!("Mapper not implemented/known"),
-        }
-    }
+    /// * `Option<u8>` - Byte mapped to `addr`, `None` if the cartridge does not drive the data bus, e.g. for missing or disabled PRG RAM.
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8>;
 
-    /// Return the mapped address.
+    /// Writes a byte to the cartridge space of the CPU, either into PRG RAM or into the registers of the mapper.
     ///
     /// # Arguments
     ///
-    /// * `self` - Instance of mapper itself.
-    /// * `addr` - Address that shall be translated to the mapped address.
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - CPU address in $6000-$FFFF.
+    /// * `data` - Data byte that shall be written.
+    ///
+    /// Nothing is returned.
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8);
+
+    /// Reads a byte from the pattern tables of the PPU. Every pattern fetch of the PPU goes through here, so boards that watch the fetches (like the CHR latches of MMC2) update their state in this method. As the PPU only holds a shared reference to the bus while rendering, that state has to live in a `Cell`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - PPU address in $0000-$1FFF.
     ///
     /// # Return
-    /// * `u16` - Mapped address.
-    pub fn get_mapper_address(&self, addr: u16) -> u16 {
-        match self {
-            MapperType::Nrom {
-                prg_rom_size_in_16kb,
-            } => {
-                if addr < 0x8000 {
-                    return addr;
-                }
-                if *prg_rom_size_in_16kb > 2 {
-                    panic!("Program ROM is too big for NROM")
-                }
-                if *prg_rom_size_in_16kb == 2 {
-                    return addr;
-                }
-                if addr < 0xc000 {
-                    addr
-                } else {
-                    addr - 16384
-                }
-            }
-            MapperType::MMC1 { .. } => addr,
-        }
-    }
+    /// * `u8` - Byte mapped to `addr`.
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8;
 
-    /// Write to mapped address (Not possible for NROM mapper).
+    /// Writes a byte to the pattern tables of the PPU. CHR ROM ignores the write.
     ///
     /// # Arguments
     ///
-    /// * `self` - Borrowed instance of mapper itself.
-    /// * `addr` - Address to which the data byte shall be written.
-    /// * `data` - Data byte that shall be written.
-    /// * `mem`  - Whole memory of a i. e. CPU.
     /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - PPU address in $0000-$1FFF.
+    /// * `data` - Data byte that shall be written.
     ///
     /// Nothing is returned.
-    pub fn write_mapper(
-        &mut self,
-        addr: u16,
-        data: u8,
-        mem: &mut [u8; 0xffff + 1],
-        cart: &Cartridge,
-    ) {
-        //You can't write to read-only memory
-        match self {
-            MapperType::Nrom { .. } => {}
-            MapperType::MMC1 {
-                ref mut mirroring,
-                ref mut prg_rom_bank_mode,
-                ref mut chr_rom_bank_mode,
-                ref mut chr_bank0,
-                ref mut chr_bank1,
-                ref mut prg_bank,
-                ref mut mmc1b,
-                // Stored values
-                ref mut shift_register,
-                ref mut amount_shifted,
-            } => {
-                let mut prg_bank_changed = false;
-                *shift_register >>= 1;
-                *shift_register |= (data & 0x1) << 4;
-                *amount_shifted += 1;
-                if data & 0x80 == 0x80 {
-                    *shift_register = 0;
-                    *amount_shifted = 0;
-                    *prg_rom_bank_mode = 0b11;
-                    //return;
-                }
-                if *amount_shifted == 5 {
-                    if (0x8000..=0x9fff).contains(&addr) {
-                        *mirroring = *shift_register & 0b11;
-                        *prg_rom_bank_mode = (*shift_register & 0b1100) >> 2;
-                        *chr_rom_bank_mode = (*shift_register & 0b1_0000) == 0b1_0000;
-                        prg_bank_changed = true;
-                    } else if (0xa000..=0xbfff).contains(&addr) {
-                        *chr_bank0 = *shift_register & 0b1_1111;
-                    } else if (0xc000..=0xdfff).contains(&addr) {
-                        *chr_bank1 = *shift_register & 0b1_1111;
-                    } else if (0xe000..=0xffff).contains(&addr) {
-                        *prg_bank = *shift_register & 0b1111;
-                        *mmc1b = *shift_register & 0b1_0000 == 0b1_0000;
-                        prg_bank_changed = true;
-                    }
-                    *shift_register = 0;
-                    *amount_shifted = 0;
-                    if prg_bank_changed {
-                        //for i in 0x8000..0xffff {
-                        for (i, mem_ref) in mem.iter_mut().enumerate().take(0xffff + 1).skip(0x8000)
-                        {
-                            if *prg_rom_bank_mode == 0 {
-                                if i < 0xc000 {
-                                    *mem_ref = cart.prg_rom_data[(i - 0x8000
-                                        + 16384 * (*prg_bank & 0b1110) as usize)
-                                        as usize];
-                                } else {
-                                    *mem_ref = cart.prg_rom_data[(i - 0x8000
-                                        + 16384 * (*prg_bank & 0b1111) as usize)
-                                        as usize];
-                                }
-                                //mem[i] = cart.prg_rom_data[(i-0x8000 + 32768 * (prg_bank & 0b1110) as usize) as usize]; // TODO see if this can work too
-                            } else if *prg_rom_bank_mode == 1 {
-                                if i < 0xc000 {
-                                    *mem_ref = cart.prg_rom_data[i - 0x8000]; // Set to first PRG bank
-                                } else {
-                                    *mem_ref = cart.prg_rom_data
-                                        [(i - 0x8000 + 16384 * *prg_bank as usize) as usize];
-                                }
-                            } else if *prg_rom_bank_mode == 2 {
-                                if i < 0xc000 {
-                                    *mem_ref = cart.prg_rom_data[(i - 0x8000) as usize];
-                                } else {
-                                    *mem_ref = cart.prg_rom_data
-                                        [(i - 0xc000 + 16384 * *prg_bank as usize) as usize];
-                                }
-                            } else if *prg_rom_bank_mode == 3 {
-                                if i >= 0xc000 {
-                                    *mem_ref = cart.prg_rom_data
-                                        [cart.prg_rom_data.len() - 16834 + (i - 0xc000)];
-                                } else {
-                                    *mem_ref = cart.prg_rom_data
-                                        [(i - 0x8000 + 16384 * *prg_bank as usize) as usize];
-                                }
-                            } else {
-                                panic!("Prg rom bank mode wrong")
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    pub fn get_chr_data(&self, cart: &Cartridge, offset: u16) -> u8 {
-        if cart.chr_rom_size_in_8kb == 0 {
-            return 0; // TODO handle chr RAM properly
-        }
-        let mut chr_bank: [u8; 8192] = [0; 8192];
-        match self {
-            MapperType::Nrom { .. } => cart.chr_rom_data[offset as usize],
-            MapperType::MMC1 {
-                mirroring: _,
-                prg_rom_bank_mode: _,
-                chr_rom_bank_mode,
-                chr_bank0,
-                chr_bank1,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register: _,
-                amount_shifted: _,
-            } => {
-                if *chr_rom_bank_mode {
-                    for (i, mem_ref) in chr_bank.iter_mut().enumerate().take(0x1fff) {
-                        if i <= 0x0fff {
-                            *mem_ref = cart.chr_rom_data
-                                [(i + 4096 * (chr_bank0 & 0b1110) as usize) as usize];
-                        } else {
-                            *mem_ref = cart.chr_rom_data
-                                [(i + 4096 * (chr_bank0 & 0b1111) as usize) as usize];
-                        }
-                    }
-                } else {
-                    for (i, mem_ref) in chr_bank.iter_mut().enumerate().take(0x1fff) {
-                        if i <= 0x0fff {
-                            *mem_ref = cart.chr_rom_data[(i + 4096 * *chr_bank0 as usize) as usize];
-                        } else {
-                            *mem_ref = cart.chr_rom_data[(i + 4096 * *chr_bank1 as usize) as usize];
-                        }
-                    }
-                }
-                chr_bank[offset as usize]
-            }
-        }
-    }
-}
+    fn ppu_write(&mut self, _cart: &mut Cartridge, _addr: u16, _data: u8) {}
 
-#[cfg(test)]
-mod mapper_tests {
-    use crate::bus::Bus;
-    use crate::controller::Controller;
-    use crate::MapperType::{Nrom, MMC1};
-    use crate::{Cartridge, Cpu6502, MapperType};
+    /// Returns the nametable mirroring currently selected by the mapper, the bus checks it after every write to the mapper.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge, used for boards with hard-wired mirroring.
+    ///
+    /// # Return
+    /// * `Mirroring` - Mirroring the PPU should use.
+    fn mirroring(&self, cart: &Cartridge) -> Mirroring;
 
-    #[test]
-    fn test_nrom() {
-        let cart = Cartridge::default();
-        let mapper0 = MapperType::get_mapper(0, cart);
-        assert_eq!(
-            mapper0,
-            Nrom {
-                prg_rom_size_in_16kb: 1
-            }
-        );
-        assert_eq!(mapper0.get_mapper_address(0xC000), 0x8000);
-        // Make cartridge with 32 kb
-        let cart_32kb = Cartridge {
-            prg_rom_size_in_16kb: 2,
-            chr_rom_size_in_8kb: 1,
-            mapper_number: 0,
-            mirroring_control: false,
-            trainer_available: false,
-            battery_backed_prg_ram_available: false,
-            mirroring_mode: false,
-            nes_2_0_format: false,
-            playchoice_10: false,
-            vs_unisystem: false,
-            prg_ram_size_in_8kb: 1,
-            tv_system_mode: false,
-            prg_ram_present: false,
-            bus_conflicts: false,
-            prg_rom_data: Vec::new(), // Dont need this
-            chr_rom_data: Vec::new(), // Don't need this
-        };
-        let mapper0_32kb = MapperType::get_mapper(0, cart_32kb);
-        assert_eq!(mapper0_32kb.get_mapper_address(0x8000), 0x8000);
-        assert_eq!(mapper0_32kb.get_mapper_address(0xC000), 0xC000);
+    /// Returns whether the mapper pulls the IRQ line of the CPU low.
+    fn irq(&self) -> bool {
+        false
     }
 
-    #[test]
-    fn test_mmc1_write_prg() {
-        let mut rom_data: [u8; 0xffff + 1] = [0; 0xffff + 1];
-        for i in 0x8000..=0xffff {
-            rom_data[i] = (i - 0x8000) as u8;
-        }
+    /// Called by the bus once per visible or pre-render scanline while rendering is enabled.
+    fn notify_scanline(&mut self) {}
 
-        let test_cpu = Cpu6502 {
-            a: 0,
-            x: 0,
-            y: 0,
-            pc: 0x8000,
-            sp: 0x01FF,
-
-            carry: false,
-            zero: false,
-            irq_dis: false,
-            dec: false,
-            b: false,
-            overflow: false,
-            negative: false,
-
-            mem: rom_data,
-        };
+    /// Called by the bus once per CPU cycle.
+    fn notify_cpu_cycle(&mut self) {}
 
-        let prg_rom_size_in_16kb = 16;
-        let chr_rom_size_in_8kb = 1;
-        let mut prg_data: Vec<u8> = Vec::new();
-        let mut chr_data: Vec<u8> = Vec::new();
-        // Fill cartridge and rom with same data
-        for i in 0..16384 * prg_rom_size_in_16kb as u32 {
-            prg_data.push((i % 8) as u8);
-        }
-        for i in 0..8192 * (chr_rom_size_in_8kb as u16) {
-            chr_data.push((i % 8) as u8);
-        }
-
-        let cart = Cartridge {
-            prg_rom_size_in_16kb,
-            chr_rom_size_in_8kb,
+    /// Returns the memory that is kept alive by the battery of the cartridge.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// # Return
+    /// * `Option<&[u8]>` - The battery-backed memory, `None` if the cartridge has no battery.
+    fn save_ram<'a>(&self, cart: &'a Cartridge) -> Option<&'a [u8]> {
+        cart.battery_backed_prg_ram_available
+            .then_some(cart.prg_ram.as_slice())
+    }
 
-            mapper_number: 1,
-            mirroring_control: false,
-            battery_backed_prg_ram_available: false,
-            trainer_available: false,
-            mirroring_mode: false,
+    /// Restores the memory that is kept alive by the battery of the cartridge, e.g. from a save file.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `data` - Saved memory, ignored beyond the size of the battery-backed memory.
+    ///
+    /// Nothing is returned.
+    fn load_save_ram(&mut self, cart: &mut Cartridge, data: &[u8]) {
+        if cart.battery_backed_prg_ram_available {
+            let len = data.len().min(cart.prg_ram.len());
+            cart.prg_ram[..len].copy_from_slice(&data[..len]);
+        }
+    }
 
-            vs_unisystem: false,
-            playchoice_10: false,
-            nes_2_0_format: false,
+    /// Serializes the registers of the mapper.
+    ///
+    /// # Return
+    /// * `Vec<u8>` - State that can be restored with `load_state`.
+    fn save_state(&self) -> Vec<u8>;
 
-            prg_ram_size_in_8kb: 1,
+    /// Restores the registers of the mapper from a state created by `save_state`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge, used to rebuild the bank table.
+    /// * `state` - Serialized registers.
+    ///
+    /// # Return
+    /// * `Result<(), StateError>` - An error if `state` does not belong to this mapper.
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError>;
+}
 
-            tv_system_mode: false, // Not used for now
+/// Creates the mapper for a cartridge.
+type MapperConstructor = fn(&Cartridge) -> Result<Box<dyn Mapper>, RomError>;
+
+/// All supported boards as (mapper number, submapper number, constructor). A submapper of `None` matches every submapper that has no entry of its own.
+const MAPPERS: &[(u16, Option<u8>, MapperConstructor)] = &[
+    (0, None, |cart| Ok(Box::new(Nrom::new(cart)?))),
+    (1, None, |cart| Ok(Box::new(Mmc1::new(cart)))),
+    (2, None, |cart| Ok(Box::new(Uxrom::new(cart)))),
+    (3, None, |cart| Ok(Box::new(Cnrom::new(cart)))),
+    (4, None, |cart| Ok(Box::new(Mmc3::new(cart)))),
+    (7, None, |cart| Ok(Box::new(Axrom::new(cart)))),
+    (9, None, |cart| Ok(Box::new(Mmc2::new(cart)))),
+    (10, None, |cart| Ok(Box::new(Mmc2::new(cart)))),
+];
+
+/// Return a mapper instance based on the provided cartridge.
+///
+/// # Arguments
+///
+/// * `cartridge` - Borrowed instance of `Cartridge`, used to determine the mapper and submapper number.
+///
+/// # Return
+/// * `Result<Box<dyn Mapper>, RomError>` - The mapper, or an error if the mapper is not known or does not fit the cartridge.
+pub fn get_mapper(cartridge: &Cartridge) -> Result<Box<dyn Mapper>, RomError> {
+    let board = |submapper| {
+        MAPPERS
+            .iter()
+            .find(|(number, sub, _)| *number == cartridge.mapper_number && *sub == submapper)
+    };
+    let (_, _, constructor) = board(Some(cartridge.submapper_number))
+        .or_else(|| board(None))
+        .ok_or(RomError::UnsupportedMapper(cartridge.mapper_number))?;
+    constructor(cartridge)
+}
 
-            prg_ram_present: false,
-            bus_conflicts: false,
+impl Default for Box<dyn Mapper> {
+    /// Implements the trait `Default` for a boxed mapper which returns an NROM mapper for the default cartridge.
+    ///
+    /// # Return
+    /// * `Self` - Instance of the mapper in the default state.
+    fn default() -> Self {
+        Box::new(Nrom)
+    }
+}
 
-            prg_rom_data: prg_data,
-            chr_rom_data: chr_data,
-        };
+/// Offsets of the banks mapped into the address spaces of the CPU and the PPU. Mappers rebuild the table when a bank register changes, so that reads are a lookup instead of redoing the bank arithmetic for every byte.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct BankTable {
+    /// Offsets into PRG ROM of the 8 kB windows at $8000, $A000, $C000 and $E000.
+    pub prg: [usize; 4],
+    /// Offsets into CHR ROM or CHR RAM of the 1 kB windows at $0000-$1FFF.
+    pub chr: [usize; 8],
+}
 
-        let mut bus = Bus {
-            cpu: test_cpu,
-            cartridge: cart,
-            cycle: 0,
-            mapper: MMC1 {
-                mirroring: 0,
-                prg_rom_bank_mode: 0,
-                chr_rom_bank_mode: true,
-                chr_bank0: 0,
-                chr_bank1: 0,
-                prg_bank: 0,
-                mmc1b: false,
-                // Stored values
-                shift_register: 0,
-                amount_shifted: 0,
-            },
-            controller: Controller::new(),
-            jam: false,
-        };
-        bus.mapper
-            .write_mapper(0xa000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring: _,
-                prg_rom_bank_mode: _,
-                chr_rom_bank_mode: _,
-                chr_bank0: _,
-                chr_bank1: _,
-                prg_bank,
-                mmc1b,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 2);
-                assert_eq!(shift_register, 0b0_0000);
-                assert_eq!(prg_bank, 0);
-                assert_eq!(mmc1b, false);
-            }
-            _ => panic!("This is a mmc1 test"),
-        }
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xe000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring: _,
-                prg_rom_bank_mode: _,
-                chr_rom_bank_mode: _,
-                chr_bank0: _,
-                chr_bank1: _,
-                prg_bank,
-                mmc1b,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 0);
-                assert_eq!(shift_register, 0b0);
-                assert_eq!(prg_bank, 0b1100);
-                assert_eq!(mmc1b, true);
-            }
-            _ => panic!("This is a mmc1 test"),
+impl BankTable {
+    /// Maps a PRG ROM bank into the address space of the CPU.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge, bank numbers wrap around its PRG ROM size.
+    /// * `addr` - First CPU address of the bank, a multiple of 8 kB in $8000-$FFFF.
+    /// * `size` - Size of the bank in bytes, a multiple of 8 kB.
+    /// * `bank` - Number of the bank in units of `size`.
+    ///
+    /// Nothing is returned.
+    pub fn map_prg(&mut self, cart: &Cartridge, addr: u16, size: usize, bank: usize) {
+        let len = cart.prg_rom_data.len().max(1);
+        let first = (addr as usize - 0x8000) / 8192;
+        for (i, window) in self.prg[first..first + size / 8192].iter_mut().enumerate() {
+            *window = (bank * size + i * 8192) % len;
         }
     }
-    #[test]
-    fn test_mmc1_write_chr_bank1() {
-        let mut rom_data: [u8; 0xffff + 1] = [0; 0xffff + 1];
-        for i in 0x8000..=0xffff {
-            rom_data[i] = (i - 0x8000) as u8;
-        }
 
-        let test_cpu = Cpu6502 {
-            a: 0,
-            x: 0,
-            y: 0,
-            pc: 0x8000,
-            sp: 0x01FF,
-
-            carry: false,
-            zero: false,
-            irq_dis: false,
-            dec: false,
-            b: false,
-            overflow: false,
-            negative: false,
-
-            mem: rom_data,
-        };
-
-        let prg_rom_size_in_16kb = 16;
-        let chr_rom_size_in_8kb = 1;
-        let mut prg_data: Vec<u8> = Vec::new();
-        let mut chr_data: Vec<u8> = Vec::new();
-        // Fill cartridge and rom with same data
-        for i in 0..16384 * (prg_rom_size_in_16kb as u32) {
-            prg_data.push(i as u8);
-        }
-        for i in 0..8192 * (chr_rom_size_in_8kb as u16) {
-            chr_data.push(i as u8);
+    /// Maps a CHR bank into the address space of the PPU.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge, bank numbers wrap around the size of its CHR memory.
+    /// * `addr` - First PPU address of the bank, a multiple of 1 kB in $0000-$1FFF.
+    /// * `size` - Size of the bank in bytes, a multiple of 1 kB.
+    /// * `bank` - Number of the bank in units of `size`.
+    ///
+    /// Nothing is returned.
+    pub fn map_chr(&mut self, cart: &Cartridge, addr: u16, size: usize, bank: usize) {
+        let len = chr_memory(cart).len().max(1);
+        let first = addr as usize / 1024;
+        for (i, window) in self.chr[first..first + size / 1024].iter_mut().enumerate() {
+            *window = (bank * size + i * 1024) % len;
         }
+    }
 
-        let cart = Cartridge {
-            prg_rom_size_in_16kb,
-            chr_rom_size_in_8kb,
-
-            mapper_number: 1,
-            mirroring_control: false,
-            battery_backed_prg_ram_available: false,
-            trainer_available: false,
-            mirroring_mode: false,
-
-            vs_unisystem: false,
-            playchoice_10: false,
-            nes_2_0_format: false,
-
-            prg_ram_size_in_8kb: 1,
-
-            tv_system_mode: false, // Not used for now
-
-            prg_ram_present: false,
-            bus_conflicts: false,
+    /// Reads a byte of PRG ROM through the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - CPU address in $8000-$FFFF.
+    ///
+    /// # Return
+    /// * `u8` - Byte mapped to `addr`, 0 if the bank lies beyond the end of the PRG ROM.
+    pub fn read_prg(&self, cart: &Cartridge, addr: u16) -> u8 {
+        let addr = addr as usize - 0x8000;
+        let offset = self.prg[addr >> 13] + (addr & 0x1fff);
+        cart.prg_rom_data.get(offset).copied().unwrap_or(0)
+    }
 
-            prg_rom_data: prg_data,
-            chr_rom_data: chr_data,
-        };
+    /// Reads a byte of CHR ROM or CHR RAM through the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - PPU address in $0000-$1FFF.
+    ///
+    /// # Return
+    /// * `u8` - Byte mapped to `addr`, 0 if the cartridge has no CHR memory.
+    pub fn read_chr(&self, cart: &Cartridge, addr: u16) -> u8 {
+        let addr = addr as usize & 0x1fff;
+        let offset = self.chr[addr >> 10] + (addr & 0x3ff);
+        chr_memory(cart).get(offset).copied().unwrap_or(0)
+    }
 
-        let mut bus = Bus {
-            cpu: test_cpu,
-            cartridge: cart,
-            cycle: 0,
-            mapper: MMC1 {
-                mirroring: 0,
-                prg_rom_bank_mode: 0,
-                chr_rom_bank_mode: true,
-                chr_bank0: 0,
-                chr_bank1: 0,
-                prg_bank: 0,
-                mmc1b: false,
-                // Stored values
-                shift_register: 0,
-                amount_shifted: 0,
-            },
-            controller: Controller::new(),
-            jam: false,
-        };
-        bus.mapper
-            .write_mapper(0xa000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring,
-                prg_rom_bank_mode,
-                chr_rom_bank_mode,
-                chr_bank0: _,
-                chr_bank1: _,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 2);
-                assert_eq!(shift_register, 0b0_0000);
-                assert_eq!(mirroring, 0);
-                assert_eq!(prg_rom_bank_mode, 0);
-                assert_eq!(chr_rom_bank_mode, true);
-            }
-            _ => panic!("This is a mmc1 test"),
-        }
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring: _,
-                prg_rom_bank_mode: _,
-                chr_rom_bank_mode: _,
-                chr_bank0,
-                chr_bank1: _,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 0);
-                assert_eq!(shift_register, 0b0);
-                assert_eq!(chr_bank0, 0b11100);
+    /// Writes a byte to CHR RAM through the table. Writes to CHR ROM are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - PPU address in $0000-$1FFF.
+    /// * `data` - Data byte that shall be written.
+    ///
+    /// Nothing is returned.
+    pub fn write_chr(&self, cart: &mut Cartridge, addr: u16, data: u8) {
+        let addr = addr as usize & 0x1fff;
+        if cart.chr_rom_data.is_empty() {
+            if let Some(byte) = cart.chr_ram.get_mut(self.chr[addr >> 10] + (addr & 0x3ff)) {
+                *byte = data;
             }
-            _ => panic!("This is a mmc1 test"),
         }
     }
-    #[test]
-    fn test_mmc1_write_chr_bank0() {
-        let mut rom_data: [u8; 0xffff + 1] = [0; 0xffff + 1];
-        for i in 0x8000..=0xffff {
-            rom_data[i] = (i - 0x8000) as u8;
-        }
+}
 
-        let test_cpu = Cpu6502 {
-            a: 0,
-            x: 0,
-            y: 0,
-            pc: 0x8000,
-            sp: 0x01FF,
-
-            carry: false,
-            zero: false,
-            irq_dis: false,
-            dec: false,
-            b: false,
-            overflow: false,
-            negative: false,
-
-            mem: rom_data,
-        };
+/// Returns the CHR memory of the cartridge, which is CHR ROM if there is any and CHR RAM otherwise.
+///
+/// # Arguments
+///
+/// * `cart` - Borrowed instance of cartridge.
+///
+/// # Return
+/// * `&[u8]` - The CHR memory.
+fn chr_memory(cart: &Cartridge) -> &[u8] {
+    if cart.chr_rom_data.is_empty() {
+        &cart.chr_ram
+    } else {
+        &cart.chr_rom_data
+    }
+}
 
-        let prg_rom_size_in_16kb = 1;
-        let chr_rom_size_in_8kb = 1;
-        let mut prg_data: Vec<u8> = Vec::new();
-        let mut chr_data: Vec<u8> = Vec::new();
-        // Fill cartridge and rom with same data
-        for i in 0..16384 * (prg_rom_size_in_16kb as u16) {
-            prg_data.push(i as u8);
-        }
-        for i in 0..8192 * (chr_rom_size_in_8kb as u16) {
-            chr_data.push(i as u8);
-        }
+/// Reads a byte from the PRG RAM at $6000-$7FFF, mirrored if the RAM is smaller than 8 kB.
+///
+/// # Arguments
+///
+/// * `cart` - Borrowed instance of cartridge.
+/// * `addr` - CPU address in $6000-$7FFF.
+///
+/// # Return
+/// * `Option<u8>` - Byte of PRG RAM, `None` if the cartridge has no PRG RAM.
+fn read_prg_ram(cart: &Cartridge, addr: u16) -> Option<u8> {
+    if cart.prg_ram.is_empty() {
+        return None;
+    }
+    Some(cart.prg_ram[(addr as usize - 0x6000) % cart.prg_ram.len()])
+}
 
-        let cart = Cartridge {
-            prg_rom_size_in_16kb,
-            chr_rom_size_in_8kb,
+/// Writes a byte to the PRG RAM at $6000-$7FFF, mirrored if the RAM is smaller than 8 kB.
+///
+/// # Arguments
+///
+/// * `cart` - Borrowed instance of cartridge.
+/// * `addr` - CPU address in $6000-$7FFF.
+/// * `data` - Data byte that shall be written.
+///
+/// Nothing is returned.
+fn write_prg_ram(cart: &mut Cartridge, addr: u16, data: u8) {
+    if !cart.prg_ram.is_empty() {
+        let len = cart.prg_ram.len();
+        cart.prg_ram[(addr as usize - 0x6000) % len] = data;
+    }
+}
 
-            mapper_number: 1,
-            mirroring_control: false,
-            battery_backed_prg_ram_available: false,
-            trainer_available: false,
-            mirroring_mode: false,
+/// Reads a byte from the CHR memory of the cartridge, which is CHR ROM if there is any and CHR RAM otherwise.
+///
+/// # Arguments
+///
+/// * `cart` - Borrowed instance of cartridge.
+/// * `offset` - Offset into the CHR memory, wrapped around its size.
+///
+/// # Return
+/// * `u8` - Byte of CHR memory, 0 if the cartridge has neither CHR ROM nor CHR RAM.
+fn read_chr(cart: &Cartridge, offset: usize) -> u8 {
+    let chr = chr_memory(cart);
+    if chr.is_empty() {
+        return 0;
+    }
+    chr[offset % chr.len()]
+}
 
-            vs_unisystem: false,
-            playchoice_10: false,
-            nes_2_0_format: false,
+/// Writes a byte to the CHR RAM of the cartridge. Writes to CHR ROM are ignored.
+///
+/// # Arguments
+///
+/// * `cart` - Borrowed instance of cartridge.
+/// * `offset` - Offset into the CHR RAM, wrapped around its size.
+/// * `data` - Data byte that shall be written.
+///
+/// Nothing is returned.
+fn write_chr(cart: &mut Cartridge, offset: usize, data: u8) {
+    if cart.chr_rom_data.is_empty() && !cart.chr_ram.is_empty() {
+        let len = cart.chr_ram.len();
+        cart.chr_ram[offset % len] = data;
+    }
+}
 
-            prg_ram_size_in_8kb: 1,
+/// Emulates a bus conflict: boards without a write-enable line on the PRG ROM let the ROM drive the data bus during writes to it as well, so the mapper receives the written value ANDed with the ROM byte.
+///
+/// # Arguments
+///
+/// * `cart` - Borrowed instance of cartridge, its `bus_conflicts` flag tells if the board has bus conflicts.
+/// * `rom_byte` - Byte of PRG ROM at the written address.
+/// * `data` - Data byte written by the CPU.
+///
+/// # Return
+/// * `u8` - The value the mapper latches.
+fn bus_conflict(cart: &Cartridge, rom_byte: u8, data: u8) -> u8 {
+    if cart.bus_conflicts {
+        data & rom_byte
+    } else {
+        data
+    }
+}
 
-            tv_system_mode: false, // Not used for now
+/// Builds a cartridge for the mapper tests where every byte of PRG and CHR ROM holds the number of its bank, so a read shows which bank is mapped.
+///
+/// # Arguments
+///
+/// * `mapper_number` - Mapper of the cartridge.
+/// * `prg_len` - Size of the PRG ROM in bytes.
+/// * `prg_bank` - Size in bytes of the banks the PRG ROM is numbered by.
+/// * `chr_len` - Size of the CHR ROM in bytes, 0 for a board with 8 kB of CHR RAM.
+/// * `chr_bank` - Size in bytes of the banks the CHR ROM is numbered by.
+///
+/// # Return
+/// * `Cartridge` - The cartridge.
+#[cfg(test)]
+fn banked_cart(
+    mapper_number: u16,
+    prg_len: usize,
+    prg_bank: usize,
+    chr_len: usize,
+    chr_bank: usize,
+) -> Cartridge {
+    let chr_ram_size = if chr_len == 0 { 8192 } else { 0 };
+    Cartridge {
+        prg_rom_size_in_16kb: ((prg_len + 16383) / 16384) as u16,
+        chr_rom_size_in_8kb: (chr_len / 8192) as u16,
+        mapper_number,
+        chr_ram_size,
+        prg_rom_data: (0..prg_len).map(|i| (i / prg_bank) as u8).collect(),
+        chr_rom_data: (0..chr_len).map(|i| (i / chr_bank) as u8).collect(),
+        chr_ram: vec![0; chr_ram_size],
+        ..Default::default()
+    }
+}
 
-            prg_ram_present: false,
-            bus_conflicts: false,
+#[cfg(test)]
+mod mapper_tests {
+    use crate::cartridge::RomError;
+    use crate::mapper::{get_mapper, StateError};
+    use crate::Cartridge;
+    use tudelft_nes_ppu::Mirroring;
 
-            prg_rom_data: prg_data,
-            chr_rom_data: chr_data,
+    #[test]
+    fn test_get_mapper() {
+        let vertical_cart = Cartridge {
+            mirroring_control: true,
+            ..Default::default()
         };
+        let nrom = get_mapper(&vertical_cart).unwrap();
+        assert_eq!(nrom.mirroring(&vertical_cart), Mirroring::Vertical);
 
-        let mut bus = Bus {
-            cpu: test_cpu,
-            cartridge: cart,
-            cycle: 0,
-            mapper: MMC1 {
-                mirroring: 0,
-                prg_rom_bank_mode: 0,
-                chr_rom_bank_mode: true,
-                chr_bank0: 0,
-                chr_bank1: 0,
-                prg_bank: 0,
-                mmc1b: false,
-                // Stored values
-                shift_register: 0,
-                amount_shifted: 0,
-            },
-            controller: Controller::new(),
-            jam: false,
+        let mmc1_cart = Cartridge {
+            mapper_number: 1,
+            submapper_number: 5,
+            ..Default::default()
         };
-        bus.mapper
-            .write_mapper(0xa000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring,
-                prg_rom_bank_mode,
-                chr_rom_bank_mode,
-                chr_bank0: _,
-                chr_bank1: _,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 2);
-                assert_eq!(shift_register, 0b0_0000);
-                assert_eq!(mirroring, 0);
-                assert_eq!(prg_rom_bank_mode, 0);
-                assert_eq!(chr_rom_bank_mode, true);
-            }
-            _ => panic!("This is a mmc1 test"),
-        }
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0xa000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring: _,
-                prg_rom_bank_mode: _,
-                chr_rom_bank_mode: _,
-                chr_bank0,
-                chr_bank1: _,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 0);
-                assert_eq!(shift_register, 0b0);
-                assert_eq!(chr_bank0, 0b11100);
-            }
-            _ => panic!("This is a mmc1 test"),
-        }
+        let mut mmc1 = get_mapper(&mmc1_cart).unwrap();
+        assert_eq!(mmc1.save_state().len(), 10);
+        assert_eq!(
+            mmc1.load_state(&mmc1_cart, &[0; 3]),
+            Err(StateError {
+                expected: 10,
+                found: 3
+            })
+        );
     }
-    #[test]
-    fn test_mmc1_write_control() {
-        let mut rom_data: [u8; 0xffff + 1] = [0; 0xffff + 1];
-        for i in 0x8000..=0xffff {
-            rom_data[i] = (i - 0x8000) as u8;
-        }
 
-        let test_cpu = Cpu6502 {
-            a: 0,
-            x: 0,
-            y: 0,
-            pc: 0x8000,
-            sp: 0x01FF,
-
-            carry: false,
-            zero: false,
-            irq_dis: false,
-            dec: false,
-            b: false,
-            overflow: false,
-            negative: false,
-
-            mem: rom_data,
+    #[test]
+    fn test_get_mapper_errors() {
+        let cart_64kb = Cartridge {
+            prg_rom_size_in_16kb: 4,
+            ..Default::default()
         };
+        assert_eq!(
+            get_mapper(&cart_64kb).err(),
+            Some(RomError::OversizedNrom {
+                prg_rom_size_in_16kb: 4
+            })
+        );
+        assert_eq!(
+            get_mapper(&Cartridge {
+                mapper_number: 99,
+                ..Default::default()
+            })
+            .err(),
+            Some(RomError::UnsupportedMapper(99))
+        );
+    }
 
-        let prg_rom_size_in_16kb = 2;
-        let chr_rom_size_in_8kb = 1;
-        let mut prg_data: Vec<u8> = Vec::new();
-        let mut chr_data: Vec<u8> = Vec::new();
-        // Fill cartridge and rom with same data
-        for i in 0..16384 * (prg_rom_size_in_16kb as u16) {
-            prg_data.push(i as u8);
-        }
-        for i in 0..8192 * (chr_rom_size_in_8kb as u16) {
-            chr_data.push(i as u8);
-        }
-
-        let cart = Cartridge {
-            prg_rom_size_in_16kb,
-            chr_rom_size_in_8kb,
-
-            mapper_number: 1,
-            mirroring_control: false,
-            battery_backed_prg_ram_available: false,
-            trainer_available: false,
-            mirroring_mode: false,
-
-            vs_unisystem: false,
-            playchoice_10: false,
-            nes_2_0_format: false,
-
-            prg_ram_size_in_8kb: 1,
-
-            tv_system_mode: false, // Not used for now
-
-            prg_ram_present: false,
-            bus_conflicts: false,
-
-            prg_rom_data: prg_data,
-            chr_rom_data: chr_data,
+    #[test]
+    fn test_prg_ram() {
+        let mut cart = Cartridge {
+            prg_ram: vec![0; 0x800],
+            battery_backed_prg_ram_available: true,
+            ..Default::default()
         };
+        let mut mapper = get_mapper(&cart).unwrap();
+        mapper.cpu_write(&mut cart, 0x6001, 0x42);
+        assert_eq!(mapper.cpu_read(&cart, 0x6801), Some(0x42)); // Mirrored 2 kB
+        assert_eq!(mapper.save_ram(&cart).unwrap()[1], 0x42);
 
-        let mut bus = Bus {
-            cpu: test_cpu,
-            cartridge: cart,
-            cycle: 0,
-            mapper: MMC1 {
-                mirroring: 0,
-                prg_rom_bank_mode: 0,
-                chr_rom_bank_mode: true,
-                chr_bank0: 0,
-                chr_bank1: 0,
-                prg_bank: 0,
-                mmc1b: false,
-                // Stored values
-                shift_register: 0,
-                amount_shifted: 0,
-            },
-            controller: Controller::new(),
-            jam: false,
-        };
-        bus.mapper
-            .write_mapper(0x8000, 0b1111, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0x8000, 0b0111, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring,
-                prg_rom_bank_mode,
-                chr_rom_bank_mode,
-                chr_bank0: _,
-                chr_bank1: _,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 2);
-                assert_eq!(shift_register, 0b1_1000);
-                assert_eq!(mirroring, 0);
-                assert_eq!(prg_rom_bank_mode, 0);
-                assert_eq!(chr_rom_bank_mode, true);
-            }
-            _ => panic!("This is a mmc1 test"),
-        }
-        bus.mapper
-            .write_mapper(0x8000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0x8000, 0b1, &mut bus.cpu.mem, &mut bus.cartridge);
-        bus.mapper
-            .write_mapper(0x8000, 0b0, &mut bus.cpu.mem, &mut bus.cartridge);
-        match bus.mapper {
-            MapperType::MMC1 {
-                mirroring,
-                prg_rom_bank_mode,
-                chr_rom_bank_mode,
-                chr_bank0: _,
-                chr_bank1: _,
-                prg_bank: _,
-                mmc1b: _,
-                // Stored values
-                shift_register,
-                amount_shifted,
-            } => {
-                assert_eq!(amount_shifted, 0);
-                assert_eq!(shift_register, 0b0);
-                assert_eq!(mirroring, 0b11);
-                assert_eq!(prg_rom_bank_mode, 0b11);
-                assert_eq!(chr_rom_bank_mode, false);
-            }
-            _ => panic!("This is a mmc1 test"),
-        }
+        mapper.load_save_ram(&mut cart, &[0x12, 0x34]);
+        assert_eq!(mapper.cpu_read(&cart, 0x7800), Some(0x12));
+        assert_eq!(mapper.cpu_read(&cart, 0x6001), Some(0x34));
+
+        let cart = Cartridge::default();
+        assert_eq!(mapper.save_ram(&cart), None);
     }
 }
@@ -0,0 +1,143 @@
+//! This module provides the AxROM boards (mapper 7), which switch 32 kB of PRG ROM and select one of the two nametables for single-screen mirroring.
+
+use crate::mapper::{bus_conflict, state_registers, BankTable, Mapper, StateError};
+use crate::Cartridge;
+use tudelft_nes_ppu::Mirroring;
+
+/// ANROM/AMROM/AOROM mapper with a single register at $8000-$FFFF.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Axrom {
+    /// 32 kB PRG bank mapped to $8000-$FFFF.
+    pub prg_bank: u8,
+    /// Indicates whether all nametables show the second page of VRAM instead of the first one.
+    pub upper_nametable: bool,
+    /// Offsets of the mapped banks.
+    pub banks: BankTable,
+}
+
+impl Axrom {
+    /// Creates the mapper for a cartridge in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, used for the size of its memory.
+    ///
+    /// # Return
+    /// * `Axrom` - The mapper.
+    pub fn new(cartridge: &Cartridge) -> Axrom {
+        let mut axrom = Axrom::default();
+        axrom.update_banks(cartridge);
+        axrom
+    }
+
+    /// Rebuilds the bank table after the bank register has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// Nothing is returned.
+    fn update_banks(&mut self, cart: &Cartridge) {
+        self.banks
+            .map_prg(cart, 0x8000, 32768, self.prg_bank as usize);
+        self.banks.map_chr(cart, 0x0000, 8192, 0);
+    }
+}
+
+impl Mapper for Axrom {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            return None; // No PRG RAM on these boards
+        }
+        Some(self.banks.read_prg(cart, addr))
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        if addr < 0x8000 {
+            return;
+        }
+        let data = bus_conflict(cart, self.banks.read_prg(cart, addr), data);
+        self.prg_bank = data & 0b111;
+        self.upper_nametable = data & 0b1_0000 != 0;
+        self.update_banks(cart);
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        self.banks.read_chr(cart, addr)
+    }
+
+    fn ppu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        self.banks.write_chr(cart, addr, data);
+    }
+
+    fn mirroring(&self, _cart: &Cartridge) -> Mirroring {
+        if self.upper_nametable {
+            Mirroring::SingleScreenUpper
+        } else {
+            Mirroring::SingleScreenLower
+        }
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![self.prg_bank, self.upper_nametable as u8]
+    }
+
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        let [prg_bank, upper_nametable] = state_registers(state)?;
+        self.prg_bank = prg_bank;
+        self.upper_nametable = upper_nametable != 0;
+        self.update_banks(cart);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod axrom_tests {
+    use crate::mapper::axrom::Axrom;
+    use crate::mapper::{banked_cart, Mapper};
+    use crate::Cartridge;
+    use tudelft_nes_ppu::Mirroring;
+
+    #[test]
+    fn test_axrom_prg() {
+        let mut cart = banked_cart(7, 16 * 16384, 32768, 0, 1);
+        let mut axrom = Axrom::new(&cart);
+        assert_eq!(axrom.cpu_read(&cart, 0x8000), Some(0));
+        assert_eq!(axrom.cpu_read(&cart, 0xFFFF), Some(0));
+
+        axrom.cpu_write(&mut cart, 0x8000, 0b1110_0101);
+        assert_eq!(axrom.cpu_read(&cart, 0x8000), Some(5));
+        assert_eq!(axrom.cpu_read(&cart, 0xFFFF), Some(5));
+    }
+
+    #[test]
+    fn test_axrom_mirroring() {
+        let mut cart = banked_cart(7, 16 * 16384, 32768, 0, 1);
+        let mut axrom = Axrom::new(&cart);
+        assert_eq!(axrom.mirroring(&cart), Mirroring::SingleScreenLower);
+
+        axrom.cpu_write(&mut cart, 0xC000, 0b1_0010);
+        assert_eq!(axrom.mirroring(&cart), Mirroring::SingleScreenUpper);
+        assert_eq!(axrom.prg_bank, 2);
+
+        let state = axrom.save_state();
+        axrom.cpu_write(&mut cart, 0xC000, 0);
+        assert_eq!(axrom.mirroring(&cart), Mirroring::SingleScreenLower);
+        axrom.load_state(&cart, &state).unwrap();
+        assert_eq!(axrom.mirroring(&cart), Mirroring::SingleScreenUpper);
+        assert_eq!(axrom.cpu_read(&cart, 0x8000), Some(2));
+    }
+
+    #[test]
+    fn test_axrom_bus_conflicts() {
+        let mut cart = Cartridge {
+            bus_conflicts: true,
+            ..banked_cart(7, 16 * 16384, 32768, 0, 1)
+        };
+        let mut axrom = Axrom::new(&cart);
+        // Bank 0 holds zeros everywhere, so the ROM pulls every bit low
+        axrom.cpu_write(&mut cart, 0x8000, 0b1_0011);
+        assert_eq!(axrom.prg_bank, 0);
+        assert_eq!(axrom.mirroring(&cart), Mirroring::SingleScreenLower);
+    }
+}
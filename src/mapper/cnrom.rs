@@ -0,0 +1,127 @@
+//! This module provides the CNROM board (mapper 3), which has fixed PRG ROM like NROM and a switchable 8 kB CHR bank.
+
+use crate::mapper::{
+    bus_conflict, read_prg_ram, state_registers, write_prg_ram, BankTable, Mapper, StateError,
+};
+use crate::Cartridge;
+use tudelft_nes_ppu::Mirroring;
+
+/// CNROM mapper with 16 or 32 kB of PRG ROM and an 8 kB CHR bank selected by writes to $8000-$FFFF.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Cnrom {
+    /// 8 kB CHR bank mapped to $0000-$1FFF of the PPU.
+    pub chr_bank: u8,
+    /// Offsets of the mapped banks.
+    pub banks: BankTable,
+}
+
+impl Cnrom {
+    /// Creates the mapper for a cartridge in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, used for the size of its memory.
+    ///
+    /// # Return
+    /// * `Cnrom` - The mapper.
+    pub fn new(cartridge: &Cartridge) -> Cnrom {
+        let mut cnrom = Cnrom::default();
+        cnrom.update_banks(cartridge);
+        cnrom
+    }
+
+    /// Rebuilds the bank table after the bank register has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// Nothing is returned.
+    fn update_banks(&mut self, cart: &Cartridge) {
+        // 16 kB games are mirrored at $C000
+        self.banks.map_prg(cart, 0x8000, 16384, 0);
+        self.banks.map_prg(cart, 0xc000, 16384, 1);
+        self.banks
+            .map_chr(cart, 0x0000, 8192, self.chr_bank as usize);
+    }
+}
+
+impl Mapper for Cnrom {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            return read_prg_ram(cart, addr);
+        }
+        Some(self.banks.read_prg(cart, addr))
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        if addr < 0x8000 {
+            write_prg_ram(cart, addr, data);
+            return;
+        }
+        self.chr_bank = bus_conflict(cart, self.banks.read_prg(cart, addr), data);
+        self.update_banks(cart);
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        self.banks.read_chr(cart, addr)
+    }
+
+    fn ppu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        self.banks.write_chr(cart, addr, data);
+    }
+
+    fn mirroring(&self, cart: &Cartridge) -> Mirroring {
+        cart.mirroring()
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![self.chr_bank]
+    }
+
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        let [chr_bank] = state_registers(state)?;
+        self.chr_bank = chr_bank;
+        self.update_banks(cart);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod cnrom_tests {
+    use crate::mapper::cnrom::Cnrom;
+    use crate::mapper::{banked_cart, Mapper};
+    use crate::Cartridge;
+
+    #[test]
+    fn test_cnrom() {
+        let mut cart = banked_cart(3, 16384, 1, 4 * 8192, 8192);
+        let mut cnrom = Cnrom::new(&cart);
+        assert_eq!(cnrom.cpu_read(&cart, 0x8012), Some(0x12));
+        assert_eq!(cnrom.cpu_read(&cart, 0xC012), Some(0x12));
+        assert_eq!(cnrom.ppu_read(&cart, 0x1fff), 0);
+
+        cnrom.cpu_write(&mut cart, 0x8003, 2);
+        assert_eq!(cnrom.ppu_read(&cart, 0x0000), 2);
+        assert_eq!(cnrom.ppu_read(&cart, 0x1fff), 2);
+
+        // Bank numbers wrap around the CHR ROM size
+        cnrom.cpu_write(&mut cart, 0x8003, 7);
+        assert_eq!(cnrom.ppu_read(&cart, 0x1000), 3);
+    }
+
+    #[test]
+    fn test_cnrom_bus_conflicts() {
+        let mut cart = Cartridge {
+            bus_conflicts: true,
+            ..banked_cart(3, 16384, 1, 4 * 8192, 8192)
+        };
+        let mut cnrom = Cnrom::new(&cart);
+        // $8001 holds 0x01, so only bit 0 survives
+        cnrom.cpu_write(&mut cart, 0x8001, 3);
+        assert_eq!(cnrom.chr_bank, 1);
+        assert_eq!(cnrom.ppu_read(&cart, 0x0000), 1);
+        cnrom.cpu_write(&mut cart, 0x8002, 1);
+        assert_eq!(cnrom.ppu_read(&cart, 0x0000), 0);
+    }
+}
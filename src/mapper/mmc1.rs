@@ -0,0 +1,526 @@
+//! This module provides the Nintendo MMC1 (mapper 1), which is configured through a serial shift register.
+
+use crate::mapper::{state_registers, BankTable, Mapper, StateError};
+use crate::Cartridge;
+use tudelft_nes_ppu::Mirroring;
+
+/// Nintendo MMC1 mapper.
+///
+/// Boards with only 8 kB of CHR (SNROM, SOROM, SUROM, SXROM) use the upper bits of the CHR bank registers to select a 256 kB half of PRG ROM and an 8 kB bank of PRG RAM.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Mmc1 {
+    // Settings
+    /// Nametable mirroring used by the PPU (0 -> one-screen lower, 1 -> one-screen upper, 2 -> vertical, 3 -> horizontal).
+    pub mirroring: u8,
+    /// Specifies the PRGM ROM bank mode.
+    pub prg_rom_bank_mode: u8,
+    /// Specifies the CHR bank mode, `true` for two separate 4 kB banks and `false` for one 8 kB bank.
+    pub chr_rom_bank_mode: bool,
+    /// Specifies which CHR are used by the PPU.
+    pub chr_bank0: u8,
+    /// Specifies which CHR are used by the PPU.
+    pub chr_bank1: u8,
+    /// Location of the switchable bank.
+    pub prg_bank: u8,
+    /// Indicates whether bit 4 of the PRG bank register has disabled the PRG RAM. Only the MMC1B is emulated, the header does not tell it apart from the MMC1A, which ignores this bit.
+    pub prg_ram_disabled: bool,
+
+    /// Register used by load-register.
+    pub shift_register: u8,
+    /// Data bits in the shift register.
+    pub amount_shifted: u8,
+    /// CPU cycles during which writes to the shift register are ignored, as the MMC1 only sees the first of writes on consecutive cycles.
+    pub write_cooldown: u8,
+    /// Offsets of the mapped banks.
+    pub banks: BankTable,
+}
+
+impl Mmc1 {
+    /// Creates the mapper for a cartridge in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, used for the initial mirroring.
+    ///
+    /// # Return
+    /// * `Mmc1` - The mapper.
+    pub fn new(cartridge: &Cartridge) -> Mmc1 {
+        let mut mmc1 = Mmc1 {
+            // Until the game writes the control register, use the mirroring of the header
+            mirroring: match cartridge.mirroring() {
+                Mirroring::Vertical => 2,
+                _ => 3,
+            },
+            // At power-on the last bank is fixed at $C000, so the reset vector can be found
+            prg_rom_bank_mode: 3,
+            ..Default::default()
+        };
+        mmc1.update_banks(cartridge);
+        mmc1
+    }
+
+    /// Returns the CHR bank register whose upper bits drive the extra address lines of boards with 8 kB of CHR. The bits of the register selected for $1000 while the PPU fetches from there are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// # Return
+    /// * `Option<u8>` - The register, `None` if the board uses all bits to bank CHR.
+    fn outer_bank_register(&self, cart: &Cartridge) -> Option<u8> {
+        let chr_size = cart.chr_rom_data.len().max(cart.chr_ram.len());
+        (chr_size <= 8192).then_some(self.chr_bank0)
+    }
+
+    /// Translates an address of the CPU in $6000-$7FFF into an offset in PRG RAM.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    /// * `addr` - CPU address in $6000-$7FFF.
+    ///
+    /// # Return
+    /// * `Option<usize>` - Offset into the PRG RAM, `None` if the RAM is missing or disabled.
+    fn prg_ram_offset(&self, cart: &Cartridge, addr: u16) -> Option<usize> {
+        if cart.prg_ram.is_empty() || self.prg_ram_disabled {
+            return None;
+        }
+        let outer = self.outer_bank_register(cart);
+        // SNROM disables the RAM with bit 4 of the CHR bank, which selects the outer PRG bank on larger boards
+        if cart.prg_rom_data.len() <= 256 * 1024
+            && matches!(outer, Some(bank) if bank & 0b1_0000 != 0)
+        {
+            return None;
+        }
+        let bank = match (cart.prg_ram.len(), outer) {
+            (32768, Some(bank)) => (bank >> 2) & 0b11, // SXROM
+            (16384, Some(bank)) => (bank >> 3) & 0b1,  // SOROM
+            _ => 0,
+        };
+        Some((bank as usize * 8192 + (addr as usize - 0x6000)) % cart.prg_ram.len())
+    }
+
+    /// Rebuilds the bank table after a register has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// Nothing is returned.
+    fn update_banks(&mut self, cart: &Cartridge) {
+        // SUROM and SXROM select a 256 kB half of PRG ROM with bit 4 of the CHR bank
+        let outer = match self.outer_bank_register(cart) {
+            Some(bank) if cart.prg_rom_data.len() > 256 * 1024 => (bank & 0b1_0000) as usize,
+            _ => 0,
+        };
+        let bank = outer | (self.prg_bank & 0b1111) as usize;
+        match self.prg_rom_bank_mode {
+            // Switch 32 kB at $8000, ignoring the low bit of the bank number
+            0 | 1 => self.banks.map_prg(cart, 0x8000, 32768, bank >> 1),
+            // Fix first bank at $8000 and switch 16 kB bank at $C000
+            2 => {
+                self.banks.map_prg(cart, 0x8000, 16384, outer);
+                self.banks.map_prg(cart, 0xc000, 16384, bank);
+            }
+            // Fix last bank at $C000 and switch 16 kB bank at $8000
+            _ => {
+                self.banks.map_prg(cart, 0x8000, 16384, bank);
+                self.banks.map_prg(cart, 0xc000, 16384, outer | 0b1111);
+            }
+        }
+
+        if self.chr_rom_bank_mode {
+            self.banks
+                .map_chr(cart, 0x0000, 4096, self.chr_bank0 as usize);
+            self.banks
+                .map_chr(cart, 0x1000, 4096, self.chr_bank1 as usize);
+        } else {
+            // 8 kB mode ignores the low bit of the bank number
+            self.banks
+                .map_chr(cart, 0x0000, 8192, self.chr_bank0 as usize >> 1);
+        }
+    }
+}
+
+impl Mapper for Mmc1 {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            return self
+                .prg_ram_offset(cart, addr)
+                .map(|offset| cart.prg_ram[offset]);
+        }
+        Some(self.banks.read_prg(cart, addr))
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        if addr < 0x8000 {
+            if let Some(offset) = self.prg_ram_offset(cart, addr) {
+                cart.prg_ram[offset] = data;
+            }
+            return;
+        }
+        // Read-modify-write instructions write twice in a row, only the first write counts
+        if self.write_cooldown > 0 {
+            return;
+        }
+        self.write_cooldown = 2;
+
+        if data & 0x80 == 0x80 {
+            self.shift_register = 0;
+            self.amount_shifted = 0;
+            self.prg_rom_bank_mode = 0b11;
+            self.update_banks(cart);
+            return;
+        }
+        self.shift_register >>= 1;
+        self.shift_register |= (data & 0x1) << 4;
+        self.amount_shifted += 1;
+        if self.amount_shifted == 5 {
+            if (0x8000..=0x9fff).contains(&addr) {
+                self.mirroring = self.shift_register & 0b11;
+                self.prg_rom_bank_mode = (self.shift_register & 0b1100) >> 2;
+                self.chr_rom_bank_mode = (self.shift_register & 0b1_0000) == 0b1_0000;
+            } else if (0xa000..=0xbfff).contains(&addr) {
+                self.chr_bank0 = self.shift_register & 0b1_1111;
+            } else if (0xc000..=0xdfff).contains(&addr) {
+                self.chr_bank1 = self.shift_register & 0b1_1111;
+            } else if (0xe000..=0xffff).contains(&addr) {
+                self.prg_bank = self.shift_register & 0b1111;
+                self.prg_ram_disabled = self.shift_register & 0b1_0000 == 0b1_0000;
+            }
+            self.shift_register = 0;
+            self.amount_shifted = 0;
+            self.update_banks(cart);
+        }
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        self.banks.read_chr(cart, addr)
+    }
+
+    fn ppu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        self.banks.write_chr(cart, addr, data);
+    }
+
+    fn mirroring(&self, _cart: &Cartridge) -> Mirroring {
+        match self.mirroring & 0b11 {
+            0 => Mirroring::SingleScreenLower,
+            1 => Mirroring::SingleScreenUpper,
+            2 => Mirroring::Vertical,
+            _ => Mirroring::Horizontal,
+        }
+    }
+
+    fn notify_cpu_cycle(&mut self) {
+        self.write_cooldown = self.write_cooldown.saturating_sub(1);
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![
+            self.mirroring,
+            self.prg_rom_bank_mode,
+            self.chr_rom_bank_mode as u8,
+            self.chr_bank0,
+            self.chr_bank1,
+            self.prg_bank,
+            self.prg_ram_disabled as u8,
+            self.shift_register,
+            self.amount_shifted,
+            self.write_cooldown,
+        ]
+    }
+
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        let state: [u8; 10] = state_registers(state)?;
+        *self = Mmc1 {
+            mirroring: state[0] & 0b11,
+            prg_rom_bank_mode: state[1] & 0b11,
+            chr_rom_bank_mode: state[2] != 0,
+            chr_bank0: state[3] & 0b1_1111,
+            chr_bank1: state[4] & 0b1_1111,
+            prg_bank: state[5] & 0b1111,
+            prg_ram_disabled: state[6] != 0,
+            shift_register: state[7] & 0b1_1111,
+            amount_shifted: state[8] % 5,
+            write_cooldown: state[9].min(2),
+            banks: BankTable::default(),
+        };
+        self.update_banks(cart);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod mmc1_tests {
+    use crate::mapper::mmc1::Mmc1;
+    use crate::mapper::{banked_cart, Mapper};
+    use crate::Cartridge;
+    use tudelft_nes_ppu::Mirroring;
+
+    /// Writes to the mapper like a store instruction, a few cycles after the previous write.
+    fn write(mmc1: &mut Mmc1, cart: &mut Cartridge, addr: u16, data: u8) {
+        for _ in 0..4 {
+            mmc1.notify_cpu_cycle();
+        }
+        mmc1.cpu_write(cart, addr, data);
+    }
+
+    /// Writes a 5-bit value to a register through the shift register.
+    fn write_register(mmc1: &mut Mmc1, cart: &mut Cartridge, addr: u16, value: u8) {
+        for i in 0..5 {
+            write(mmc1, cart, addr, (value >> i) & 1);
+        }
+    }
+
+    #[test]
+    fn test_mirroring() {
+        let mut cart = Cartridge {
+            mirroring_control: true,
+            ..banked_cart(1, 2 * 16384, 16384, 0, 1)
+        };
+        let mut mmc1 = Mmc1::new(&cart);
+        assert_eq!(mmc1.mirroring(&cart), Mirroring::Vertical);
+
+        // Write 0b0_0001 to the control register: one-screen upper
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b0_0001);
+        assert_eq!(mmc1.mirroring(&cart), Mirroring::SingleScreenUpper);
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b0_0011);
+        assert_eq!(mmc1.mirroring(&cart), Mirroring::Horizontal);
+    }
+
+    #[test]
+    fn test_mmc1_read_prg() {
+        let mut cart = banked_cart(1, 8 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        // Power-on: bank 0 at $8000, last bank at $C000
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(0));
+        assert_eq!(mmc1.cpu_read(&cart, 0xFFFF), Some(7));
+
+        // PRG bank 5
+        write_register(&mut mmc1, &mut cart, 0xE000, 5);
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(5));
+        assert_eq!(mmc1.cpu_read(&cart, 0xC000), Some(7));
+
+        // 32 kB mode uses banks 4 and 5
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b0_0000);
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(4));
+        assert_eq!(mmc1.cpu_read(&cart, 0xC000), Some(5));
+
+        // First bank fixed at $8000
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b0_1000);
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(0));
+        assert_eq!(mmc1.cpu_read(&cart, 0xC000), Some(5));
+    }
+
+    #[test]
+    fn test_mmc1_write_prg() {
+        let mut cart = banked_cart(1, 16 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1 {
+            chr_rom_bank_mode: true,
+            ..Default::default()
+        };
+        write(&mut mmc1, &mut cart, 0xa000, 0b0);
+        write(&mut mmc1, &mut cart, 0xa000, 0b0);
+        assert_eq!(mmc1.amount_shifted, 2);
+        assert_eq!(mmc1.shift_register, 0b0_0000);
+        assert_eq!(mmc1.prg_bank, 0);
+        assert!(!mmc1.prg_ram_disabled);
+
+        write(&mut mmc1, &mut cart, 0xa000, 0b1);
+        write(&mut mmc1, &mut cart, 0xa000, 0b1);
+        write(&mut mmc1, &mut cart, 0xe000, 0b1);
+        assert_eq!(mmc1.amount_shifted, 0);
+        assert_eq!(mmc1.shift_register, 0b0);
+        assert_eq!(mmc1.prg_bank, 0b1100);
+        assert!(mmc1.prg_ram_disabled);
+    }
+
+    #[test]
+    fn test_mmc1_write_chr_bank0() {
+        let mut cart = banked_cart(1, 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1 {
+            chr_rom_bank_mode: true,
+            ..Default::default()
+        };
+        write(&mut mmc1, &mut cart, 0xa000, 0b0);
+        write(&mut mmc1, &mut cart, 0xa000, 0b0);
+        assert_eq!(mmc1.amount_shifted, 2);
+        assert_eq!(mmc1.shift_register, 0b0_0000);
+        assert_eq!(mmc1.mirroring, 0);
+        assert_eq!(mmc1.prg_rom_bank_mode, 0);
+        assert!(mmc1.chr_rom_bank_mode);
+
+        write(&mut mmc1, &mut cart, 0xa000, 0b1);
+        write(&mut mmc1, &mut cart, 0xa000, 0b1);
+        write(&mut mmc1, &mut cart, 0xa000, 0b1);
+        assert_eq!(mmc1.amount_shifted, 0);
+        assert_eq!(mmc1.shift_register, 0b0);
+        assert_eq!(mmc1.chr_bank0, 0b11100);
+    }
+
+    #[test]
+    fn test_mmc1_write_chr_bank1() {
+        let mut cart = banked_cart(1, 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::default();
+        write_register(&mut mmc1, &mut cart, 0xc000, 0b1_0110);
+        assert_eq!(mmc1.chr_bank1, 0b1_0110);
+        assert_eq!(mmc1.chr_bank0, 0);
+    }
+
+    #[test]
+    fn test_mmc1_chr_ram() {
+        let mut cart = banked_cart(1, 2 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        write_register(&mut mmc1, &mut cart, 0xc000, 1);
+        mmc1.ppu_write(&mut cart, 0x0123, 0x45);
+        mmc1.ppu_write(&mut cart, 0x1123, 0x67);
+        assert_eq!(mmc1.ppu_read(&cart, 0x0123), 0x45);
+        assert_eq!(mmc1.ppu_read(&cart, 0x1123), 0x67);
+        assert_eq!(cart.chr_ram[0x1123], 0x67);
+    }
+
+    #[test]
+    fn test_mmc1_write_control() {
+        let mut cart = banked_cart(1, 2 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1 {
+            chr_rom_bank_mode: true,
+            ..Default::default()
+        };
+        write(&mut mmc1, &mut cart, 0x8000, 0b1111);
+        write(&mut mmc1, &mut cart, 0x8000, 0b0111);
+        assert_eq!(mmc1.amount_shifted, 2);
+        assert_eq!(mmc1.shift_register, 0b1_1000);
+        assert_eq!(mmc1.mirroring, 0);
+        assert_eq!(mmc1.prg_rom_bank_mode, 0);
+        assert!(mmc1.chr_rom_bank_mode);
+
+        write(&mut mmc1, &mut cart, 0x8000, 0b1);
+        write(&mut mmc1, &mut cart, 0x8000, 0b1);
+        write(&mut mmc1, &mut cart, 0x8000, 0b0);
+        assert_eq!(mmc1.amount_shifted, 0);
+        assert_eq!(mmc1.shift_register, 0b0);
+        assert_eq!(mmc1.mirroring, 0b11);
+        assert_eq!(mmc1.prg_rom_bank_mode, 0b11);
+        assert!(!mmc1.chr_rom_bank_mode);
+
+        // Reset bit
+        write(&mut mmc1, &mut cart, 0x8000, 0b1);
+        write(&mut mmc1, &mut cart, 0x8000, 0x80);
+        assert_eq!(mmc1.amount_shifted, 0);
+        assert_eq!(mmc1.prg_rom_bank_mode, 0b11);
+    }
+
+    #[test]
+    fn test_mmc1_chr_modes() {
+        let mut cart = banked_cart(1, 2 * 16384, 16384, 4 * 8192, 4096);
+        let mut mmc1 = Mmc1::new(&cart);
+        write_register(&mut mmc1, &mut cart, 0xa000, 5);
+        write_register(&mut mmc1, &mut cart, 0xc000, 2);
+
+        // 8 kB mode ignores the low bit of CHR bank 0 and CHR bank 1 entirely
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b0_1100);
+        assert_eq!(mmc1.ppu_read(&cart, 0x0000), 4);
+        assert_eq!(mmc1.ppu_read(&cart, 0x1fff), 5);
+
+        // 4 kB mode
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b1_1100);
+        assert_eq!(mmc1.ppu_read(&cart, 0x0000), 5);
+        assert_eq!(mmc1.ppu_read(&cart, 0x1fff), 2);
+    }
+
+    #[test]
+    fn test_mmc1_surom() {
+        // 512 kB PRG ROM with 8 kB CHR RAM
+        let mut cart = banked_cart(1, 32 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        write_register(&mut mmc1, &mut cart, 0xe000, 2);
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(2));
+        assert_eq!(mmc1.cpu_read(&cart, 0xc000), Some(15));
+
+        // Bit 4 of the CHR bank selects the second 256 kB, including the fixed bank
+        write_register(&mut mmc1, &mut cart, 0xa000, 0b1_0000);
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(18));
+        assert_eq!(mmc1.cpu_read(&cart, 0xc000), Some(31));
+
+        write_register(&mut mmc1, &mut cart, 0x8000, 0b0_1000);
+        assert_eq!(mmc1.cpu_read(&cart, 0x8000), Some(16));
+        assert_eq!(mmc1.cpu_read(&cart, 0xc000), Some(18));
+    }
+
+    #[test]
+    fn test_mmc1_prg_ram_banks() {
+        // SXROM with 32 kB PRG RAM
+        let mut sxrom = Cartridge {
+            prg_ram: vec![0; 32768],
+            ..banked_cart(1, 32 * 16384, 16384, 0, 1)
+        };
+        let mut mmc1 = Mmc1::new(&sxrom);
+        write_register(&mut mmc1, &mut sxrom, 0xa000, 0b1_1000);
+        write(&mut mmc1, &mut sxrom, 0x6000, 0x42);
+        assert_eq!(sxrom.prg_ram[2 * 8192], 0x42);
+        assert_eq!(mmc1.cpu_read(&sxrom, 0xc000), Some(31));
+
+        // SOROM with 16 kB PRG RAM
+        let mut sorom = Cartridge {
+            prg_ram: vec![0; 16384],
+            ..banked_cart(1, 8 * 16384, 16384, 0, 1)
+        };
+        let mut mmc1 = Mmc1::new(&sorom);
+        write_register(&mut mmc1, &mut sorom, 0xa000, 0b0_1000);
+        write(&mut mmc1, &mut sorom, 0x7fff, 0x42);
+        assert_eq!(sorom.prg_ram[16383], 0x42);
+
+        // SNROM disables the PRG RAM with bit 4
+        let mut cart = banked_cart(1, 8 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        write(&mut mmc1, &mut cart, 0x6000, 0x42);
+        write_register(&mut mmc1, &mut cart, 0xa000, 0b1_0000);
+        assert_eq!(mmc1.cpu_read(&cart, 0x6000), None);
+        write_register(&mut mmc1, &mut cart, 0xa000, 0);
+        assert_eq!(mmc1.cpu_read(&cart, 0x6000), Some(0x42));
+    }
+
+    #[test]
+    fn test_mmc1_prg_ram_enable() {
+        let mut cart = banked_cart(1, 8 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        write(&mut mmc1, &mut cart, 0x6000, 0x42);
+        write_register(&mut mmc1, &mut cart, 0xe000, 0b1_0000);
+        assert_eq!(mmc1.cpu_read(&cart, 0x6000), None);
+        write(&mut mmc1, &mut cart, 0x6000, 0x43);
+        assert_eq!(cart.prg_ram[0], 0x42);
+    }
+
+    #[test]
+    fn test_mmc1_consecutive_writes() {
+        let mut cart = banked_cart(1, 8 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        // Like INC $E000: the unchanged value is written first, the second write is ignored
+        write(&mut mmc1, &mut cart, 0xe000, 1);
+        mmc1.notify_cpu_cycle();
+        mmc1.cpu_write(&mut cart, 0xe000, 0);
+        assert_eq!(mmc1.amount_shifted, 1);
+        assert_eq!(mmc1.shift_register, 0b1_0000);
+
+        // The reset bit is ignored as well
+        mmc1.cpu_write(&mut cart, 0xe000, 0x80);
+        assert_eq!(mmc1.amount_shifted, 1);
+        mmc1.notify_cpu_cycle();
+        mmc1.notify_cpu_cycle();
+        mmc1.cpu_write(&mut cart, 0xe000, 0x80);
+        assert_eq!(mmc1.amount_shifted, 0);
+    }
+
+    #[test]
+    fn test_mmc1_state() {
+        let mut cart = banked_cart(1, 2 * 16384, 16384, 0, 1);
+        let mut mmc1 = Mmc1::new(&cart);
+        write_register(&mut mmc1, &mut cart, 0xe000, 0b0_0001);
+        write(&mut mmc1, &mut cart, 0xa000, 1);
+        let state = mmc1.save_state();
+
+        let mut restored = Mmc1::default();
+        restored.load_state(&cart, &state).unwrap();
+        assert_eq!(restored, mmc1);
+    }
+}
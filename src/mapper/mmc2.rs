@@ -0,0 +1,219 @@
+//! This module provides the MMC2 (mapper 9) and MMC4 (mapper 10), which switch CHR banks by themselves when the PPU fetches the tiles $FD or $FE.
+
+use crate::mapper::{
+    read_chr, read_prg_ram, state_registers, write_prg_ram, BankTable, Mapper, StateError,
+};
+use crate::Cartridge;
+use std::cell::Cell;
+use tudelft_nes_ppu::Mirroring;
+
+/// MMC2/MMC4 mapper, both pattern tables have two 4 kB banks of which a latch selects one.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Mmc2 {
+    /// Indicates whether the board is an MMC4, which switches 16 kB of PRG ROM instead of 8 kB and reacts to all eight rows of the latch tiles.
+    pub mmc4: bool,
+    /// Switchable PRG bank mapped to $8000.
+    pub prg_bank: u8,
+    /// 4 kB CHR banks for $0000 with latch $FD, $0000 with latch $FE, $1000 with latch $FD and $1000 with latch $FE.
+    pub chr_banks: [u8; 4],
+    /// Latches of both pattern tables, `true` if tile $FE was fetched last and `false` for $FD. These change while the PPU reads, which it does through a shared reference.
+    pub latches: [Cell<bool>; 2],
+    /// Indicates whether the nametables are mirrored horizontally instead of vertically.
+    pub horizontal_mirroring: bool,
+    /// Offsets of the mapped PRG banks, the CHR banks depend on the latches and are resolved while reading.
+    pub banks: BankTable,
+}
+
+impl Mmc2 {
+    /// Creates the mapper for a cartridge in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, mapper 10 is an MMC4.
+    ///
+    /// # Return
+    /// * `Mmc2` - The mapper.
+    pub fn new(cartridge: &Cartridge) -> Mmc2 {
+        let mut mmc2 = Mmc2 {
+            mmc4: cartridge.mapper_number == 10,
+            latches: [Cell::new(true), Cell::new(true)],
+            ..Default::default()
+        };
+        mmc2.update_banks(cartridge);
+        mmc2
+    }
+
+    /// Rebuilds the bank table after the PRG bank register has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// Nothing is returned.
+    fn update_banks(&mut self, cart: &Cartridge) {
+        if self.mmc4 {
+            // 16 kB switchable bank followed by the last 16 kB bank
+            let last = (cart.prg_rom_data.len() / 16384).max(1) - 1;
+            self.banks
+                .map_prg(cart, 0x8000, 16384, self.prg_bank as usize);
+            self.banks.map_prg(cart, 0xc000, 16384, last);
+        } else {
+            // 8 kB switchable bank followed by the last three 8 kB banks
+            let last = (cart.prg_rom_data.len() / 8192).max(3) - 3;
+            self.banks
+                .map_prg(cart, 0x8000, 8192, self.prg_bank as usize);
+            for (i, addr) in [0xa000, 0xc000, 0xe000].into_iter().enumerate() {
+                self.banks.map_prg(cart, addr, 8192, last + i);
+            }
+        }
+    }
+}
+
+impl Mapper for Mmc2 {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            return read_prg_ram(cart, addr);
+        }
+        Some(self.banks.read_prg(cart, addr))
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        match addr {
+            0x6000..=0x7fff => write_prg_ram(cart, addr, data),
+            0xa000..=0xafff => {
+                self.prg_bank = data & 0x0f;
+                self.update_banks(cart);
+            }
+            0xb000..=0xefff => {
+                self.chr_banks[(addr as usize - 0xb000) / 0x1000] = data & 0x1f;
+            }
+            0xf000..=0xffff => self.horizontal_mirroring = data & 1 == 1,
+            _ => {}
+        }
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        let table = (addr as usize >> 12) & 1;
+        let bank = self.chr_banks[table * 2 + self.latches[table].get() as usize];
+        let data = read_chr(cart, bank as usize * 4096 + (addr as usize & 0xfff));
+
+        // The latch switches after the fetch, so the latch tile itself is drawn from the old bank
+        let tile = addr & 0x0ff8;
+        if (tile == 0x0fd8 || tile == 0x0fe8) && (self.mmc4 || table == 1 || addr & 0b111 == 0) {
+            self.latches[table].set(tile == 0x0fe8);
+        }
+        data
+    }
+
+    fn mirroring(&self, _cart: &Cartridge) -> Mirroring {
+        if self.horizontal_mirroring {
+            Mirroring::Horizontal
+        } else {
+            Mirroring::Vertical
+        }
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = vec![self.prg_bank];
+        state.extend_from_slice(&self.chr_banks);
+        state.extend([
+            self.latches[0].get() as u8,
+            self.latches[1].get() as u8,
+            self.horizontal_mirroring as u8,
+        ]);
+        state
+    }
+
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        let [prg_bank, chr0, chr1, chr2, chr3, latch0, latch1, horizontal_mirroring] =
+            state_registers(state)?;
+        self.prg_bank = prg_bank;
+        self.chr_banks = [chr0, chr1, chr2, chr3];
+        self.latches[0].set(latch0 != 0);
+        self.latches[1].set(latch1 != 0);
+        self.horizontal_mirroring = horizontal_mirroring != 0;
+        self.update_banks(cart);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod mmc2_tests {
+    use crate::mapper::mmc2::Mmc2;
+    use crate::mapper::{banked_cart, Mapper};
+    use tudelft_nes_ppu::Mirroring;
+
+    #[test]
+    fn test_mmc2_prg() {
+        let mut cart = banked_cart(9, 8 * 16384, 8192, 16 * 8192, 4096);
+        let mut mmc2 = Mmc2::new(&cart);
+        mmc2.cpu_write(&mut cart, 0xa000, 3);
+        assert_eq!(mmc2.cpu_read(&cart, 0x8000), Some(3));
+        assert_eq!(mmc2.cpu_read(&cart, 0xa000), Some(13));
+        assert_eq!(mmc2.cpu_read(&cart, 0xc000), Some(14));
+        assert_eq!(mmc2.cpu_read(&cart, 0xffff), Some(15));
+    }
+
+    #[test]
+    fn test_mmc4_prg() {
+        let mut cart = banked_cart(10, 8 * 16384, 8192, 16 * 8192, 4096);
+        let mut mmc4 = Mmc2::new(&cart);
+        assert!(mmc4.mmc4);
+        mmc4.cpu_write(&mut cart, 0xa000, 3);
+        assert_eq!(mmc4.cpu_read(&cart, 0x8000), Some(6));
+        assert_eq!(mmc4.cpu_read(&cart, 0xbfff), Some(7));
+        assert_eq!(mmc4.cpu_read(&cart, 0xc000), Some(14));
+        assert_eq!(mmc4.cpu_read(&cart, 0xffff), Some(15));
+    }
+
+    #[test]
+    fn test_mmc2_latches() {
+        let mut cart = banked_cart(9, 8 * 16384, 8192, 16 * 8192, 4096);
+        let mut mmc2 = Mmc2::new(&cart);
+        for (i, addr) in (0xb000..=0xe000).step_by(0x1000).enumerate() {
+            mmc2.cpu_write(&mut cart, addr, 4 + i as u8);
+        }
+        assert_eq!(mmc2.ppu_read(&cart, 0x0000), 5);
+        assert_eq!(mmc2.ppu_read(&cart, 0x1000), 7);
+
+        // Only the first row of tile $FD switches the left latch
+        assert_eq!(mmc2.ppu_read(&cart, 0x0fd9), 5);
+        assert_eq!(mmc2.ppu_read(&cart, 0x0000), 5);
+        assert_eq!(mmc2.ppu_read(&cart, 0x0fd8), 5);
+        assert_eq!(mmc2.ppu_read(&cart, 0x0000), 4);
+
+        // The right latch reacts to every row
+        assert_eq!(mmc2.ppu_read(&cart, 0x1fdd), 7);
+        assert_eq!(mmc2.ppu_read(&cart, 0x1000), 6);
+        mmc2.ppu_read(&cart, 0x1fef);
+        assert_eq!(mmc2.ppu_read(&cart, 0x1000), 7);
+        assert_eq!(mmc2.ppu_read(&cart, 0x0000), 4);
+    }
+
+    #[test]
+    fn test_mmc4_latches() {
+        let cart = banked_cart(10, 8 * 16384, 8192, 16 * 8192, 4096);
+        let mmc4 = Mmc2::new(&cart);
+        mmc4.ppu_read(&cart, 0x0fdb);
+        assert!(!mmc4.latches[0].get());
+        mmc4.ppu_read(&cart, 0x0fef);
+        assert!(mmc4.latches[0].get());
+    }
+
+    #[test]
+    fn test_mmc2_mirroring_and_state() {
+        let mut cart = banked_cart(9, 8 * 16384, 8192, 16 * 8192, 4096);
+        let mut mmc2 = Mmc2::new(&cart);
+        assert_eq!(mmc2.mirroring(&cart), Mirroring::Vertical);
+        mmc2.cpu_write(&mut cart, 0xf000, 1);
+        assert_eq!(mmc2.mirroring(&cart), Mirroring::Horizontal);
+
+        mmc2.cpu_write(&mut cart, 0xc000, 9);
+        mmc2.ppu_read(&cart, 0x1fd8);
+        let state = mmc2.save_state();
+        let mut restored = Mmc2::new(&cart);
+        restored.load_state(&cart, &state).unwrap();
+        assert_eq!(restored, mmc2);
+        assert!(restored.load_state(&cart, &state[1..]).is_err());
+    }
+}
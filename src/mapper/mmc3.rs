@@ -0,0 +1,332 @@
+//! This module provides the Nintendo MMC3 (mapper 4), which has eight bank registers and a scanline counter that raises IRQs.
+
+use crate::mapper::{read_prg_ram, state_registers, BankTable, Mapper, StateError};
+use crate::Cartridge;
+use tudelft_nes_ppu::Mirroring;
+
+/// Nintendo MMC3 mapper.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Mmc3 {
+    /// Bank registers R0-R7: R0-R1 select 2 kB CHR banks, R2-R5 1 kB CHR banks, R6-R7 8 kB PRG banks.
+    pub registers: [u8; 8],
+    /// Bank select register ($8000): bits 0-2 select the register written by $8001, bit 6 the PRG mode and bit 7 the CHR inversion.
+    pub bank_select: u8,
+    /// Indicates whether the nametables are mirrored horizontally (vertically if false).
+    pub horizontal_mirroring: bool,
+    /// Indicates whether the PRG RAM is enabled.
+    pub prg_ram_enabled: bool,
+    /// Indicates whether writes to the PRG RAM are ignored.
+    pub prg_ram_write_protected: bool,
+
+    /// Value the IRQ counter is reloaded with.
+    pub irq_latch: u8,
+    /// Scanline counter, an IRQ is raised when it reaches 0.
+    pub irq_counter: u8,
+    /// Indicates whether the counter is reloaded on the next clock.
+    pub irq_reload: bool,
+    /// Indicates whether the counter may raise IRQs.
+    pub irq_enabled: bool,
+    /// Indicates whether the IRQ line is pulled low.
+    pub irq_pending: bool,
+    /// Offsets of the mapped banks.
+    pub banks: BankTable,
+}
+
+impl Default for Mmc3 {
+    /// Implements the trait `Default` for Mmc3 which returns the mapper in its power-on state.
+    ///
+    /// # Return
+    /// * `Self` - Instance of the struct in the default state.
+    fn default() -> Self {
+        Mmc3 {
+            registers: [0, 2, 4, 5, 6, 7, 0, 1],
+            bank_select: 0,
+            horizontal_mirroring: false,
+            prg_ram_enabled: true,
+            prg_ram_write_protected: false,
+            irq_latch: 0,
+            irq_counter: 0,
+            irq_reload: false,
+            irq_enabled: false,
+            irq_pending: false,
+            banks: BankTable::default(),
+        }
+    }
+}
+
+impl Mmc3 {
+    /// Creates the mapper for a cartridge in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, used for the position of the fixed banks.
+    ///
+    /// # Return
+    /// * `Mmc3` - The mapper.
+    pub fn new(cartridge: &Cartridge) -> Mmc3 {
+        let mut mmc3 = Mmc3::default();
+        mmc3.update_banks(cartridge);
+        mmc3
+    }
+
+    /// Rebuilds the bank table after a bank register has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// Nothing is returned.
+    fn update_banks(&mut self, cart: &Cartridge) {
+        let banks = (cart.prg_rom_data.len() / 8192).max(1);
+        let second_last = banks.saturating_sub(2);
+        let (r6, r7) = (self.registers[6] as usize, self.registers[7] as usize);
+        // PRG mode 1 swaps $8000 and $C000
+        let (bank_8000, bank_c000) = if self.bank_select & 0b0100_0000 != 0 {
+            (second_last, r6)
+        } else {
+            (r6, second_last)
+        };
+        self.banks.map_prg(cart, 0x8000, 8192, bank_8000);
+        self.banks.map_prg(cart, 0xa000, 8192, r7);
+        self.banks.map_prg(cart, 0xc000, 8192, bank_c000);
+        self.banks.map_prg(cart, 0xe000, 8192, banks - 1);
+
+        // With CHR inversion the 2 kB banks are at $1000 and the 1 kB banks at $0000
+        let (two_kb, one_kb) = if self.bank_select & 0b1000_0000 != 0 {
+            (0x1000, 0x0000)
+        } else {
+            (0x0000, 0x1000)
+        };
+        for (i, &register) in self.registers[..2].iter().enumerate() {
+            let addr = two_kb + i as u16 * 0x800;
+            self.banks.map_chr(cart, addr, 2048, register as usize >> 1);
+        }
+        for (i, &register) in self.registers[2..6].iter().enumerate() {
+            let addr = one_kb + i as u16 * 0x400;
+            self.banks.map_chr(cart, addr, 1024, register as usize);
+        }
+    }
+}
+
+impl Mapper for Mmc3 {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            if !self.prg_ram_enabled {
+                return None;
+            }
+            return read_prg_ram(cart, addr);
+        }
+        Some(self.banks.read_prg(cart, addr))
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        let even = addr & 1 == 0;
+        match addr {
+            0x6000..=0x7fff
+                if self.prg_ram_enabled
+                    && !self.prg_ram_write_protected
+                    && !cart.prg_ram.is_empty() =>
+            {
+                let len = cart.prg_ram.len();
+                cart.prg_ram[(addr as usize - 0x6000) % len] = data;
+            }
+            0x6000..=0x7fff => {}
+            0x8000..=0x9fff if even => {
+                self.bank_select = data;
+                self.update_banks(cart);
+            }
+            0x8000..=0x9fff => {
+                let register = (self.bank_select & 0b111) as usize;
+                self.registers[register] = if register >= 6 { data & 0x3f } else { data };
+                self.update_banks(cart);
+            }
+            0xa000..=0xbfff if even => self.horizontal_mirroring = data & 1 == 1,
+            0xa000..=0xbfff => {
+                self.prg_ram_enabled = data & 0b1000_0000 != 0;
+                self.prg_ram_write_protected = data & 0b0100_0000 != 0;
+            }
+            0xc000..=0xdfff if even => self.irq_latch = data,
+            0xc000..=0xdfff => {
+                self.irq_counter = 0;
+                self.irq_reload = true;
+            }
+            0xe000..=0xffff if even => {
+                self.irq_enabled = false;
+                self.irq_pending = false;
+            }
+            0xe000..=0xffff => self.irq_enabled = true,
+            _ => {}
+        }
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        self.banks.read_chr(cart, addr)
+    }
+
+    fn ppu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        self.banks.write_chr(cart, addr, data);
+    }
+
+    fn mirroring(&self, cart: &Cartridge) -> Mirroring {
+        if cart.mirroring_mode {
+            Mirroring::FourScreen
+        } else if self.horizontal_mirroring {
+            Mirroring::Horizontal
+        } else {
+            Mirroring::Vertical
+        }
+    }
+
+    fn irq(&self) -> bool {
+        self.irq_pending
+    }
+
+    fn notify_scanline(&mut self) {
+        if self.irq_counter == 0 || self.irq_reload {
+            self.irq_counter = self.irq_latch;
+            self.irq_reload = false;
+        } else {
+            self.irq_counter -= 1;
+        }
+        if self.irq_counter == 0 && self.irq_enabled {
+            self.irq_pending = true;
+        }
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = self.registers.to_vec();
+        state.extend([
+            self.bank_select,
+            self.horizontal_mirroring as u8,
+            self.prg_ram_enabled as u8,
+            self.prg_ram_write_protected as u8,
+            self.irq_latch,
+            self.irq_counter,
+            self.irq_reload as u8,
+            self.irq_enabled as u8,
+            self.irq_pending as u8,
+        ]);
+        state
+    }
+
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        let state: [u8; 17] = state_registers(state)?;
+        *self = Mmc3 {
+            registers: [
+                state[0],
+                state[1],
+                state[2],
+                state[3],
+                state[4],
+                state[5],
+                state[6] & 0x3f,
+                state[7] & 0x3f,
+            ],
+            bank_select: state[8],
+            horizontal_mirroring: state[9] != 0,
+            prg_ram_enabled: state[10] != 0,
+            prg_ram_write_protected: state[11] != 0,
+            irq_latch: state[12],
+            irq_counter: state[13],
+            irq_reload: state[14] != 0,
+            irq_enabled: state[15] != 0,
+            irq_pending: state[16] != 0,
+            banks: BankTable::default(),
+        };
+        self.update_banks(cart);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod mmc3_tests {
+    use crate::mapper::mmc3::Mmc3;
+    use crate::mapper::{banked_cart, Mapper};
+    use tudelft_nes_ppu::Mirroring;
+
+    #[test]
+    fn test_mmc3_prg() {
+        let mut cart = banked_cart(4, 8 * 16384, 8192, 16 * 8192, 1024);
+        let mut mmc3 = Mmc3::new(&cart);
+        mmc3.cpu_write(&mut cart, 0x8000, 6);
+        mmc3.cpu_write(&mut cart, 0x8001, 3);
+        mmc3.cpu_write(&mut cart, 0x8000, 7);
+        mmc3.cpu_write(&mut cart, 0x8001, 5);
+        assert_eq!(mmc3.cpu_read(&cart, 0x8000), Some(3));
+        assert_eq!(mmc3.cpu_read(&cart, 0xA000), Some(5));
+        assert_eq!(mmc3.cpu_read(&cart, 0xC000), Some(14));
+        assert_eq!(mmc3.cpu_read(&cart, 0xE000), Some(15));
+
+        // Swap $8000 and $C000
+        mmc3.cpu_write(&mut cart, 0x8000, 0b0100_0000);
+        assert_eq!(mmc3.cpu_read(&cart, 0x8000), Some(14));
+        assert_eq!(mmc3.cpu_read(&cart, 0xC000), Some(3));
+        assert_eq!(mmc3.cpu_read(&cart, 0xE000), Some(15));
+    }
+
+    #[test]
+    fn test_mmc3_chr() {
+        let mut cart = banked_cart(4, 8 * 16384, 8192, 16 * 8192, 1024);
+        let mut mmc3 = Mmc3::new(&cart);
+        for (register, bank) in [(0, 8), (1, 11), (2, 20), (3, 21), (4, 22), (5, 23)] {
+            mmc3.cpu_write(&mut cart, 0x8000, register);
+            mmc3.cpu_write(&mut cart, 0x8001, bank);
+        }
+        assert_eq!(mmc3.ppu_read(&cart, 0x0000), 8);
+        assert_eq!(mmc3.ppu_read(&cart, 0x0400), 9);
+        assert_eq!(mmc3.ppu_read(&cart, 0x0800), 10); // Low bit of R1 is ignored
+        assert_eq!(mmc3.ppu_read(&cart, 0x0c00), 11);
+        assert_eq!(mmc3.ppu_read(&cart, 0x1000), 20);
+        assert_eq!(mmc3.ppu_read(&cart, 0x1c00), 23);
+
+        // CHR inversion
+        mmc3.cpu_write(&mut cart, 0x8000, 0b1000_0000);
+        assert_eq!(mmc3.ppu_read(&cart, 0x0000), 20);
+        assert_eq!(mmc3.ppu_read(&cart, 0x1000), 8);
+    }
+
+    #[test]
+    fn test_mmc3_mirroring_and_ram() {
+        let mut cart = banked_cart(4, 8 * 16384, 8192, 16 * 8192, 1024);
+        let mut mmc3 = Mmc3::new(&cart);
+        assert_eq!(mmc3.mirroring(&cart), Mirroring::Vertical);
+        mmc3.cpu_write(&mut cart, 0xA000, 1);
+        assert_eq!(mmc3.mirroring(&cart), Mirroring::Horizontal);
+
+        mmc3.cpu_write(&mut cart, 0x6000, 0x12);
+        assert_eq!(mmc3.cpu_read(&cart, 0x6000), Some(0x12));
+        mmc3.cpu_write(&mut cart, 0xA001, 0b1100_0000); // Write protect
+        mmc3.cpu_write(&mut cart, 0x6000, 0x34);
+        assert_eq!(mmc3.cpu_read(&cart, 0x6000), Some(0x12));
+        mmc3.cpu_write(&mut cart, 0xA001, 0); // Disable
+        assert_eq!(mmc3.cpu_read(&cart, 0x6000), None);
+    }
+
+    #[test]
+    fn test_mmc3_irq() {
+        let mut cart = banked_cart(4, 8 * 16384, 8192, 16 * 8192, 1024);
+        let mut mmc3 = Mmc3::new(&cart);
+        mmc3.cpu_write(&mut cart, 0xC000, 2); // Latch
+        mmc3.cpu_write(&mut cart, 0xC001, 0); // Reload
+        mmc3.cpu_write(&mut cart, 0xE001, 0); // Enable
+
+        mmc3.notify_scanline(); // Reload to 2
+        assert!(!mmc3.irq());
+        mmc3.notify_scanline();
+        assert!(!mmc3.irq());
+        mmc3.notify_scanline();
+        assert!(mmc3.irq());
+
+        // Acknowledge
+        mmc3.cpu_write(&mut cart, 0xE000, 0);
+        assert!(!mmc3.irq());
+        mmc3.cpu_write(&mut cart, 0xE001, 0);
+        mmc3.notify_scanline(); // Reload to 2
+        assert!(!mmc3.irq());
+
+        let state = mmc3.save_state();
+        let mut restored = Mmc3::new(&cart);
+        restored.load_state(&cart, &state).unwrap();
+        assert_eq!(restored, mmc3);
+    }
+}
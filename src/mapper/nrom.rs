@@ -0,0 +1,120 @@
+//! This module provides the NROM board (mapper 0), which has no registers and at most 32 kB of PRG ROM.
+
+use crate::cartridge::RomError;
+use crate::mapper::{read_chr, read_prg_ram, write_chr, write_prg_ram, Mapper, StateError};
+use crate::Cartridge;
+use tudelft_nes_ppu::Mirroring;
+
+/// Simple mapper which maps the logical address to the same physical address.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Nrom;
+
+impl Nrom {
+    /// Creates the mapper for a cartridge.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, used to check the prg rom size.
+    ///
+    /// # Return
+    /// * `Result<Nrom, RomError>` - The mapper, or an error if the PRG ROM does not fit on the board.
+    pub fn new(cartridge: &Cartridge) -> Result<Nrom, RomError> {
+        if cartridge.prg_rom_size_in_16kb > 2 {
+            return Err(RomError::OversizedNrom {
+                prg_rom_size_in_16kb: cartridge.prg_rom_size_in_16kb,
+            });
+        }
+        Ok(Nrom)
+    }
+}
+
+impl Mapper for Nrom {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            return read_prg_ram(cart, addr);
+        }
+        // 16 kB games are mirrored at $C000
+        Some(cart.prg_rom_data[(addr - 0x8000) as usize % cart.prg_rom_data.len()])
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        //You can't write to read-only memory
+        if addr < 0x8000 {
+            write_prg_ram(cart, addr, data);
+        }
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        read_chr(cart, addr as usize)
+    }
+
+    fn ppu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        write_chr(cart, addr as usize, data);
+    }
+
+    fn mirroring(&self, cart: &Cartridge) -> Mirroring {
+        cart.mirroring()
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn load_state(&mut self, _cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        if !state.is_empty() {
+            return Err(StateError {
+                expected: 0,
+                found: state.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod nrom_tests {
+    use crate::mapper::nrom::Nrom;
+    use crate::mapper::Mapper;
+    use crate::Cartridge;
+
+    #[test]
+    fn test_nrom() {
+        let mut cart = Cartridge::default();
+        cart.prg_rom_data[0x0123] = 0x45;
+        cart.chr_rom_data[0x1abc] = 0x89;
+        let mut mapper0 = Nrom::new(&cart).unwrap();
+        assert_eq!(mapper0.cpu_read(&cart, 0x8123), Some(0x45));
+        assert_eq!(mapper0.cpu_read(&cart, 0xC123), Some(0x45));
+        assert_eq!(mapper0.ppu_read(&cart, 0x1abc), 0x89);
+        mapper0.ppu_write(&mut cart, 0x1abc, 0x00); // CHR ROM is not writable
+        assert_eq!(mapper0.ppu_read(&cart, 0x1abc), 0x89);
+        mapper0.cpu_write(&mut cart, 0x8123, 0x00);
+        assert_eq!(mapper0.cpu_read(&cart, 0x8123), Some(0x45));
+
+        // Make cartridge with 32 kb
+        let mut cart_32kb = Cartridge {
+            prg_rom_size_in_16kb: 2,
+            prg_rom_data: vec![0; 0x8000],
+            ..Default::default()
+        };
+        cart_32kb.prg_rom_data[0x4123] = 0x67;
+        let mapper0_32kb = Nrom::new(&cart_32kb).unwrap();
+        assert_eq!(mapper0_32kb.cpu_read(&cart_32kb, 0x8123), Some(0x00));
+        assert_eq!(mapper0_32kb.cpu_read(&cart_32kb, 0xC123), Some(0x67));
+    }
+
+    #[test]
+    fn test_nrom_chr_ram() {
+        let mut cart = Cartridge {
+            chr_rom_size_in_8kb: 0,
+            chr_ram_size: 8192,
+            chr_rom_data: Vec::new(),
+            chr_ram: vec![0; 8192],
+            ..Default::default()
+        };
+        let mut mapper0 = Nrom::new(&cart).unwrap();
+        mapper0.ppu_write(&mut cart, 0x1abc, 0x89);
+        assert_eq!(mapper0.ppu_read(&cart, 0x1abc), 0x89);
+        assert_eq!(cart.chr_ram[0x1abc], 0x89);
+    }
+}
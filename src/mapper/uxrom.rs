@@ -0,0 +1,142 @@
+//! This module provides the UxROM boards (mapper 2), which switch a 16 kB PRG bank at $8000 and use CHR RAM.
+
+use crate::mapper::{
+    bus_conflict, read_prg_ram, state_registers, write_prg_ram, BankTable, Mapper, StateError,
+};
+use crate::Cartridge;
+use tudelft_nes_ppu::Mirroring;
+
+/// UNROM/UOROM mapper with a switchable bank at $8000 and the last bank fixed at $C000.
+#[derive(PartialEq, Eq, Debug, Default)]
+pub struct Uxrom {
+    /// 16 kB PRG bank mapped to $8000-$BFFF.
+    pub prg_bank: u8,
+    /// Offsets of the mapped banks.
+    pub banks: BankTable,
+}
+
+impl Uxrom {
+    /// Creates the mapper for a cartridge in its power-on state.
+    ///
+    /// # Arguments
+    ///
+    /// * `cartridge` - Borrowed instance of `Cartridge`, used for the position of the last bank.
+    ///
+    /// # Return
+    /// * `Uxrom` - The mapper.
+    pub fn new(cartridge: &Cartridge) -> Uxrom {
+        let mut uxrom = Uxrom::default();
+        uxrom.update_banks(cartridge);
+        uxrom
+    }
+
+    /// Rebuilds the bank table after the bank register has changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `cart` - Borrowed instance of cartridge.
+    ///
+    /// Nothing is returned.
+    fn update_banks(&mut self, cart: &Cartridge) {
+        let last_bank = (cart.prg_rom_data.len() / 16384).max(1) - 1;
+        self.banks
+            .map_prg(cart, 0x8000, 16384, self.prg_bank as usize);
+        self.banks.map_prg(cart, 0xc000, 16384, last_bank);
+        self.banks.map_chr(cart, 0x0000, 8192, 0);
+    }
+}
+
+impl Mapper for Uxrom {
+    fn cpu_read(&self, cart: &Cartridge, addr: u16) -> Option<u8> {
+        if addr < 0x8000 {
+            return read_prg_ram(cart, addr);
+        }
+        Some(self.banks.read_prg(cart, addr))
+    }
+
+    fn cpu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        if addr < 0x8000 {
+            write_prg_ram(cart, addr, data);
+            return;
+        }
+        self.prg_bank = bus_conflict(cart, self.banks.read_prg(cart, addr), data);
+        self.update_banks(cart);
+    }
+
+    fn ppu_read(&self, cart: &Cartridge, addr: u16) -> u8 {
+        self.banks.read_chr(cart, addr)
+    }
+
+    fn ppu_write(&mut self, cart: &mut Cartridge, addr: u16, data: u8) {
+        self.banks.write_chr(cart, addr, data);
+    }
+
+    fn mirroring(&self, cart: &Cartridge) -> Mirroring {
+        cart.mirroring()
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        vec![self.prg_bank]
+    }
+
+    fn load_state(&mut self, cart: &Cartridge, state: &[u8]) -> Result<(), StateError> {
+        let [prg_bank] = state_registers(state)?;
+        self.prg_bank = prg_bank;
+        self.update_banks(cart);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod uxrom_tests {
+    use crate::mapper::uxrom::Uxrom;
+    use crate::mapper::{banked_cart, Mapper};
+    use crate::Cartridge;
+
+    #[test]
+    fn test_uxrom_prg() {
+        let mut cart = banked_cart(2, 8 * 16384, 16384, 0, 1);
+        let mut uxrom = Uxrom::new(&cart);
+        assert_eq!(uxrom.cpu_read(&cart, 0x8000), Some(0));
+        assert_eq!(uxrom.cpu_read(&cart, 0xC000), Some(7));
+
+        uxrom.cpu_write(&mut cart, 0x8000, 5);
+        assert_eq!(uxrom.cpu_read(&cart, 0xBFFF), Some(5));
+        assert_eq!(uxrom.cpu_read(&cart, 0xFFFF), Some(7));
+
+        // Bank numbers wrap around the PRG ROM size
+        uxrom.cpu_write(&mut cart, 0xF000, 11);
+        assert_eq!(uxrom.cpu_read(&cart, 0x8000), Some(3));
+    }
+
+    #[test]
+    fn test_uxrom_small_prg() {
+        // NES 2.0 exponent sizes allow less PRG ROM than one bank, which is mirrored
+        let cart = banked_cart(2, 8192, 256, 0, 1);
+        let uxrom = Uxrom::new(&cart);
+        assert_eq!(uxrom.cpu_read(&cart, 0x8100), Some(1));
+        assert_eq!(uxrom.cpu_read(&cart, 0xe100), Some(1));
+    }
+
+    #[test]
+    fn test_uxrom_bus_conflicts() {
+        let mut cart = Cartridge {
+            bus_conflicts: true,
+            ..banked_cart(2, 8 * 16384, 16384, 0, 1)
+        };
+        let mut uxrom = Uxrom::new(&cart);
+        // $C000 holds 7, so the ROM drives 0b111 onto the bus
+        uxrom.cpu_write(&mut cart, 0xC000, 0b1110);
+        assert_eq!(uxrom.prg_bank, 0b0110);
+        assert_eq!(uxrom.cpu_read(&cart, 0x8000), Some(6));
+    }
+
+    #[test]
+    fn test_uxrom_chr_ram() {
+        let mut cart = banked_cart(2, 2 * 16384, 16384, 0, 1);
+        let mut uxrom = Uxrom::new(&cart);
+        uxrom.ppu_write(&mut cart, 0x1234, 0x56);
+        assert_eq!(uxrom.ppu_read(&cart, 0x1234), 0x56);
+        assert_eq!(cart.chr_ram[0x1234], 0x56);
+    }
+}
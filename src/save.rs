@@ -0,0 +1,122 @@
+//! This module stores the battery-backed memory of a cartridge in a `.sav` file next to the ROM, so that saved games survive a restart.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use tudelft_nes_ppu::CPU_FREQ;
+
+/// Number of CPU cycles between two flushes of the battery-backed memory, about one second.
+const FLUSH_INTERVAL: u32 = CPU_FREQ as u32;
+
+/// A `.sav` file holding the battery-backed memory of a cartridge.
+#[derive(PartialEq, Eq, Debug)]
+pub struct SaveFile {
+    /// Location of the save file.
+    pub path: PathBuf,
+    /// Contents of the file as last read or written, used to skip writes when nothing changed.
+    stored: Vec<u8>,
+    /// CPU cycles left until the next periodic flush.
+    cycles_until_flush: u32,
+}
+
+impl SaveFile {
+    /// Creates the save file belonging to a ROM, it has the same name with the extension `.sav`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rom_path` - Path to the `.nes` file.
+    ///
+    /// # Return
+    /// * `SaveFile` - The save file, which does not have to exist yet.
+    pub fn for_rom(rom_path: &Path) -> SaveFile {
+        SaveFile {
+            path: rom_path.with_extension("sav"),
+            stored: Vec::new(),
+            cycles_until_flush: FLUSH_INTERVAL,
+        }
+    }
+
+    /// Reads the saved memory.
+    ///
+    /// # Return
+    /// * `io::Result<Option<Vec<u8>>>` - The saved memory, `None` if there is no save file yet.
+    pub fn load(&mut self) -> io::Result<Option<Vec<u8>>> {
+        match fs::read(&self.path) {
+            Ok(data) => {
+                self.stored = data.clone();
+                Ok(Some(data))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the memory to the save file if it differs from what the file holds. The data is written to a temporary file first, so that a crash cannot leave a half-written save behind.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The battery-backed memory.
+    ///
+    /// # Return
+    /// * `io::Result<()>` - An error if the file could not be written.
+    pub fn flush(&mut self, data: &[u8]) -> io::Result<()> {
+        if data == self.stored {
+            return Ok(());
+        }
+        let temporary = self.path.with_extension("sav.tmp");
+        fs::write(&temporary, data)?;
+        fs::rename(&temporary, &self.path)?;
+        self.stored = data.to_vec();
+        Ok(())
+    }
+
+    /// Counts a CPU cycle.
+    ///
+    /// # Return
+    /// * `bool` - Whether it is time for a periodic flush.
+    pub fn tick(&mut self) -> bool {
+        self.cycles_until_flush -= 1;
+        if self.cycles_until_flush == 0 {
+            self.cycles_until_flush = FLUSH_INTERVAL;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod save_tests {
+    use crate::save::{SaveFile, FLUSH_INTERVAL};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_save_file() {
+        let dir = std::env::temp_dir().join(format!("nes-save-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut save_file = SaveFile::for_rom(&dir.join("game.nes"));
+        assert_eq!(save_file.path, dir.join("game.sav"));
+        assert_eq!(save_file.load().unwrap(), None);
+
+        save_file.flush(&[1, 2, 3]).unwrap();
+        assert_eq!(fs::read(&save_file.path).unwrap(), [1, 2, 3]);
+        assert!(!dir.join("game.sav.tmp").exists());
+
+        // Unchanged memory is not written again
+        fs::remove_file(&save_file.path).unwrap();
+        save_file.flush(&[1, 2, 3]).unwrap();
+        assert!(!save_file.path.exists());
+
+        save_file.flush(&[4, 5]).unwrap();
+        let mut reloaded = SaveFile::for_rom(&dir.join("game.nes"));
+        assert_eq!(reloaded.load().unwrap(), Some(vec![4, 5]));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_flush_interval() {
+        let mut save_file = SaveFile::for_rom(Path::new("game.nes"));
+        assert_eq!((0..FLUSH_INTERVAL).filter(|_| save_file.tick()).count(), 1);
+        assert!(!save_file.tick());
+    }
+}
@@ -0,0 +1,111 @@
+//! This module records audio to WAV files with 16-bit PCM samples.
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Size of the RIFF and format headers before the sample data.
+const HEADER_SIZE: u32 = 44;
+
+/// Writes mono samples to a WAV file. The header is kept up to date after every write, so the file can be played even if the emulator is closed without finishing it.
+pub struct WavWriter<W: Write + Seek> {
+    /// Destination of the file.
+    writer: W,
+    /// Number of bytes of sample data that were written.
+    data_len: u32,
+}
+
+impl WavWriter<BufWriter<File>> {
+    /// Creates a WAV file, replacing an existing one.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file.
+    /// * `sample_rate` - Number of samples per second.
+    ///
+    /// # Return
+    /// * `io::Result<WavWriter<BufWriter<File>>>` - The writer, or an error if the file could not be created.
+    pub fn create(path: &Path, sample_rate: u32) -> io::Result<Self> {
+        WavWriter::new(BufWriter::new(File::create(path)?), sample_rate)
+    }
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    /// Writes the header of an empty WAV file.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Destination of the file.
+    /// * `sample_rate` - Number of samples per second.
+    ///
+    /// # Return
+    /// * `io::Result<WavWriter<W>>` - The writer, or an error if the header could not be written.
+    pub fn new(mut writer: W, sample_rate: u32) -> io::Result<Self> {
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(HEADER_SIZE - 8).to_le_bytes())?;
+        writer.write_all(b"WAVEfmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?; // Size of the format chunk
+        writer.write_all(&1u16.to_le_bytes())?; // PCM
+        writer.write_all(&1u16.to_le_bytes())?; // Mono
+        writer.write_all(&sample_rate.to_le_bytes())?;
+        writer.write_all(&(sample_rate * 2).to_le_bytes())?; // Bytes per second
+        writer.write_all(&2u16.to_le_bytes())?; // Bytes per sample
+        writer.write_all(&16u16.to_le_bytes())?; // Bits per sample
+        writer.write_all(b"data")?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.flush()?;
+        Ok(WavWriter {
+            writer,
+            data_len: 0,
+        })
+    }
+
+    /// Appends samples to the file and updates the sizes in the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Samples between -1.0 and 1.0, values outside are clipped.
+    ///
+    /// # Return
+    /// * `io::Result<()>` - An error if the file could not be written.
+    pub fn write_samples(&mut self, samples: &[f32]) -> io::Result<()> {
+        for &sample in samples {
+            let sample = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+            self.writer.write_all(&sample.to_le_bytes())?;
+        }
+        self.data_len += samples.len() as u32 * 2;
+
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.writer
+            .write_all(&(HEADER_SIZE - 8 + self.data_len).to_le_bytes())?;
+        self.writer.seek(SeekFrom::Start(HEADER_SIZE as u64 - 4))?;
+        self.writer.write_all(&self.data_len.to_le_bytes())?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod wav_tests {
+    use crate::wav::WavWriter;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_wav() {
+        let mut wav = WavWriter::new(Cursor::new(Vec::new()), 44100).unwrap();
+        wav.write_samples(&[0.0, 1.0]).unwrap();
+        wav.write_samples(&[-1.0, 2.0, -0.5]).unwrap();
+        let file = wav.writer.into_inner();
+
+        assert_eq!(file.len(), 44 + 5 * 2);
+        assert_eq!(&file[0..4], b"RIFF");
+        assert_eq!(file[4..8], (36u32 + 10).to_le_bytes());
+        assert_eq!(&file[8..16], b"WAVEfmt ");
+        assert_eq!(file[24..28], 44100u32.to_le_bytes());
+        assert_eq!(file[40..44], 10u32.to_le_bytes());
+        let samples: Vec<i16> = file[44..]
+            .chunks(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(samples, [0, 32767, -32767, 32767, -16383]);
+    }
+}